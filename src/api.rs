@@ -0,0 +1,329 @@
+//! Optional HTTP API: serves the shared observer snapshot and accepts run controls.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anyhow::Context;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::{Notify, mpsc, watch};
+
+use crate::simulation::{
+    AllNationMetrics, ArmySnapshot, Bloc, DiplomaticSnapshot, HexGridSnapshot, Intervention,
+    MacroState, Markets, Nation, NationCivState, NationMetrics, NationSupply, ObserverSnapshot,
+    TradeRoute, Treasury, War, WorldEvent,
+};
+use crate::{MAX_YEARS_PER_TICK, MIN_TICK_DURATION, MIN_YEARS_PER_TICK};
+
+/// Messages buffered per stream client before it is told it lagged.
+const FEED_CAPACITY: usize = 1_024;
+
+/// Handles shared with the TUI: the snapshot it renders and the watch
+/// channels it drives the simulation task with.
+#[derive(Clone)]
+pub struct ApiState {
+    pub observer: Arc<RwLock<ObserverSnapshot>>,
+    pub tick_duration_tx: watch::Sender<Duration>,
+    pub timescale_tx: watch::Sender<f64>,
+    pub pause_tx: watch::Sender<bool>,
+    pub intervention_tx: mpsc::UnboundedSender<Intervention>,
+}
+
+impl ApiState {
+    fn snapshot(&self) -> ObserverSnapshot {
+        self.observer
+            .read()
+            .expect("Observer lock is poisoned")
+            .clone()
+    }
+
+    fn control(&self) -> ControlReport {
+        ControlReport {
+            paused: *self.pause_tx.borrow(),
+            tick_ms: self.tick_duration_tx.borrow().as_millis() as u64,
+            years_per_tick: *self.timescale_tx.borrow(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ControlReport {
+    paused: bool,
+    tick_ms: u64,
+    years_per_tick: f64,
+}
+
+/// Partial update; omitted fields are left alone.
+#[derive(Debug, Deserialize)]
+struct ControlRequest {
+    paused: Option<bool>,
+    tick_ms: Option<u64>,
+    years_per_tick: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct NationReport {
+    nation: Nation,
+    metrics: NationMetrics,
+    civ_state: Option<NationCivState>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventQuery {
+    /// Only return events recorded after this tick.
+    since: Option<u64>,
+}
+
+type ApiError = (StatusCode, String);
+
+/// Template receiver for the live feed; each stream client resubscribes from it.
+type Feed = Arc<broadcast::Receiver<Event>>;
+
+/// Binds the listener up front so a bad address fails before the TUI starts.
+pub async fn bind(addr: SocketAddr) -> anyhow::Result<TcpListener> {
+    TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("failed to bind API server on {addr}"))
+}
+
+/// Serves the API until `shutdown` fires. `ticks` is signalled by the
+/// simulation task after every tick and drives the live stream.
+pub async fn serve(
+    listener: TcpListener,
+    state: ApiState,
+    ticks: watch::Receiver<()>,
+    shutdown: Arc<Notify>,
+) -> anyhow::Result<()> {
+    let (feed_tx, feed_rx) = broadcast::channel(FEED_CAPACITY);
+    tokio::spawn(run_feed(state.observer.clone(), ticks, feed_tx));
+    axum::serve(listener, router(state, Arc::new(feed_rx)))
+        .with_graceful_shutdown(async move { shutdown.notified().await })
+        .await
+        .context("API server failed")
+}
+
+fn router(state: ApiState, feed: Feed) -> Router {
+    Router::new()
+        .route("/api/snapshot", get(snapshot))
+        .route("/api/nations", get(nations))
+        .route("/api/nations/:nation", get(nation))
+        .route("/api/grid", get(grid))
+        .route("/api/armies", get(armies))
+        .route("/api/wars", get(wars))
+        .route("/api/blocs", get(blocs))
+        .route("/api/diplomacy", get(diplomacy))
+        .route("/api/markets", get(markets))
+        .route("/api/trade-routes", get(trade_routes))
+        .route("/api/supply", get(supply))
+        .route("/api/treasuries", get(treasuries))
+        .route("/api/economies", get(economies))
+        .route("/api/events", get(events))
+        .route("/api/control", get(control).post(update_control))
+        .route("/api/interventions", post(intervene))
+        .route("/api/stream", get(stream))
+        .layer(Extension(feed))
+        .with_state(state)
+}
+
+/// Turns published snapshots into stream messages: one `world_event` per new
+/// log entry, then a `delta` for the tick. Ends when the simulation task drops
+/// its tick sender, which closes every open stream.
+async fn run_feed(
+    observer: Arc<RwLock<ObserverSnapshot>>,
+    mut ticks: watch::Receiver<()>,
+    feed: broadcast::Sender<Event>,
+) {
+    let read = || observer.read().expect("Observer lock is poisoned").clone();
+    let mut previous = read();
+    while ticks.changed().await.is_ok() {
+        let current = read();
+        if current.tick == previous.tick {
+            continue;
+        }
+        // A quickload can move the clock backwards; its log is history, not news.
+        let since = previous.tick.min(current.tick);
+        for event in current.events.iter().filter(|event| event.tick > since) {
+            if let Ok(message) = Event::default().event("world_event").json_data(event) {
+                feed.send(message).ok();
+            }
+        }
+        if let Ok(message) = Event::default()
+            .event("delta")
+            .json_data(current.delta_since(&previous))
+        {
+            feed.send(message).ok();
+        }
+        previous = current;
+    }
+}
+
+async fn stream(
+    Extension(feed): Extension<Feed>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = futures_util::stream::unfold(feed.resubscribe(), |mut rx| async move {
+        let message = match rx.recv().await {
+            Ok(message) => message,
+            // The client missed messages; tell it so it can refetch /api/snapshot.
+            Err(RecvError::Lagged(skipped)) => {
+                Event::default().event("lagged").data(skipped.to_string())
+            }
+            Err(RecvError::Closed) => return None,
+        };
+        Some((Ok(message), rx))
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+async fn snapshot(State(state): State<ApiState>) -> Json<ObserverSnapshot> {
+    Json(state.snapshot())
+}
+
+async fn nations(State(state): State<ApiState>) -> Json<AllNationMetrics> {
+    Json(state.snapshot().all_metrics)
+}
+
+async fn nation(
+    State(state): State<ApiState>,
+    Path(nation): Path<Nation>,
+) -> Result<Json<NationReport>, ApiError> {
+    let snapshot = state.snapshot();
+    let metrics = snapshot.all_metrics.0.get(&nation).cloned().ok_or((
+        StatusCode::NOT_FOUND,
+        format!("no metrics for {}", snapshot.nations.name(nation)),
+    ))?;
+    Ok(Json(NationReport {
+        nation,
+        metrics,
+        civ_state: snapshot.civ_state.0.get(&nation).cloned(),
+    }))
+}
+
+async fn grid(State(state): State<ApiState>) -> Json<HexGridSnapshot> {
+    Json(state.snapshot().grid)
+}
+
+async fn armies(State(state): State<ApiState>) -> Json<Vec<ArmySnapshot>> {
+    Json(state.snapshot().armies)
+}
+
+async fn wars(State(state): State<ApiState>) -> Json<Vec<War>> {
+    Json(state.snapshot().wars)
+}
+
+async fn blocs(State(state): State<ApiState>) -> Json<Vec<Bloc>> {
+    Json(state.snapshot().blocs)
+}
+
+async fn diplomacy(State(state): State<ApiState>) -> Json<DiplomaticSnapshot> {
+    Json(state.snapshot().diplomacy)
+}
+
+async fn markets(State(state): State<ApiState>) -> Json<Markets> {
+    Json(state.snapshot().markets)
+}
+
+async fn trade_routes(State(state): State<ApiState>) -> Json<Vec<TradeRoute>> {
+    Json(state.snapshot().trade_routes)
+}
+
+async fn supply(State(state): State<ApiState>) -> Json<BTreeMap<Nation, NationSupply>> {
+    Json(state.snapshot().supply)
+}
+
+async fn treasuries(State(state): State<ApiState>) -> Json<BTreeMap<Nation, Treasury>> {
+    Json(state.snapshot().treasuries)
+}
+
+async fn economies(State(state): State<ApiState>) -> Json<BTreeMap<Nation, MacroState>> {
+    Json(state.snapshot().economies)
+}
+
+async fn events(
+    State(state): State<ApiState>,
+    Query(query): Query<EventQuery>,
+) -> Json<Vec<WorldEvent>> {
+    let mut events = state.snapshot().events;
+    if let Some(since) = query.since {
+        events.retain(|event| event.tick > since);
+    }
+    Json(events)
+}
+
+async fn control(State(state): State<ApiState>) -> Json<ControlReport> {
+    Json(state.control())
+}
+
+async fn update_control(
+    State(state): State<ApiState>,
+    Json(request): Json<ControlRequest>,
+) -> Result<Json<ControlReport>, ApiError> {
+    // Validate everything before applying anything so a bad field is a no-op.
+    let tick_duration = request.tick_ms.map(Duration::from_millis);
+    if tick_duration.is_some_and(|duration| duration < MIN_TICK_DURATION) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("tick_ms must be at least {}", MIN_TICK_DURATION.as_millis()),
+        ));
+    }
+    if request
+        .years_per_tick
+        .is_some_and(|years| !(MIN_YEARS_PER_TICK..=MAX_YEARS_PER_TICK).contains(&years))
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("years_per_tick must be between {MIN_YEARS_PER_TICK} and {MAX_YEARS_PER_TICK}"),
+        ));
+    }
+
+    if let Some(paused) = request.paused {
+        state.pause_tx.send(paused).ok();
+    }
+    if let Some(duration) = tick_duration {
+        state.tick_duration_tx.send(duration).ok();
+    }
+    if let Some(years) = request.years_per_tick {
+        state.timescale_tx.send(years).ok();
+    }
+    Ok(Json(state.control()))
+}
+
+/// Queues an observer intervention for the next tick and echoes it back.
+async fn intervene(
+    State(state): State<ApiState>,
+    Json(intervention): Json<Intervention>,
+) -> Result<(StatusCode, Json<Intervention>), ApiError> {
+    let snapshot = state.snapshot();
+    if let Some(nation) = intervention
+        .nations()
+        .into_iter()
+        .find(|nation| !snapshot.all_metrics.0.contains_key(nation))
+    {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("no nation {}", snapshot.nations.name(nation)),
+        ));
+    }
+    if let Some(problem) = intervention.problem(&snapshot.tech_tree, &snapshot.all_metrics) {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, problem));
+    }
+    state
+        .intervention_tx
+        .send(intervention.clone())
+        .map_err(|_| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "the simulation has stopped".to_string(),
+            )
+        })?;
+    Ok((StatusCode::ACCEPTED, Json(intervention)))
+}
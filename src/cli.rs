@@ -0,0 +1,160 @@
+//! Command-line options for the simulator binary.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use anyhow::{Context, bail};
+
+use crate::simulation::{AiProfile, MAX_NATIONS, MIN_NATIONS};
+
+const USAGE: &str = "\
+Usage: mk3_rust_studio [OPTIONS]
+
+Options:
+  --headless        Run without the TUI and print the final snapshot as JSON
+  --ticks <N>       Maximum ticks for a headless run (default 2000)
+  --output <PATH>   Write the headless snapshot to PATH instead of stdout
+  --radius <N>      Hex grid radius (default 24)
+  --seed <N>        Master seed for a reproducible run (random if omitted)
+  --world <PATH>    Load world metadata from a .toml, .ron or .json file
+  --nations <N>     Start with N nations from the built-in roster (2-20)
+  --ai <PROFILE>    Give every nation one AI profile: conqueror, scholar, diplomat or builder
+                    (by default each nation's scenario entry, or one drawn from the seed)
+  --play <NATION>   Command NATION yourself (by name); the others keep their AI
+  --export-world <PATH>
+                    Write the built-in world metadata to PATH as a template and exit
+  --load <PATH>     Resume from a save file (ignores --radius, --seed, --world, --nations and --ai)
+  --save <PATH>     Save the full state to PATH when a headless run ends
+  --serve <ADDR>    Serve the HTTP API on ADDR (e.g. 127.0.0.1:8080) alongside the TUI
+  -h, --help        Show this help";
+
+#[derive(Debug, Clone)]
+pub struct CliOptions {
+    pub headless: bool,
+    pub ticks: u64,
+    pub output: Option<PathBuf>,
+    pub grid_radius: i32,
+    pub seed: Option<u64>,
+    pub world: Option<PathBuf>,
+    pub nations: Option<usize>,
+    pub ai: Option<AiProfile>,
+    pub play: Option<String>,
+    pub export_world: Option<PathBuf>,
+    pub load: Option<PathBuf>,
+    pub save: Option<PathBuf>,
+    pub serve: Option<SocketAddr>,
+}
+
+impl Default for CliOptions {
+    fn default() -> Self {
+        Self {
+            headless: false,
+            ticks: 2_000,
+            output: None,
+            grid_radius: 24,
+            seed: None,
+            world: None,
+            nations: None,
+            ai: None,
+            play: None,
+            export_world: None,
+            load: None,
+            save: None,
+            serve: None,
+        }
+    }
+}
+
+impl CliOptions {
+    /// Parses the process arguments, printing usage and exiting on `--help`.
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--headless" => options.headless = true,
+                "--ticks" => {
+                    options.ticks = parse_value(&arg, args.next())?;
+                }
+                "--output" => {
+                    let path: String = parse_value(&arg, args.next())?;
+                    options.output = Some(PathBuf::from(path));
+                }
+                "--radius" => {
+                    options.grid_radius = parse_value(&arg, args.next())?;
+                    if options.grid_radius < 4 {
+                        bail!("--radius must be at least 4");
+                    }
+                }
+                "--seed" => {
+                    options.seed = Some(parse_value(&arg, args.next())?);
+                }
+                "--world" => {
+                    let path: String = parse_value(&arg, args.next())?;
+                    options.world = Some(PathBuf::from(path));
+                }
+                "--nations" => {
+                    let count = parse_value(&arg, args.next())?;
+                    if !(MIN_NATIONS..=MAX_NATIONS).contains(&count) {
+                        bail!("--nations must be between {MIN_NATIONS} and {MAX_NATIONS}");
+                    }
+                    options.nations = Some(count);
+                }
+                "--ai" => {
+                    let name: String = parse_value(&arg, args.next())?;
+                    let Some(profile) = AiProfile::from_name(&name) else {
+                        bail!(
+                            "unknown AI profile `{name}`; expected conqueror, scholar, diplomat or builder"
+                        );
+                    };
+                    options.ai = Some(profile);
+                }
+                "--play" => {
+                    options.play = Some(parse_value(&arg, args.next())?);
+                }
+                "--export-world" => {
+                    let path: String = parse_value(&arg, args.next())?;
+                    options.export_world = Some(PathBuf::from(path));
+                }
+                "--load" => {
+                    let path: String = parse_value(&arg, args.next())?;
+                    options.load = Some(PathBuf::from(path));
+                }
+                "--save" => {
+                    let path: String = parse_value(&arg, args.next())?;
+                    options.save = Some(PathBuf::from(path));
+                }
+                "--serve" => {
+                    options.serve = Some(parse_value(&arg, args.next())?);
+                }
+                "-h" | "--help" => {
+                    println!("{USAGE}");
+                    std::process::exit(0);
+                }
+                other => bail!("unknown argument `{other}`\n\n{USAGE}"),
+            }
+        }
+        if options.headless && options.serve.is_some() {
+            bail!("--serve needs the TUI run loop and cannot be combined with --headless");
+        }
+        if options.headless && options.play.is_some() {
+            bail!("--play needs the TUI and cannot be combined with --headless");
+        }
+        Ok(options)
+    }
+}
+
+fn parse_value<T>(flag: &str, value: Option<String>) -> anyhow::Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let value = value.with_context(|| format!("{flag} expects a value"))?;
+    value
+        .parse()
+        .with_context(|| format!("invalid value `{value}` for {flag}"))
+}
@@ -0,0 +1,58 @@
+//! Headless batch runner: ticks the simulation without the TUI and dumps the final snapshot.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::sync::{Arc, RwLock};
+
+use anyhow::Context;
+
+use crate::cli::CliOptions;
+use crate::simulation::{ObserverSnapshot, SimulationConfig, SimulationWorld, WorldMetadata};
+
+/// Runs the world as fast as possible for `options.ticks` ticks or until the
+/// science victory track finishes, then writes the final snapshot as JSON.
+pub fn run(
+    config: SimulationConfig,
+    metadata: WorldMetadata,
+    options: &CliOptions,
+) -> anyhow::Result<()> {
+    let observer = Arc::new(RwLock::new(ObserverSnapshot::default()));
+    let mut simulation = match &options.load {
+        Some(path) => SimulationWorld::load_with_observer(path, observer.clone())?,
+        None => SimulationWorld::with_metadata(config, metadata, observer.clone()),
+    };
+
+    let mut ticks_run = 0;
+    while ticks_run < options.ticks && !simulation.is_finished() {
+        simulation.tick();
+        ticks_run += 1;
+    }
+
+    if let Some(path) = &options.save {
+        simulation.save(path)?;
+    }
+
+    let snapshot = observer.read().expect("Observer lock is poisoned").clone();
+    match &options.output {
+        Some(path) => {
+            let file = File::create(path)
+                .with_context(|| format!("failed to create {}", path.display()))?;
+            let mut writer = BufWriter::new(file);
+            serde_json::to_writer_pretty(&mut writer, &snapshot)?;
+            writer.flush()?;
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            serde_json::to_writer_pretty(&mut stdout, &snapshot)?;
+            writeln!(stdout)?;
+        }
+    }
+
+    eprintln!(
+        "headless run stopped after {} ticks (seed: {}, finished: {})",
+        ticks_run,
+        snapshot.seed,
+        simulation.is_finished()
+    );
+    Ok(())
+}
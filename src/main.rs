@@ -1,5 +1,6 @@
 use std::io::{self, stdout};
 use std::panic;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
@@ -9,13 +10,37 @@ use crossterm::{
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{Terminal, prelude::*};
-use tokio::sync::{Notify, watch};
+use tokio::sync::{Notify, mpsc, watch};
 
+mod api;
+mod cli;
+mod headless;
 mod simulation;
 mod ui;
 
-use simulation::{AxialCoord, ObserverSnapshot, SimulationConfig, SimulationWorld};
-use ui::{ControlState, MapOverlay, PresetStatus};
+use anyhow::Context;
+use cli::CliOptions;
+use simulation::{
+    AxialCoord, Intervention, NationProfile, ObserverSnapshot, PlayerCommand, SimulationConfig,
+    SimulationWorld, WorldMetadata,
+};
+use ui::{
+    CommandRow, ControlState, InterventionRow, MapOverlay, PresetStatus, next_metric, scale_metric,
+};
+
+/// Bounds shared by the TUI controls and the HTTP API.
+const MIN_TICK_DURATION: Duration = Duration::from_millis(1);
+const MIN_YEARS_PER_TICK: f64 = 1_000.0;
+const MAX_YEARS_PER_TICK: f64 = 50_000_000_000.0;
+
+/// Where the F5/F9 quicksave slot lives, relative to the working directory.
+const QUICKSAVE_PATH: &str = "quicksave.json";
+
+/// Save/load work handed from the TUI to the simulation task, which owns the world.
+enum PersistRequest {
+    Save(PathBuf),
+    Load(PathBuf),
+}
 
 #[derive(Clone, Copy)]
 struct SpeedPreset {
@@ -65,19 +90,46 @@ const SPEED_PRESETS: [SpeedPreset; 4] = [
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let options = CliOptions::from_env()?;
+    if let Some(path) = &options.export_world {
+        WorldMetadata::default().export(path)?;
+        println!("wrote built-in world metadata to {}", path.display());
+        return Ok(());
+    }
+    let mut metadata = match &options.world {
+        Some(path) => WorldMetadata::from_file(path)?,
+        None => WorldMetadata::default(),
+    };
+    if let Some(count) = options.nations {
+        metadata.nations = NationProfile::roster(count);
+    }
+    if let Some(profile) = options.ai {
+        for nation in &mut metadata.nations {
+            nation.ai = Some(profile);
+        }
+    }
+
     // Simulation Setup
     let config = SimulationConfig {
         tick_duration: Duration::from_millis(50),
-        grid_radius: 24,
+        grid_radius: options.grid_radius,
         years_per_tick: 1_000_000.0,
-        ..Default::default()
+        seed: options.seed.unwrap_or_else(rand::random),
     };
+    if options.headless {
+        return headless::run(config, metadata, &options);
+    }
     let initial_tick_duration = config.tick_duration;
     let initial_years_per_tick = config.years_per_tick;
 
     let (tick_duration_tx, mut tick_duration_rx) = watch::channel(initial_tick_duration);
     let (timescale_tx, mut timescale_rx) = watch::channel(initial_years_per_tick);
     let (pause_tx, mut pause_rx) = watch::channel(false);
+    let (persist_tx, mut persist_rx) = mpsc::unbounded_channel::<PersistRequest>();
+    let (persist_status_tx, persist_status_rx) = watch::channel::<Option<String>>(None);
+    let (command_tx, mut command_rx) = mpsc::unbounded_channel::<PlayerCommand>();
+    let (intervention_tx, mut intervention_rx) = mpsc::unbounded_channel::<Intervention>();
+    let (tick_tx, tick_rx) = watch::channel(());
     let mut active_preset: Option<char> = Some('2');
     let mut map_overlay = MapOverlay::Ownership;
     let mut selected_hex: Option<simulation::AxialCoord> = None;
@@ -87,11 +139,31 @@ async fn main() -> anyhow::Result<()> {
     let mut pinned_nation: Option<simulation::Nation> = None;
     let mut log_pin_selected = false;
     let mut focus_mode = false;
+    let mut command_open = false;
+    let mut command_row = 0;
+    let mut intervene_open = false;
+    let mut intervene_row = 0;
+    let mut intervene_metric = simulation::Metric::Economy;
 
     let observer = Arc::new(RwLock::new(ObserverSnapshot::default()));
     let shutdown_notify = Arc::new(Notify::new());
 
-    let mut simulation = SimulationWorld::with_observer(config, observer.clone());
+    let mut simulation = match &options.load {
+        Some(path) => SimulationWorld::load_with_observer(path, observer.clone())?,
+        None => SimulationWorld::with_metadata(config, metadata, observer.clone()),
+    };
+    simulation.set_timescale(initial_years_per_tick);
+    if let Some(name) = &options.play {
+        let nation = simulation
+            .nation_named(name)
+            .with_context(|| format!("no nation named `{name}` to play"))?;
+        simulation.command(PlayerCommand::TakeCommand {
+            nation: Some(nation),
+        });
+        pinned_nation = Some(nation);
+        command_open = true;
+    }
+    let observer_for_simulation = observer.clone();
     let notify_for_simulation = shutdown_notify.clone();
     let simulation_task = tokio::spawn(async move {
         let mut interval = tokio::time::interval(*tick_duration_rx.borrow());
@@ -101,6 +173,7 @@ async fn main() -> anyhow::Result<()> {
                 _ = interval.tick() => {
                     if !paused {
                         simulation.tick();
+                        tick_tx.send(()).ok();
                     }
                 },
                 result = tick_duration_rx.changed() => {
@@ -127,10 +200,52 @@ async fn main() -> anyhow::Result<()> {
                         break;
                     }
                 },
+                Some(command) = command_rx.recv() => simulation.command(command),
+                Some(intervention) = intervention_rx.recv() => simulation.intervene(intervention),
+                Some(request) = persist_rx.recv() => {
+                    let status = match request {
+                        PersistRequest::Save(path) => match simulation.save(&path) {
+                            Ok(()) => format!("Saved {}", path.display()),
+                            Err(err) => format!("Save failed: {err:#}"),
+                        },
+                        PersistRequest::Load(path) => match SimulationWorld::load_with_observer(
+                            &path,
+                            observer_for_simulation.clone(),
+                        ) {
+                            Ok(loaded) => {
+                                simulation = loaded;
+                                simulation.set_timescale(*timescale_rx.borrow());
+                                tick_tx.send(()).ok();
+                                format!("Loaded {}", path.display())
+                            }
+                            Err(err) => format!("Load failed: {err:#}"),
+                        },
+                    };
+                    persist_status_tx.send(Some(status)).ok();
+                },
                 _ = notify_for_simulation.notified() => break,
             }
         }
     });
+    let api_task = match options.serve {
+        Some(addr) => {
+            let listener = api::bind(addr).await?;
+            let state = api::ApiState {
+                observer: observer.clone(),
+                tick_duration_tx: tick_duration_tx.clone(),
+                timescale_tx: timescale_tx.clone(),
+                pause_tx: pause_tx.clone(),
+                intervention_tx: intervention_tx.clone(),
+            };
+            Some(tokio::spawn(api::serve(
+                listener,
+                state,
+                tick_rx,
+                shutdown_notify.clone(),
+            )))
+        }
+        None => None,
+    };
     let ctrlc_notify = shutdown_notify.clone();
     let ctrl_c_task = tokio::spawn(async move {
         let _ = tokio::signal::ctrl_c().await;
@@ -159,6 +274,12 @@ async fn main() -> anyhow::Result<()> {
             pinned_nation,
             log_pin_selected,
             focus_mode,
+            command_open,
+            command_row,
+            intervene_open,
+            intervene_row,
+            intervene_metric,
+            persistence_status: persist_status_rx.borrow().clone(),
         };
 
         terminal.draw(|frame| {
@@ -193,7 +314,7 @@ async fn main() -> anyhow::Result<()> {
                     }
                     KeyCode::Char('+') | KeyCode::Char('=') => {
                         let current_duration = *tick_duration_tx.borrow();
-                        let new_duration = (current_duration / 2).max(Duration::from_millis(1));
+                        let new_duration = (current_duration / 2).max(MIN_TICK_DURATION);
                         active_preset = None;
                         tick_duration_tx.send(new_duration).ok();
                     }
@@ -205,13 +326,13 @@ async fn main() -> anyhow::Result<()> {
                     }
                     KeyCode::Char('<') | KeyCode::Char(',') => {
                         let current = *timescale_tx.borrow();
-                        let new_scale = (current / 2.0).max(1_000.0);
+                        let new_scale = (current / 2.0).max(MIN_YEARS_PER_TICK);
                         active_preset = None;
                         timescale_tx.send(new_scale).ok();
                     }
                     KeyCode::Char('>') | KeyCode::Char('.') => {
                         let current = *timescale_tx.borrow();
-                        let new_scale = (current * 2.0).min(50_000_000_000.0);
+                        let new_scale = (current * 2.0).min(MAX_YEARS_PER_TICK);
                         active_preset = None;
                         timescale_tx.send(new_scale).ok();
                     }
@@ -242,6 +363,87 @@ async fn main() -> anyhow::Result<()> {
                     KeyCode::Char('v') | KeyCode::Char('V') => {
                         focus_mode = !focus_mode;
                     }
+                    KeyCode::Char('t') | KeyCode::Char('T') => {
+                        // Take command of the selected nation, or hand the
+                        // played one back to its leaders.
+                        let played = observer.read().ok().and_then(|s| s.player.nation);
+                        let nation = match selected_owner.or(pinned_nation) {
+                            Some(nation) if Some(nation) != played => Some(nation),
+                            _ => None,
+                        };
+                        command_tx.send(PlayerCommand::TakeCommand { nation }).ok();
+                        if nation.is_some() {
+                            pinned_nation = nation;
+                        }
+                        command_open = nation.is_some();
+                    }
+                    KeyCode::Tab => {
+                        command_open = !command_open;
+                        intervene_open = false;
+                    }
+                    KeyCode::Char('i') | KeyCode::Char('I') => {
+                        intervene_open = !intervene_open;
+                        command_open = false;
+                    }
+                    KeyCode::Up if intervene_open => {
+                        intervene_row = intervene_row
+                            .checked_sub(1)
+                            .unwrap_or(InterventionRow::ALL.len() - 1);
+                    }
+                    KeyCode::Down if intervene_open => {
+                        intervene_row = (intervene_row + 1) % InterventionRow::ALL.len();
+                    }
+                    KeyCode::Left | KeyCode::Right if intervene_open => {
+                        if InterventionRow::ALL[intervene_row] == InterventionRow::Metric
+                            && let Ok(snapshot) = observer.read()
+                            && let Some(intervention) =
+                                scale_metric(&snapshot, &control_state, key.code == KeyCode::Right)
+                        {
+                            intervention_tx.send(intervention).ok();
+                        }
+                    }
+                    KeyCode::Enter if intervene_open => {
+                        let row = InterventionRow::ALL[intervene_row];
+                        if row == InterventionRow::Metric {
+                            intervene_metric = next_metric(intervene_metric);
+                        } else if let Ok(snapshot) = observer.read()
+                            && let Some(intervention) = row.order(&snapshot, &control_state)
+                        {
+                            intervention_tx.send(intervention).ok();
+                        }
+                    }
+                    KeyCode::Up if command_open => {
+                        command_row = command_row
+                            .checked_sub(1)
+                            .unwrap_or(CommandRow::ALL.len() - 1);
+                    }
+                    KeyCode::Down if command_open => {
+                        command_row = (command_row + 1) % CommandRow::ALL.len();
+                    }
+                    KeyCode::Left | KeyCode::Right if command_open => {
+                        if let Some(order) =
+                            CommandRow::ALL[command_row].shift(key.code == KeyCode::Right)
+                        {
+                            command_tx.send(order).ok();
+                        }
+                    }
+                    KeyCode::Enter if command_open => {
+                        if let Some(order) = selected_owner
+                            .and_then(|target| CommandRow::ALL[command_row].order(target))
+                        {
+                            command_tx.send(order).ok();
+                        }
+                    }
+                    KeyCode::F(5) => {
+                        persist_tx
+                            .send(PersistRequest::Save(PathBuf::from(QUICKSAVE_PATH)))
+                            .ok();
+                    }
+                    KeyCode::F(9) => {
+                        persist_tx
+                            .send(PersistRequest::Load(PathBuf::from(QUICKSAVE_PATH)))
+                            .ok();
+                    }
                     _ => {}
                 },
                 Event::Mouse(mouse) => {
@@ -253,7 +455,7 @@ async fn main() -> anyhow::Result<()> {
                                 if let Ok(snapshot) = observer.read() {
                                     if let Some(hex) = snapshot.grid.hexes.get(&coord) {
                                         selected_hex = Some(coord);
-                                        selected_owner = Some(hex.owner);
+                                        selected_owner = hex.owner;
                                     } else {
                                         selected_hex = None;
                                         selected_owner = None;
@@ -274,7 +476,7 @@ async fn main() -> anyhow::Result<()> {
                                     // [+]
                                     let current_duration = *tick_duration_tx.borrow();
                                     let new_duration =
-                                        (current_duration / 2).max(Duration::from_millis(1));
+                                        (current_duration / 2).max(MIN_TICK_DURATION);
                                     active_preset = None;
                                     tick_duration_tx.send(new_duration).ok();
                                 } else if (9..=11).contains(&mouse.column) {
@@ -302,6 +504,9 @@ async fn main() -> anyhow::Result<()> {
     simulation_task.await?;
     restore_terminal()?;
     term_guard.disarm();
+    if let Some(api_task) = api_task {
+        api_task.await??;
+    }
 
     Ok(())
 }
@@ -1,25 +1,102 @@
 use bevy_ecs::prelude::Resource;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::BTreeSet;
 
 use crate::simulation::Nation;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum BlocKind {
     ResearchPact,
     Sanction,
     DefenseTreaty,
 }
 
+impl BlocKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            BlocKind::ResearchPact => "Research Pact",
+            BlocKind::Sanction => "Sanctions Regime",
+            BlocKind::DefenseTreaty => "Defense Treaty",
+        }
+    }
+}
+
+/// What happened to a bloc's membership.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlocChange {
+    Founded,
+    Joined,
+    Expelled,
+    Dissolved,
+}
+
+impl BlocChange {
+    pub fn label(self) -> &'static str {
+        match self {
+            BlocChange::Founded => "founds",
+            BlocChange::Joined => "joins",
+            BlocChange::Expelled => "is expelled from",
+            BlocChange::Dissolved => "dissolves",
+        }
+    }
+}
+
+/// A standing organisation of nations. Blocs outlive the conditions that
+/// founded them: nations apply to join, get expelled, and the bloc is
+/// dissolved once fewer than two members remain.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bloc {
+    pub id: u64,
     pub kind: BlocKind,
-    pub members: HashSet<Nation>,
+    pub name: String,
+    pub founders: BTreeSet<Nation>,
+    pub members: BTreeSet<Nation>,
     pub leader: Option<Nation>,
+    /// The nation a sanctions regime embargoes.
+    pub target: Option<Nation>,
+    pub founded: u64,
     pub strength: f32,
 }
 
 #[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
 pub struct WorldBlocs {
-    pub blocs: HashMap<BlocKind, Bloc>,
+    pub blocs: Vec<Bloc>,
+    pub next_id: u64,
+    /// Requests to join, as (applicant, bloc id), put to a vote at the next
+    /// bloc review.
+    #[serde(default)]
+    pub applications: Vec<(Nation, u64)>,
+}
+
+impl WorldBlocs {
+    /// The bloc of `kind` that `nation` belongs to; a nation joins at most
+    /// one bloc of each kind.
+    pub fn bloc_of(&self, nation: Nation, kind: BlocKind) -> Option<&Bloc> {
+        self.blocs
+            .iter()
+            .find(|bloc| bloc.kind == kind && bloc.members.contains(&nation))
+    }
+
+    /// Nations bound to come to `nation`'s defence, with the treaty binding them.
+    pub fn treaty_partners(&self, nation: Nation) -> Vec<(Nation, u64)> {
+        self.bloc_of(nation, BlocKind::DefenseTreaty)
+            .map(|bloc| {
+                bloc.members
+                    .iter()
+                    .filter(|member| **member != nation)
+                    .map(|member| (*member, bloc.id))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Combined strength of the sanctions regimes embargoing `nation`.
+    pub fn embargo_on(&self, nation: Nation) -> f32 {
+        self.blocs
+            .iter()
+            .filter(|bloc| bloc.kind == BlocKind::Sanction && bloc.target == Some(nation))
+            .map(|bloc| bloc.strength)
+            .sum()
+    }
 }
@@ -3,7 +3,7 @@
 use bevy_ecs::prelude::Component;
 use serde::{Deserialize, Serialize};
 
-use crate::simulation::Nation;
+use crate::simulation::{AxialCoord, Nation};
 
 #[derive(Debug, Clone, Component, Serialize, Deserialize)]
 pub struct Identity {
@@ -33,13 +33,28 @@ pub struct Attributes {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Biome {
+    Ocean,
+    /// Shallow water along a shoreline.
+    Coast,
+    Glacier,
+    Tundra,
+    Mountain,
     Forest,
     Plains,
     Desert,
+    Savanna,
+    Jungle,
+    // Settlements NPCs are anchored to; terrain never classifies as these.
     Village,
     Market,
 }
 
+impl Biome {
+    pub fn is_water(self) -> bool {
+        matches!(self, Biome::Ocean | Biome::Coast)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Component, Serialize, Deserialize)]
 pub struct Position {
     pub x: f32,
@@ -59,7 +74,7 @@ pub struct ItemStack {
     pub quantity: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ItemKind {
     Resource(String),
     Equipment(String),
@@ -117,3 +132,24 @@ pub struct Combatants {
 
     pub nation_b: Nation,
 }
+
+/// A field army. Its position is the `AxialCoord` on the same entity.
+#[derive(Debug, Clone, Component, Serialize, Deserialize)]
+pub struct Army {
+    pub id: u64,
+    pub nation: Nation,
+    /// Fighting strength, on the same scale as the nation's `military`.
+    pub strength: f32,
+    /// 0..1; drains on foreign ground and at the front, refills at home.
+    pub supply: f32,
+    /// 0..1; victories raise it, defeats and hunger wear it down.
+    pub morale: f32,
+    /// The nation this army is campaigning against, if any.
+    pub enemy: Option<Nation>,
+    /// Hex the army is marching to.
+    pub objective: Option<AxialCoord>,
+    /// Remaining route to the objective, next step last.
+    pub path: Vec<AxialCoord>,
+    /// Movement banked toward entering the next hex on the route.
+    pub march: f32,
+}
@@ -2,11 +2,14 @@
 
 use std::collections::VecDeque;
 
-use crate::simulation::Nation;
+use crate::simulation::{Nation, NationRegistry};
 use bevy_ecs::prelude::Resource;
 use serde::{Deserialize, Serialize};
 
-use crate::simulation::{BehaviorState, Biome, Era, Faction, WeaponTier};
+use crate::simulation::{
+    AllianceEnd, BehaviorState, Biome, BlocChange, BlocKind, CasusBelli, CrisisKind, Era, Faction,
+    Intervention, RouteChange, Salvo, Tech, War, WeaponTier,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -27,18 +30,83 @@ pub enum WorldEventKind {
         projected_impact: String,
         casualties: Option<u64>,
     },
+    /// One battle inside war `war`.
     Warfare {
+        war: u64,
         winner: Nation,
         loser: Nation,
-        territory_change: f32,
+        hexes_captured: usize,
         casualties: u64,
         nuclear: bool,
     },
+    /// A salvo of warheads fired during a battle; `retaliation` marks the
+    /// target's second strike.
+    NuclearStrike {
+        attacker: Nation,
+        target: Nation,
+        warheads: u32,
+        casualties: u64,
+        retaliation: bool,
+    },
+    WarDeclared {
+        war: u64,
+        attacker: Nation,
+        defender: Nation,
+        casus_belli: CasusBelli,
+        /// Nations pulled in on either side, mostly by defense treaties.
+        allies: usize,
+    },
+    HexesCeded {
+        from: Nation,
+        to: Nation,
+        hexes: usize,
+    },
+    ReparationsPaid {
+        payer: Nation,
+        payee: Nation,
+        amount: f32,
+    },
+    TruceSigned {
+        a: Nation,
+        b: Nation,
+        until: u64,
+    },
+    /// A nation founds, joins or is expelled from a bloc, or is the last
+    /// member left when it dissolves.
+    BlocChanged {
+        bloc: String,
+        bloc_kind: BlocKind,
+        nation: Nation,
+        change: BlocChange,
+    },
+    /// `breaker` failed its treaty obligations toward `ally`, either by
+    /// attacking it or by staying out of its defence.
+    TreatyBroken {
+        breaker: Nation,
+        ally: Nation,
+        treaty: String,
+    },
+    AllianceDissolved {
+        a: Nation,
+        b: Nation,
+        reason: AllianceEnd,
+    },
+    /// `concession` is the economy `target` paid to have them lifted, zero
+    /// if `issuer` relented on its own.
+    SanctionsLifted {
+        issuer: Nation,
+        target: Nation,
+        concession: f32,
+    },
     EraShift {
         nation: Nation,
         era: Era,
         weapon: WeaponTier,
     },
+    TechDiscovered {
+        nation: Nation,
+        tech: Tech,
+    },
     ScienceProgress {
         nation: Nation,
         progress: f32,
@@ -55,6 +123,39 @@ pub enum WorldEventKind {
         winner: Nation,
         progress: f32,
     },
+    /// The trade route between `a` and `b` opened or closed; `volume` is
+    /// what it carried on its first or last tick.
+    TradeRoute {
+        a: Nation,
+        b: Nation,
+        change: RouteChange,
+        volume: f32,
+    },
+    /// `nation` could no longer service `debt` and wrote off `haircut` of it.
+    SovereignDefault {
+        nation: Nation,
+        debt: f32,
+        haircut: f32,
+    },
+    /// `nation` fell into `crisis`, spread from `origin` when it did not
+    /// start at home; `output_gap` is where output stood against potential.
+    EconomicCrisis {
+        nation: Nation,
+        crisis: CrisisKind,
+        origin: Option<Nation>,
+        output_gap: f32,
+    },
+    /// `nation` came through `crisis` after `ticks`.
+    EconomicRecovery {
+        nation: Nation,
+        crisis: CrisisKind,
+        ticks: u64,
+    },
+    /// The observer changed the world; `summary` says what came of it.
+    Intervention {
+        intervention: Intervention,
+        summary: String,
+    },
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -93,11 +194,26 @@ impl WorldEvent {
             WorldEventKind::Social { .. } => "Social",
             WorldEventKind::MacroShock { .. } => "MacroShock",
             WorldEventKind::Warfare { .. } => "War",
+            WorldEventKind::NuclearStrike { .. } => "War",
+            WorldEventKind::WarDeclared { .. } => "War",
+            WorldEventKind::HexesCeded { .. } => "Peace",
+            WorldEventKind::ReparationsPaid { .. } => "Peace",
+            WorldEventKind::TruceSigned { .. } => "Peace",
+            WorldEventKind::BlocChanged { .. } => "Diplomacy",
+            WorldEventKind::TreatyBroken { .. } => "Diplomacy",
+            WorldEventKind::AllianceDissolved { .. } => "Diplomacy",
+            WorldEventKind::SanctionsLifted { .. } => "Diplomacy",
             WorldEventKind::EraShift { .. } => "Era",
+            WorldEventKind::TechDiscovered { .. } => "Science",
             WorldEventKind::ScienceProgress { .. } => "Science",
             WorldEventKind::ScienceVictory { .. } => "Science",
             WorldEventKind::InterstellarProgress { .. } => "Space",
             WorldEventKind::InterstellarVictory { .. } => "Space",
+            WorldEventKind::TradeRoute { .. } => "Trade",
+            WorldEventKind::SovereignDefault { .. } => "Finance",
+            WorldEventKind::EconomicCrisis { .. } => "Finance",
+            WorldEventKind::EconomicRecovery { .. } => "Finance",
+            WorldEventKind::Intervention { .. } => "Observer",
         }
     }
 
@@ -107,16 +223,39 @@ impl WorldEvent {
             WorldEventKind::Social { .. } => Sentiment::Positive,
             WorldEventKind::MacroShock { .. } => Sentiment::Negative,
             WorldEventKind::Warfare { .. } => Sentiment::Negative,
+            WorldEventKind::NuclearStrike { .. } => Sentiment::Negative,
+            WorldEventKind::WarDeclared { .. } => Sentiment::Negative,
+            WorldEventKind::HexesCeded { .. } => Sentiment::Neutral,
+            WorldEventKind::ReparationsPaid { .. } => Sentiment::Neutral,
+            WorldEventKind::TruceSigned { .. } => Sentiment::Positive,
+            WorldEventKind::BlocChanged { change, .. } => match change {
+                BlocChange::Founded | BlocChange::Joined => Sentiment::Positive,
+                BlocChange::Expelled | BlocChange::Dissolved => Sentiment::Negative,
+            },
+            WorldEventKind::TreatyBroken { .. } => Sentiment::Negative,
+            WorldEventKind::AllianceDissolved { .. } => Sentiment::Negative,
+            WorldEventKind::SanctionsLifted { .. } => Sentiment::Positive,
             WorldEventKind::EraShift { .. } => Sentiment::Positive,
+            WorldEventKind::TechDiscovered { .. } => Sentiment::Positive,
             WorldEventKind::ScienceProgress { .. } => Sentiment::Positive,
             WorldEventKind::ScienceVictory { .. } => Sentiment::Positive,
             WorldEventKind::InterstellarProgress { .. } => Sentiment::Positive,
             WorldEventKind::InterstellarVictory { .. } => Sentiment::Positive,
+            WorldEventKind::TradeRoute { change, .. } => match change {
+                RouteChange::Opened => Sentiment::Positive,
+                RouteChange::Severed | RouteChange::Embargoed | RouteChange::Lapsed => {
+                    Sentiment::Negative
+                }
+            },
+            WorldEventKind::SovereignDefault { .. } => Sentiment::Negative,
+            WorldEventKind::EconomicCrisis { .. } => Sentiment::Negative,
+            WorldEventKind::EconomicRecovery { .. } => Sentiment::Positive,
+            WorldEventKind::Intervention { .. } => Sentiment::Neutral,
         }
     }
 
     #[allow(dead_code)]
-    pub fn headline(&self) -> String {
+    pub fn headline(&self, nations: &NationRegistry) -> String {
         match &self.kind {
             WorldEventKind::Trade {
                 actor,
@@ -154,45 +293,191 @@ impl WorldEvent {
             WorldEventKind::Warfare {
                 winner,
                 loser,
-                territory_change,
+                hexes_captured,
                 casualties,
                 nuclear,
+                ..
             } => format!(
-                "{} wins war against {}, gaining territory {:.2}. Casualties {}{}",
-                winner.name(),
-                loser.name(),
-                territory_change,
+                "{} defeats {} in battle, capturing {} hexes. Casualties {}{}",
+                nations.name(*winner),
+                nations.name(*loser),
+                hexes_captured,
                 crate::simulation::format_number_commas(*casualties),
                 if *nuclear { " | Nuclear Strike" } else { "" }
             ),
+            WorldEventKind::NuclearStrike {
+                attacker,
+                target,
+                warheads,
+                casualties,
+                retaliation,
+            } => format!(
+                "{} {} {} warheads at {} | Casualties {}",
+                nations.name(*attacker),
+                if *retaliation {
+                    "retaliates with"
+                } else {
+                    "launches"
+                },
+                warheads,
+                nations.name(*target),
+                crate::simulation::format_number_commas(*casualties)
+            ),
+            WorldEventKind::WarDeclared {
+                attacker,
+                defender,
+                casus_belli,
+                allies,
+                ..
+            } => format!(
+                "{} declares war on {} | Casus belli: {} | Allies drawn in: {}",
+                nations.name(*attacker),
+                nations.name(*defender),
+                casus_belli.label(),
+                allies
+            ),
+            WorldEventKind::HexesCeded { from, to, hexes } => format!(
+                "{} cedes {} hexes to {} at the peace table",
+                nations.name(*from),
+                hexes,
+                nations.name(*to)
+            ),
+            WorldEventKind::ReparationsPaid {
+                payer,
+                payee,
+                amount,
+            } => format!(
+                "{} pays {:.1} in reparations to {}",
+                nations.name(*payer),
+                amount,
+                nations.name(*payee)
+            ),
+            WorldEventKind::TruceSigned { a, b, until } => format!(
+                "{} and {} sign a white peace | Truce until tick {}",
+                nations.name(*a),
+                nations.name(*b),
+                until
+            ),
+            WorldEventKind::BlocChanged {
+                bloc,
+                bloc_kind,
+                nation,
+                change,
+            } => format!(
+                "{} {} the {} | {}",
+                nations.name(*nation),
+                change.label(),
+                bloc,
+                bloc_kind.label()
+            ),
+            WorldEventKind::TreatyBroken {
+                breaker,
+                ally,
+                treaty,
+            } => format!(
+                "{} breaks the {} and abandons {}",
+                nations.name(*breaker),
+                treaty,
+                nations.name(*ally)
+            ),
+            WorldEventKind::AllianceDissolved { a, b, reason } => format!(
+                "The alliance between {} and {} ends | {}",
+                nations.name(*a),
+                nations.name(*b),
+                reason.label()
+            ),
+            WorldEventKind::SanctionsLifted {
+                issuer,
+                target,
+                concession,
+            } => format!(
+                "{} lifts its sanctions on {}{}",
+                nations.name(*issuer),
+                nations.name(*target),
+                if *concession > 0.0 {
+                    format!(" | Concession {concession:.1}")
+                } else {
+                    String::new()
+                }
+            ),
             WorldEventKind::EraShift {
                 nation,
                 era,
                 weapon,
             } => format!(
                 "{} has entered {} | Main Weapon: {}",
-                nation.name(),
+                nations.name(*nation),
                 era.label(),
                 weapon.label()
             ),
+            WorldEventKind::TechDiscovered { nation, tech } => {
+                format!("{} has discovered {}", nations.name(*nation), tech.label())
+            }
             WorldEventKind::ScienceProgress { nation, progress } => format!(
                 "{} Moon Exploration Progress {:.1}% / 100% (1 tick = 1 gen)",
-                nation.name(),
+                nations.name(*nation),
                 progress.min(100.0)
             ),
             WorldEventKind::ScienceVictory { winner, .. } => format!(
                 "{} achieved the first Moon Landing! Science Victory for all humanity",
-                winner.name()
+                nations.name(*winner)
             ),
             WorldEventKind::InterstellarProgress { leader, progress } => format!(
                 "{} Interstellar Migration Progress {:.1}% / 100%",
-                leader.name(),
+                nations.name(*leader),
                 progress.min(100.0)
             ),
             WorldEventKind::InterstellarVictory { winner, .. } => format!(
                 "{} completed Interstellar Settlement! Evolved into Space Civilization",
-                winner.name()
+                nations.name(*winner)
             ),
+            WorldEventKind::TradeRoute {
+                a,
+                b,
+                change,
+                volume,
+            } => format!(
+                "The trade route between {} and {} {} | Volume {:.1}",
+                nations.name(*a),
+                nations.name(*b),
+                change.label(),
+                volume
+            ),
+            WorldEventKind::SovereignDefault {
+                nation,
+                debt,
+                haircut,
+            } => format!(
+                "{} defaults on its debt of {:.1} | {:.1} written off",
+                nations.name(*nation),
+                debt,
+                haircut
+            ),
+            WorldEventKind::EconomicCrisis {
+                nation,
+                crisis,
+                origin,
+                output_gap,
+            } => format!(
+                "{} falls into a {}{} | Output gap {:+.1}%",
+                nations.name(*nation),
+                crisis.label().to_lowercase(),
+                origin
+                    .map(|origin| format!(" spreading from {}", nations.name(origin)))
+                    .unwrap_or_default(),
+                output_gap
+            ),
+            WorldEventKind::EconomicRecovery {
+                nation,
+                crisis,
+                ticks,
+            } => format!(
+                "{} recovers from its {} after {} ticks",
+                nations.name(*nation),
+                crisis.label().to_lowercase(),
+                ticks
+            ),
+            WorldEventKind::Intervention { summary, .. } => format!("The observer {summary}"),
         }
     }
 
@@ -262,9 +547,10 @@ impl WorldEvent {
         tick: u64,
         epoch: &str,
         season: &str,
+        war: u64,
         winner: Nation,
         loser: Nation,
-        territory_change: f32,
+        hexes_captured: usize,
         casualties: u64,
         nuclear: bool,
     ) -> Self {
@@ -273,15 +559,185 @@ impl WorldEvent {
             epoch: epoch.to_string(),
             season: season.to_string(),
             kind: WorldEventKind::Warfare {
+                war,
                 winner,
                 loser,
-                territory_change,
+                hexes_captured,
                 casualties,
                 nuclear,
             },
         }
     }
 
+    pub fn nuclear_strike(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        attacker: Nation,
+        target: Nation,
+        salvo: Salvo,
+        retaliation: bool,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::NuclearStrike {
+                attacker,
+                target,
+                warheads: salvo.warheads,
+                casualties: salvo.casualties,
+                retaliation,
+            },
+        }
+    }
+
+    /// Announces `war`, naming the leader of each coalition.
+    pub fn war_declared(tick: u64, epoch: &str, season: &str, war: &War) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::WarDeclared {
+                war: war.id,
+                attacker: war.attackers[0],
+                defender: war.defenders[0],
+                casus_belli: war.casus_belli,
+                allies: war.attackers.len() + war.defenders.len() - 2,
+            },
+        }
+    }
+
+    pub fn hexes_ceded(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        from: Nation,
+        to: Nation,
+        hexes: usize,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::HexesCeded { from, to, hexes },
+        }
+    }
+
+    pub fn reparations_paid(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        payer: Nation,
+        payee: Nation,
+        amount: f32,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::ReparationsPaid {
+                payer,
+                payee,
+                amount,
+            },
+        }
+    }
+
+    pub fn truce_signed(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        a: Nation,
+        b: Nation,
+        until: u64,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::TruceSigned { a, b, until },
+        }
+    }
+
+    pub fn bloc_changed(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        bloc: String,
+        bloc_kind: BlocKind,
+        nation: Nation,
+        change: BlocChange,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::BlocChanged {
+                bloc,
+                bloc_kind,
+                nation,
+                change,
+            },
+        }
+    }
+
+    pub fn treaty_broken(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        breaker: Nation,
+        ally: Nation,
+        treaty: String,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::TreatyBroken {
+                breaker,
+                ally,
+                treaty,
+            },
+        }
+    }
+
+    pub fn alliance_dissolved(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        a: Nation,
+        b: Nation,
+        reason: AllianceEnd,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::AllianceDissolved { a, b, reason },
+        }
+    }
+
+    pub fn sanctions_lifted(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        issuer: Nation,
+        target: Nation,
+        concession: f32,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::SanctionsLifted {
+                issuer,
+                target,
+                concession,
+            },
+        }
+    }
+
     pub fn era_shift(
         tick: u64,
         epoch: &str,
@@ -302,6 +758,123 @@ impl WorldEvent {
         }
     }
 
+    pub fn trade_route(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        a: Nation,
+        b: Nation,
+        change: RouteChange,
+        volume: f32,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::TradeRoute {
+                a,
+                b,
+                change,
+                volume,
+            },
+        }
+    }
+
+    pub fn sovereign_default(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        nation: Nation,
+        debt: f32,
+        haircut: f32,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::SovereignDefault {
+                nation,
+                debt,
+                haircut,
+            },
+        }
+    }
+
+    pub fn economic_crisis(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        nation: Nation,
+        crisis: CrisisKind,
+        origin: Option<Nation>,
+        output_gap: f32,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::EconomicCrisis {
+                nation,
+                crisis,
+                origin,
+                output_gap,
+            },
+        }
+    }
+
+    pub fn economic_recovery(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        nation: Nation,
+        crisis: CrisisKind,
+        ticks: u64,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::EconomicRecovery {
+                nation,
+                crisis,
+                ticks,
+            },
+        }
+    }
+
+    pub fn intervention(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        intervention: Intervention,
+        summary: String,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::Intervention {
+                intervention,
+                summary,
+            },
+        }
+    }
+
+    pub fn tech_discovered(
+        tick: u64,
+        epoch: &str,
+        season: &str,
+        nation: Nation,
+        tech: Tech,
+    ) -> Self {
+        Self {
+            tick,
+            epoch: epoch.to_string(),
+            season: season.to_string(),
+            kind: WorldEventKind::TechDiscovered { nation, tech },
+        }
+    }
+
     pub fn science_progress(
         tick: u64,
         epoch: &str,
@@ -363,7 +936,7 @@ impl WorldEvent {
     }
 }
 
-#[derive(Debug, Resource)]
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
 pub struct WorldEventLog {
     events: VecDeque<WorldEvent>,
     capacity: usize,
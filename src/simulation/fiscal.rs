@@ -0,0 +1,199 @@
+//! Public finance: every nation taxes its working population, spends the
+//! proceeds across its budget lines and borrows to cover any shortfall.
+//! Debt carries interest that climbs with the load, and a state that can no
+//! longer service it defaults.
+
+use std::collections::BTreeMap;
+
+use bevy_ecs::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::Nation;
+
+/// Share of the tax base a new state collects.
+pub const DEFAULT_TAX: f32 = 0.25;
+/// Bounds on the tax rate.
+const MIN_TAX: f32 = 0.05;
+pub const MAX_TAX: f32 = 0.6;
+/// Bounds on spending as a multiple of revenue.
+const MIN_SPENDING: f32 = 0.5;
+const MAX_SPENDING: f32 = 1.5;
+/// Interest charged per tick on the debt of a state that owes nothing
+/// else, and the extra charged per tick of taxable capacity it owes.
+const BASE_INTEREST: f32 = 0.001;
+const RISK_PREMIUM: f32 = 0.0001;
+
+/// One line of a nation's budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetLine {
+    Military,
+    Science,
+    Culture,
+    Diplomacy,
+    Welfare,
+}
+
+impl BudgetLine {
+    pub const ALL: [BudgetLine; 5] = [
+        BudgetLine::Military,
+        BudgetLine::Science,
+        BudgetLine::Culture,
+        BudgetLine::Diplomacy,
+        BudgetLine::Welfare,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BudgetLine::Military => "Military",
+            BudgetLine::Science => "Science",
+            BudgetLine::Culture => "Culture",
+            BudgetLine::Diplomacy => "Diplomacy",
+            BudgetLine::Welfare => "Welfare",
+        }
+    }
+}
+
+/// Percent of each tick's spending that goes to each line; always sums
+/// to 100.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Budget {
+    pub military: u8,
+    pub science: u8,
+    pub culture: u8,
+    pub diplomacy: u8,
+    pub welfare: u8,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self {
+            military: 25,
+            science: 20,
+            culture: 15,
+            diplomacy: 15,
+            welfare: 25,
+        }
+    }
+}
+
+impl Budget {
+    pub fn share(&self, line: BudgetLine) -> u8 {
+        match line {
+            BudgetLine::Military => self.military,
+            BudgetLine::Science => self.science,
+            BudgetLine::Culture => self.culture,
+            BudgetLine::Diplomacy => self.diplomacy,
+            BudgetLine::Welfare => self.welfare,
+        }
+    }
+
+    fn share_mut(&mut self, line: BudgetLine) -> &mut u8 {
+        match line {
+            BudgetLine::Military => &mut self.military,
+            BudgetLine::Science => &mut self.science,
+            BudgetLine::Culture => &mut self.culture,
+            BudgetLine::Diplomacy => &mut self.diplomacy,
+            BudgetLine::Welfare => &mut self.welfare,
+        }
+    }
+
+    /// Moves up to `delta` points into `line` (out of it when negative),
+    /// one point at a time from the largest other line or to the smallest.
+    pub fn shift(&mut self, line: BudgetLine, delta: i8) {
+        for _ in 0..delta.unsigned_abs() {
+            let others = BudgetLine::ALL.into_iter().filter(|other| *other != line);
+            if delta > 0 {
+                let Some(donor) = others
+                    .filter(|other| self.share(*other) > 0)
+                    .max_by_key(|other| self.share(*other))
+                else {
+                    break;
+                };
+                *self.share_mut(donor) -= 1;
+                *self.share_mut(line) += 1;
+            } else {
+                if self.share(line) == 0 {
+                    break;
+                }
+                let Some(taker) = others.min_by_key(|other| self.share(*other)) else {
+                    break;
+                };
+                *self.share_mut(line) -= 1;
+                *self.share_mut(taker) += 1;
+            }
+        }
+    }
+}
+
+/// A nation's public purse.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Treasury {
+    /// Share of the tax base collected each tick.
+    pub tax_rate: f32,
+    /// Spending as a multiple of revenue; above 1 the state borrows.
+    pub spending_ratio: f32,
+    pub budget: Budget,
+    /// Economy the state could raise in a tick at the highest tax rate.
+    pub capacity: f32,
+    /// Taxes collected, money spent and interest paid last tick.
+    pub revenue: f32,
+    pub spending: f32,
+    pub interest: f32,
+    pub debt: f32,
+    /// Tick before which lenders refuse new credit after a default.
+    pub shut_out_until: u64,
+    pub defaults: u32,
+}
+
+impl Default for Treasury {
+    fn default() -> Self {
+        Self {
+            tax_rate: DEFAULT_TAX,
+            spending_ratio: 1.0,
+            budget: Budget::default(),
+            capacity: 0.0,
+            revenue: 0.0,
+            spending: 0.0,
+            interest: 0.0,
+            debt: 0.0,
+            shut_out_until: 0,
+            defaults: 0,
+        }
+    }
+}
+
+impl Treasury {
+    /// Last tick's surplus, negative for a deficit.
+    pub fn balance(&self) -> f32 {
+        self.revenue - self.spending - self.interest
+    }
+
+    /// Ticks of full taxation the debt amounts to.
+    pub fn debt_load(&self) -> f32 {
+        self.debt / self.capacity.max(0.1)
+    }
+
+    /// Interest charged per tick on the debt: dearer the more is owed.
+    pub fn interest_rate(&self) -> f32 {
+        BASE_INTEREST + RISK_PREMIUM * self.debt_load()
+    }
+
+    /// Moves the tax rate by `delta`, within its bounds.
+    pub fn shift_tax(&mut self, delta: f32) {
+        self.tax_rate = (self.tax_rate + delta).clamp(MIN_TAX, MAX_TAX);
+    }
+
+    /// Moves the spending ratio by `delta`, within its bounds.
+    pub fn shift_spending(&mut self, delta: f32) {
+        self.spending_ratio = (self.spending_ratio + delta).clamp(MIN_SPENDING, MAX_SPENDING);
+    }
+
+    /// Whether lenders will cover a deficit at `tick`.
+    pub fn can_borrow(&self, tick: u64) -> bool {
+        tick >= self.shut_out_until
+    }
+}
+
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct Treasuries(pub BTreeMap<Nation, Treasury>);
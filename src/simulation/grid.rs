@@ -1,10 +1,13 @@
 use bevy_ecs::prelude::{Component, Entity, Resource};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 use crate::simulation::Nation;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Component)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Component,
+)]
 pub struct AxialCoord {
     pub q: i32,
     pub r: i32,
@@ -25,13 +28,112 @@ impl AxialCoord {
             AxialCoord::new(self.q - 1, self.r + 1),
         ]
     }
+
+    /// Number of hex steps between two coordinates.
+    pub fn distance(&self, other: AxialCoord) -> i32 {
+        ((self.q - other.q).abs()
+            + (self.q + self.r - other.q - other.r).abs()
+            + (self.r - other.r).abs())
+            / 2
+    }
+}
+
+/// Every coordinate of a hexagonal grid of `radius`, column by column.
+pub fn hex_coords(radius: i32) -> impl Iterator<Item = AxialCoord> {
+    (-radius..=radius).flat_map(move |q| {
+        ((-radius).max(-q - radius)..=radius.min(-q + radius)).map(move |r| AxialCoord::new(q, r))
+    })
 }
 
 #[derive(Debug, Clone, Component, Serialize, Deserialize)]
 pub struct Hex {
-    pub owner: Nation,
+    /// `None` for open water and land no nation has reached.
+    pub owner: Option<Nation>,
+    /// 0..1, with the shoreline at `terrain::SEA_LEVEL`.
     pub elevation: f32,
+    /// 0..1, from arid to saturated.
+    pub moisture: f32,
+    /// 0..1, from polar to tropical.
+    pub temperature: f32,
     pub biome: crate::simulation::Biome,
+    /// Base output per tick, from the biome's metadata.
+    pub yields: Yields,
+    pub deposits: Vec<Deposit>,
+}
+
+impl Hex {
+    /// What the hex produces per tick: its base yields, raised by every deposit.
+    pub fn output(&self) -> Yields {
+        let richness: f32 = self.deposits.iter().map(|deposit| deposit.richness).sum();
+        self.yields.scaled(1.0 + richness)
+    }
+}
+
+/// A named resource from the biome's `resource_profile` found in one hex.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deposit {
+    pub resource: String,
+    /// Fraction added to the hex's yields, typically 0.1..0.6.
+    pub richness: f32,
+}
+
+/// Output on the three supply channels.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Yields {
+    pub food: f32,
+    pub energy: f32,
+    pub rare: f32,
+}
+
+impl Yields {
+    pub fn new(food: f32, energy: f32, rare: f32) -> Self {
+        Self { food, energy, rare }
+    }
+
+    pub fn scaled(self, factor: f32) -> Self {
+        Self::new(self.food * factor, self.energy * factor, self.rare * factor)
+    }
+
+    /// Applies `f` channel by channel to `self` and `other`.
+    pub fn zip(self, other: Self, f: impl Fn(f32, f32) -> f32) -> Self {
+        Self::new(
+            f(self.food, other.food),
+            f(self.energy, other.energy),
+            f(self.rare, other.rare),
+        )
+    }
+}
+
+impl Add for Yields {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(
+            self.food + other.food,
+            self.energy + other.energy,
+            self.rare + other.rare,
+        )
+    }
+}
+
+impl AddAssign for Yields {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Yields {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip(other, |a, b| a - b)
+    }
+}
+
+impl SubAssign for Yields {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
 }
 
 #[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
@@ -40,22 +142,16 @@ pub struct HexGrid {
     pub radius: i32,
 }
 
-#[allow(dead_code)]
-pub fn default_continent_centers(radius: i32) -> [(Nation, AxialCoord); 3] {
-    [
-        (Nation::Tera, AxialCoord::new(-radius + 3, radius / 3)),
-        (Nation::Sora, AxialCoord::new(radius - 3, -radius / 4)),
-        (Nation::Aqua, AxialCoord::new(0, -radius + 3)),
-    ]
-}
-
-pub fn pentagon_centers(radius: i32) -> [(Nation, AxialCoord); 5] {
-    let r = radius - 2;
-    [
-        (Nation::Tera, AxialCoord::new(0, -r)),     // top
-        (Nation::Sora, AxialCoord::new(r, -r / 2)), // upper right
-        (Nation::Aqua, AxialCoord::new(r, r / 2)),  // lower right
-        (Nation::Solar, AxialCoord::new(0, r)),     // bottom
-        (Nation::Luna, AxialCoord::new(-r, 0)),     // left
-    ]
+/// Evenly spaced points on a ring partway out from the center, one per
+/// nation. Terrain generation moves each onto the nearest suitable land.
+pub fn sector_capitals(radius: i32, count: usize) -> Vec<AxialCoord> {
+    let ring = (radius - 2).max(1) as f32 * 0.6;
+    (0..count)
+        .map(|index| {
+            let angle = (index as f32 * 360.0 / count as f32).to_radians();
+            let r = ring * angle.sin() * 2.0 / (3.0_f32).sqrt();
+            let q = ring * angle.cos() - r / 2.0;
+            AxialCoord::new(q.round() as i32, r.round() as i32)
+        })
+        .collect()
 }
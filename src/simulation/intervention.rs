@@ -0,0 +1,386 @@
+//! Observer interventions: changes made to the running world from outside
+//! it, for experiments. Each is queued, carried out at the start of the next
+//! tick and written to the event log.
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::systems::events::{Catastrophe, strike_catastrophe};
+use crate::simulation::{
+    AllNationMetrics, Attributes, Behavior, BehaviorState, Biome, ClimateState, Faction, GoalKind,
+    Goals, Identity, Inventory, Nation, NationRegistry, Personality, RngStream, ScienceVictory,
+    SimulationRng, Tech, TechTree, Wars, WorldEvent, WorldEventLog, WorldMetadata, WorldTime,
+    format_number_commas,
+};
+
+/// A nation metric an intervention can set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    Economy,
+    Military,
+    Science,
+    Culture,
+    Diplomacy,
+    Religion,
+    Population,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 7] = [
+        Metric::Economy,
+        Metric::Military,
+        Metric::Science,
+        Metric::Culture,
+        Metric::Diplomacy,
+        Metric::Religion,
+        Metric::Population,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Metric::Economy => "Economy",
+            Metric::Military => "Military",
+            Metric::Science => "Science",
+            Metric::Culture => "Culture",
+            Metric::Diplomacy => "Diplomacy",
+            Metric::Religion => "Religion",
+            Metric::Population => "Population",
+        }
+    }
+
+    pub fn value(self, metrics: &crate::simulation::NationMetrics) -> f64 {
+        match self {
+            Metric::Economy => f64::from(metrics.economy),
+            Metric::Military => f64::from(metrics.military),
+            Metric::Science => f64::from(metrics.science),
+            Metric::Culture => f64::from(metrics.culture),
+            Metric::Diplomacy => f64::from(metrics.diplomacy),
+            Metric::Religion => f64::from(metrics.religion),
+            Metric::Population => metrics.population as f64,
+        }
+    }
+}
+
+/// One change to the world made by the observer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum Intervention {
+    Catastrophe {
+        catastrophe: Catastrophe,
+    },
+    /// `attacker` declares war on `defender`, truce or not.
+    ForceWar {
+        attacker: Nation,
+        defender: Nation,
+    },
+    SetMetric {
+        nation: Nation,
+        metric: Metric,
+        value: f64,
+    },
+    /// A new NPC of `faction` loyal to `nation`, named after its id when
+    /// `name` is missing.
+    SpawnNpc {
+        nation: Nation,
+        faction: Faction,
+        #[serde(default)]
+        name: Option<String>,
+    },
+    RemoveNpc {
+        id: u64,
+    },
+    /// Adds `ppm` to the atmosphere's carbon.
+    RaiseCarbon {
+        ppm: f32,
+    },
+    /// Unlocks `tech` for `nation`, or its current research target when
+    /// `tech` is missing. A named tech must be in the tech tree and have its
+    /// prerequisites unlocked.
+    GiftTech {
+        nation: Nation,
+        #[serde(default)]
+        tech: Option<Tech>,
+    },
+}
+
+impl Intervention {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Intervention::Catastrophe { .. } => "Catastrophe",
+            Intervention::ForceWar { .. } => "Force war",
+            Intervention::SetMetric { .. } => "Set metric",
+            Intervention::SpawnNpc { .. } => "Spawn NPC",
+            Intervention::RemoveNpc { .. } => "Remove NPC",
+            Intervention::RaiseCarbon { .. } => "Raise carbon",
+            Intervention::GiftTech { .. } => "Gift tech",
+        }
+    }
+
+    /// Nations the intervention names, for checking and log filters.
+    pub fn nations(&self) -> Vec<Nation> {
+        match self {
+            Intervention::ForceWar { attacker, defender } => vec![*attacker, *defender],
+            Intervention::SetMetric { nation, .. }
+            | Intervention::SpawnNpc { nation, .. }
+            | Intervention::GiftTech { nation, .. } => vec![*nation],
+            Intervention::Catastrophe { .. }
+            | Intervention::RemoveNpc { .. }
+            | Intervention::RaiseCarbon { .. } => Vec::new(),
+        }
+    }
+
+    /// Why the intervention cannot be carried out, if it cannot: a gifted
+    /// tech must be in the tech tree, with every prerequisite unlocked.
+    pub fn problem(&self, tech_tree: &TechTree, all_metrics: &AllNationMetrics) -> Option<String> {
+        let Intervention::GiftTech {
+            nation,
+            tech: Some(tech),
+        } = self
+        else {
+            return None;
+        };
+        let Some(node) = tech_tree.node(*tech) else {
+            return Some(format!("no tech {} in the tech tree", tech.label()));
+        };
+        let unlocked = all_metrics
+            .0
+            .get(nation)
+            .map_or(&[][..], |m| m.unlocked_techs.as_slice());
+        node.prerequisites
+            .iter()
+            .find(|prerequisite| !unlocked.contains(prerequisite))
+            .map(|missing| format!("{} needs {} first", tech.label(), missing.label()))
+    }
+}
+
+/// Carries out `interventions` in order, logging each with what it did.
+pub(crate) fn apply_interventions(world: &mut World, interventions: Vec<Intervention>) {
+    let tick = world.resource::<WorldTime>().tick;
+    let world_meta = world.resource::<WorldMetadata>().clone();
+    let registry = world.resource::<NationRegistry>().clone();
+    let (epoch, season) = world_meta.epoch_for_tick(tick);
+
+    for (index, intervention) in interventions.into_iter().enumerate() {
+        let summary =
+            match &intervention {
+                Intervention::Catastrophe { catastrophe } => {
+                    // Keyed by queue position so catastrophes queued together
+                    // strike independently.
+                    let mut rng = world.resource::<SimulationRng>().keyed_stream(
+                        RngStream::Intervention,
+                        tick,
+                        index as u64,
+                    );
+                    let severity = catastrophe.severity(&mut rng);
+                    let mut all_metrics = world.resource_mut::<AllNationMetrics>();
+                    let event = strike_catastrophe(
+                        *catastrophe,
+                        severity,
+                        &mut all_metrics,
+                        tick,
+                        epoch,
+                        season,
+                    );
+                    world.resource_mut::<WorldEventLog>().push(event);
+                    format!("unleashes a {}", catastrophe.label().to_lowercase())
+                }
+                Intervention::ForceWar { attacker, defender } => {
+                    // The warfare system drops declarations once the science
+                    // race is won or either side has fallen.
+                    let all_metrics = world.resource::<AllNationMetrics>();
+                    let fallen = [*attacker, *defender]
+                        .into_iter()
+                        .find(|nation| all_metrics.0.get(nation).is_none_or(|m| m.is_destroyed));
+                    let finished = world.resource::<ScienceVictory>().finished;
+                    let mut wars = world.resource_mut::<Wars>();
+                    if finished {
+                        format!(
+                            "cannot force {} to war on {}: the science race is won",
+                            registry.name(*attacker),
+                            registry.name(*defender)
+                        )
+                    } else if let Some(nation) = fallen {
+                        format!(
+                            "cannot force {} to war on {}: {} has fallen",
+                            registry.name(*attacker),
+                            registry.name(*defender),
+                            registry.name(nation)
+                        )
+                    } else if attacker == defender || wars.at_war(*attacker, *defender) {
+                        format!(
+                            "cannot force {} into a new war on {}",
+                            registry.name(*attacker),
+                            registry.name(*defender)
+                        )
+                    } else {
+                        let pair = [*attacker, *defender];
+                        wars.truces
+                            .retain(|truce| !(pair.contains(&truce.a) && pair.contains(&truce.b)));
+                        wars.declarations.push((*attacker, *defender));
+                        format!(
+                            "drives {} to war on {}",
+                            registry.name(*attacker),
+                            registry.name(*defender)
+                        )
+                    }
+                }
+                Intervention::SetMetric {
+                    nation,
+                    metric,
+                    value,
+                } => {
+                    let mut all_metrics = world.resource_mut::<AllNationMetrics>();
+                    match all_metrics.0.get_mut(nation) {
+                        Some(m) => {
+                            let value = value.max(0.0);
+                            match metric {
+                                Metric::Economy => m.economy = value as f32,
+                                Metric::Military => m.military = value as f32,
+                                Metric::Science => m.science = value as f32,
+                                Metric::Culture => m.culture = value as f32,
+                                Metric::Diplomacy => m.diplomacy = value as f32,
+                                Metric::Religion => m.religion = value as f32,
+                                Metric::Population => {
+                                    // Keep the age pyramid's shape.
+                                    let scale = value / (m.population.max(1) as f64);
+                                    m.youth = (m.youth as f64 * scale) as u64;
+                                    m.adult = (m.adult as f64 * scale) as u64;
+                                    m.elder = (m.elder as f64 * scale) as u64;
+                                    m.population = value as u64;
+                                }
+                            }
+                            let shown = match metric {
+                                Metric::Population => format_number_commas(value as u64),
+                                _ => format!("{value:.1}"),
+                            };
+                            format!(
+                                "sets {}'s {} to {}",
+                                registry.name(*nation),
+                                metric.label().to_lowercase(),
+                                shown
+                            )
+                        }
+                        None => format!("finds no nation {}", nation.0),
+                    }
+                }
+                Intervention::SpawnNpc {
+                    nation,
+                    faction,
+                    name,
+                } => {
+                    let id = world
+                        .query::<&Identity>()
+                        .iter(world)
+                        .map(|identity| identity.id)
+                        .max()
+                        .unwrap_or(0)
+                        + 1;
+                    let name = name.clone().unwrap_or_else(|| format!("Wanderer {id}"));
+                    world.spawn((
+                        Identity {
+                            id,
+                            name: name.clone(),
+                            faction: *faction,
+                            nation: *nation,
+                        },
+                        world_meta.anchor_position(Biome::Village),
+                        Inventory {
+                            items: Vec::new(),
+                            currency: 50.0,
+                        },
+                        Attributes {
+                            health: 100.0,
+                            stamina: 80.0,
+                            wealth: 50.0,
+                            fame: 10.0,
+                        },
+                        Personality {
+                            aggressive: 0.3,
+                            cautious: 0.4,
+                            social: 0.5,
+                            curious: 0.5,
+                        },
+                        Behavior {
+                            state: BehaviorState::Idle,
+                        },
+                        Goals {
+                            primary: GoalKind::Survival,
+                            intensity: 0.5,
+                        },
+                    ));
+                    format!("brings {} into {}", name, registry.name(*nation))
+                }
+                Intervention::RemoveNpc { id } => {
+                    let found = world
+                        .query::<(Entity, &Identity)>()
+                        .iter(world)
+                        .find(|(_, identity)| identity.id == *id)
+                        .map(|(entity, identity)| (entity, identity.name.clone()));
+                    match found {
+                        Some((entity, name)) => {
+                            world.despawn(entity);
+                            format!("removes {name}")
+                        }
+                        None => format!("finds no NPC {id} to remove"),
+                    }
+                }
+                Intervention::RaiseCarbon { ppm } => {
+                    let mut climate = world.resource_mut::<ClimateState>();
+                    climate.carbon_ppm = (climate.carbon_ppm + ppm).max(0.0);
+                    format!(
+                        "pumps {ppm:.0} ppm of carbon into the air (now {:.0} ppm)",
+                        climate.carbon_ppm
+                    )
+                }
+                Intervention::GiftTech { nation, .. }
+                    if let Some(problem) = intervention
+                        .problem(&world_meta.tech_tree, world.resource::<AllNationMetrics>()) =>
+                {
+                    format!(
+                        "cannot gift a tech to {}: {problem}",
+                        registry.name(*nation)
+                    )
+                }
+                Intervention::GiftTech { nation, tech } => {
+                    let mut all_metrics = world.resource_mut::<AllNationMetrics>();
+                    let gift = all_metrics.0.get_mut(nation).and_then(|m| {
+                        let tech = tech.or(m.research_target).or_else(|| {
+                            world_meta
+                                .tech_tree
+                                .available(&m.unlocked_techs)
+                                .next()
+                                .map(|node| node.tech)
+                        })?;
+                        if m.unlocked_techs.contains(&tech) {
+                            return None;
+                        }
+                        // The technology system settles era and effects next.
+                        m.unlocked_techs.push(tech);
+                        if m.research_target == Some(tech) {
+                            m.research_target = None;
+                        }
+                        Some(tech)
+                    });
+                    match gift {
+                        Some(tech) => {
+                            world.resource_mut::<WorldEventLog>().push(
+                                WorldEvent::tech_discovered(tick, epoch, season, *nation, tech),
+                            );
+                            format!("gifts {} to {}", tech.label(), registry.name(*nation))
+                        }
+                        None => format!("has no new tech to gift {}", registry.name(*nation)),
+                    }
+                }
+            };
+        world
+            .resource_mut::<WorldEventLog>()
+            .push(WorldEvent::intervention(
+                tick,
+                epoch,
+                season,
+                intervention,
+                summary,
+            ));
+    }
+}
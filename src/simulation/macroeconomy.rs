@@ -0,0 +1,114 @@
+//! The business cycle: every nation runs its own small macro model of
+//! output, prices, jobs and credit, and falls into crises that can spread
+//! to its trading partners.
+
+use std::collections::BTreeMap;
+
+use bevy_ecs::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::Nation;
+
+/// Inflation the central bank aims for, in percent.
+pub const INFLATION_TARGET: f32 = 2.0;
+/// Unemployment, in percent, of an economy running at its potential.
+pub const NATURAL_UNEMPLOYMENT: f32 = 6.0;
+/// Policy rate, in percent above inflation, that neither feeds nor starves
+/// demand.
+pub const NEUTRAL_RATE: f32 = 2.0;
+/// Share by which credit may run above its trend before it counts as a
+/// boom.
+const BOOM_THRESHOLD: f32 = 0.15;
+
+/// The kind of economic crisis a nation falls into.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CrisisKind {
+    /// Output fell well below potential.
+    Recession,
+    /// A credit boom went bust and lenders stopped lending.
+    BankingCrisis,
+    /// Prices ran away and the currency lost its value.
+    CurrencyCrisis,
+}
+
+impl CrisisKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            CrisisKind::Recession => "Recession",
+            CrisisKind::BankingCrisis => "Banking crisis",
+            CrisisKind::CurrencyCrisis => "Currency crisis",
+        }
+    }
+}
+
+/// A crisis under way.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Crisis {
+    pub kind: CrisisKind,
+    pub since: u64,
+    /// The trading partner it spread from, if it did not start at home.
+    pub origin: Option<Nation>,
+}
+
+/// One nation's economy, measured against its potential.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroState {
+    /// Percent by which output runs above (positive) or below potential.
+    pub output_gap: f32,
+    /// Rise in prices per tick, in percent.
+    pub inflation: f32,
+    /// The inflation wage and price setters expect.
+    pub expected_inflation: f32,
+    /// Percent of the workforce without work.
+    pub unemployment: f32,
+    /// The central bank's rate, in percent.
+    pub policy_rate: f32,
+    /// Private credit as a share of output, and the level it trends toward.
+    pub credit: f32,
+    pub credit_trend: f32,
+    /// Growth in credit last tick, as a share.
+    pub credit_growth: f32,
+    /// Budget deficit last tick, in ticks of full taxation; demand answers
+    /// to changes in it.
+    pub fiscal_stance: f32,
+    pub crisis: Option<Crisis>,
+}
+
+impl Default for MacroState {
+    fn default() -> Self {
+        Self {
+            output_gap: 0.0,
+            inflation: INFLATION_TARGET,
+            expected_inflation: INFLATION_TARGET,
+            unemployment: NATURAL_UNEMPLOYMENT,
+            policy_rate: NEUTRAL_RATE + INFLATION_TARGET,
+            credit: 1.0,
+            credit_trend: 1.0,
+            credit_growth: 0.0,
+            fiscal_stance: 0.0,
+            crisis: None,
+        }
+    }
+}
+
+impl MacroState {
+    /// Share by which credit runs above its trend.
+    pub fn credit_gap(&self) -> f32 {
+        self.credit / self.credit_trend.max(0.1) - 1.0
+    }
+
+    /// How far credit runs past the point where it counts as a boom; 0
+    /// outside a boom.
+    pub fn boom(&self) -> f32 {
+        (self.credit_gap() - BOOM_THRESHOLD).max(0.0)
+    }
+
+    /// The policy rate less inflation.
+    pub fn real_rate(&self) -> f32 {
+        self.policy_rate - self.inflation
+    }
+}
+
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct MacroEconomies(pub BTreeMap<Nation, MacroState>);
@@ -0,0 +1,202 @@
+//! Goods markets: every nation keeps a stock and a price for each good.
+//! Gatherers and traders bring goods in, the population and the state take
+//! them out, and the price follows how many ticks of demand are on hand.
+
+use std::collections::BTreeMap;
+
+use bevy_ecs::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::{Inventory, ItemKind, ItemStack, Nation, NationRegistry, WorldMetadata};
+
+/// Equipment traded on every market besides the biome resources.
+const EQUIPMENT: [&str; 3] = ["Dagger", "Tools", "Bow"];
+/// Artifacts traded on every market.
+const ARTIFACTS: [&str; 2] = ["Sun Relic", "Ancient Idol"];
+/// Ticks of demand a market likes to have in stock; at exactly this cover a
+/// good sells at its base price.
+const STOCK_TICKS: f32 = 10.0;
+/// How strongly the price answers to the cover ratio.
+const ELASTICITY: f32 = 0.5;
+/// Share of the gap to its target price a price closes each tick.
+const PRICE_ADJUST: f32 = 0.1;
+/// Bounds on a price, as multiples of the good's base price.
+const PRICE_FLOOR: f32 = 0.25;
+const PRICE_CEILING: f32 = 4.0;
+
+impl ItemKind {
+    pub fn name(&self) -> &str {
+        match self {
+            ItemKind::Resource(name) | ItemKind::Equipment(name) | ItemKind::Artifact(name) => name,
+        }
+    }
+
+    /// Price of one unit in a market with exactly the stock it wants.
+    pub fn base_price(&self) -> f32 {
+        match self {
+            ItemKind::Resource(_) => 4.0,
+            ItemKind::Equipment(_) => 12.0,
+            ItemKind::Artifact(_) => 30.0,
+        }
+    }
+}
+
+impl Inventory {
+    pub fn add(&mut self, item: ItemKind, quantity: u32) {
+        if quantity == 0 {
+            return;
+        }
+        match self.items.iter_mut().find(|stack| stack.item == item) {
+            Some(stack) => stack.quantity += quantity,
+            None => self.items.push(ItemStack { item, quantity }),
+        }
+    }
+
+    /// Takes up to `quantity` units of the good named `name` and returns how
+    /// many it took.
+    pub fn take(&mut self, name: &str, quantity: u32) -> u32 {
+        let Some(index) = self
+            .items
+            .iter()
+            .position(|stack| stack.item.name() == name)
+        else {
+            return 0;
+        };
+        let taken = quantity.min(self.items[index].quantity);
+        self.items[index].quantity -= taken;
+        if self.items[index].quantity == 0 {
+            self.items.remove(index);
+        }
+        taken
+    }
+}
+
+/// One good on one nation's market.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketGood {
+    pub item: ItemKind,
+    pub price: f32,
+    /// Units on sale.
+    pub stock: f32,
+    /// Units wanted per tick.
+    pub demand: f32,
+}
+
+impl MarketGood {
+    fn new(item: ItemKind) -> Self {
+        Self {
+            price: item.base_price(),
+            item,
+            stock: 0.0,
+            demand: 0.0,
+        }
+    }
+
+    /// How far the price sits above (positive) or below its base, as a
+    /// fraction of the base.
+    pub fn pressure(&self) -> f32 {
+        self.price / self.item.base_price() - 1.0
+    }
+}
+
+/// A nation's market, by good name.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Market {
+    pub goods: BTreeMap<String, MarketGood>,
+}
+
+impl Market {
+    fn new(catalog: &[ItemKind]) -> Self {
+        Self {
+            goods: catalog
+                .iter()
+                .map(|item| (item.name().to_string(), MarketGood::new(item.clone())))
+                .collect(),
+        }
+    }
+
+    pub fn price(&self, name: &str) -> Option<f32> {
+        self.goods.get(name).map(|good| good.price)
+    }
+
+    /// Puts `quantity` units of `item` on sale and returns what they fetch.
+    pub fn sell(&mut self, item: &ItemKind, quantity: u32) -> f32 {
+        let good = self
+            .goods
+            .entry(item.name().to_string())
+            .or_insert_with(|| MarketGood::new(item.clone()));
+        good.stock += quantity as f32;
+        good.price * quantity as f32
+    }
+
+    /// Buys as many whole units of `name`, up to `quantity`, as are in stock
+    /// and `budget` pays for. Returns the good, the units and their cost.
+    pub fn buy(&mut self, name: &str, quantity: u32, budget: f32) -> Option<(ItemKind, u32, f32)> {
+        let good = self.goods.get_mut(name)?;
+        let affordable = (budget / good.price).floor().max(0.0) as u32;
+        let bought = quantity.min(affordable).min(good.stock.floor() as u32);
+        if bought == 0 {
+            return None;
+        }
+        good.stock -= bought as f32;
+        Some((good.item.clone(), bought, good.price * bought as f32))
+    }
+
+    /// The good in stock, other than `except`, selling furthest below its
+    /// base price.
+    pub fn cheapest(&self, except: Option<&str>) -> Option<&MarketGood> {
+        self.goods
+            .values()
+            .filter(|good| good.stock >= 1.0 && Some(good.item.name()) != except)
+            .min_by(|a, b| a.pressure().total_cmp(&b.pressure()))
+    }
+
+    /// The good whose price sits furthest from its base, either way.
+    pub fn most_pressed(&self) -> Option<&MarketGood> {
+        self.goods
+            .values()
+            .max_by(|a, b| a.pressure().abs().total_cmp(&b.pressure().abs()))
+    }
+
+    /// Consumes one tick of demand, `wants` giving the units wanted of each
+    /// good, and moves every price toward what the remaining cover warrants.
+    pub fn clear(&mut self, wants: impl Fn(&ItemKind) -> f32) {
+        for good in self.goods.values_mut() {
+            good.demand = wants(&good.item).max(0.0);
+            good.stock = (good.stock - good.demand).max(0.0);
+            let base = good.item.base_price();
+            let cover = (good.demand * STOCK_TICKS + 0.5) / (good.stock + 0.5);
+            let target = base * cover.powf(ELASTICITY).clamp(PRICE_FLOOR, PRICE_CEILING);
+            good.price += (target - good.price) * PRICE_ADJUST;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct Markets(pub BTreeMap<Nation, Market>);
+
+impl Markets {
+    /// Opens a market in every nation for the biome resources, equipment and
+    /// artifacts, each at its base price.
+    pub fn new(nations: &NationRegistry, world_meta: &WorldMetadata) -> Self {
+        let mut resources: Vec<&String> = world_meta
+            .biomes
+            .values()
+            .flat_map(|biome| &biome.resource_profile)
+            .collect();
+        resources.sort();
+        resources.dedup();
+        let catalog: Vec<ItemKind> = resources
+            .into_iter()
+            .map(|name| ItemKind::Resource(name.clone()))
+            .chain(EQUIPMENT.map(|name| ItemKind::Equipment(name.to_string())))
+            .chain(ARTIFACTS.map(|name| ItemKind::Artifact(name.to_string())))
+            .collect();
+        Self(
+            nations
+                .ids()
+                .map(|nation| (nation, Market::new(&catalog)))
+                .collect(),
+        )
+    }
+}
@@ -2,37 +2,61 @@ use std::sync::{Arc, RwLock};
 
 use bevy_ecs::prelude::*;
 use bevy_ecs::schedule::Schedule;
+use rand::Rng;
 use std::collections::{HashMap, HashSet};
 
 pub mod blocs;
 pub mod components;
 pub mod events;
+pub mod fiscal;
 pub mod grid;
+pub mod intervention;
 pub mod localization;
+pub mod macroeconomy;
+pub mod market;
 pub mod nation;
 pub mod observer;
+pub mod persistence;
+pub mod player;
 pub mod resources;
+pub mod rng;
+pub mod serde_pairs;
+pub mod strategy;
 pub mod systems;
 pub mod technology;
+pub mod terrain;
+pub mod trade;
+pub mod wars;
 pub mod world;
 
 pub use blocs::*;
 pub use components::*;
 pub use events::*;
+pub use fiscal::*;
 pub use grid::*;
+pub use intervention::*;
 pub use localization::*;
+pub use macroeconomy::*;
+pub use market::*;
 pub use nation::*;
 pub use observer::*;
+pub use player::*;
 pub use resources::CosmicTimeline;
 pub use resources::*;
+pub use rng::*;
+pub use strategy::*;
 pub use systems::*;
 pub use technology::*;
+pub use trade::*;
+pub use wars::*;
 pub use world::*;
 
 pub struct SimulationWorld {
     world: World,
     schedule: Schedule,
     observer: Arc<RwLock<ObserverSnapshot>>,
+    /// Observer interventions waiting for the next tick.
+    interventions: Vec<Intervention>,
 }
 
 impl SimulationWorld {
@@ -41,80 +65,63 @@ impl SimulationWorld {
         Self::with_observer(config, Arc::new(RwLock::new(ObserverSnapshot::default())))
     }
 
+    #[allow(dead_code)]
     pub fn with_observer(
         config: SimulationConfig,
         observer: Arc<RwLock<ObserverSnapshot>>,
     ) -> Self {
+        Self::with_metadata(config, WorldMetadata::default(), observer)
+    }
+
+    /// Builds a world from designer-supplied metadata instead of the built-in one.
+    pub fn with_metadata(
+        config: SimulationConfig,
+        metadata: WorldMetadata,
+        observer: Arc<RwLock<ObserverSnapshot>>,
+    ) -> Self {
+        let nations = NationRegistry::new(&metadata.nations, config.grid_radius);
+        let rng = SimulationRng::new(config.seed);
         let mut world = World::default();
         world.insert_resource(config.clone());
-        world.insert_resource(AllNationMetrics::default());
-        world.insert_resource(AllNationCivState::default());
+        world.insert_resource(NationStrategies::new(&metadata.nations, &rng));
+        world.insert_resource(rng);
+        world.insert_resource(AllNationMetrics::new(&nations));
+        world.insert_resource(AllNationCivState::new(&nations));
         world.insert_resource(NuclearBlasts::default());
+        world.insert_resource(NuclearArsenals::default());
         world.insert_resource(WarFatigue::default());
         world.insert_resource(WorldRichness::default());
         world.insert_resource(ClimateState::default());
+        world.insert_resource(Markets::new(&nations, &metadata));
+        world.insert_resource(TradeRoutes::default());
+        world.insert_resource(Treasuries::default());
+        world.insert_resource(MacroEconomies::default());
         world.insert_resource(WorldBlocs::default());
         world.insert_resource(WorldTime::default());
-        world.insert_resource(WorldMetadata::default());
+        world.insert_resource(metadata);
         world.insert_resource(WorldEventLog::default());
-        world.insert_resource(ScienceVictory::default());
+        world.insert_resource(ScienceVictory::new(&nations));
         world.insert_resource(IdeologyMatrix::default());
         world.insert_resource(DiplomaticRelations::default());
         world.insert_resource(CivilizationalCycles::default());
         world.insert_resource(SupplyState::default());
+        world.insert_resource(ArmyRoster::default());
+        world.insert_resource(Wars::default());
+        world.insert_resource(PlayerControl::default());
         let mut cosmic = CosmicTimeline::default();
         cosmic.timescale_years_per_tick = config.years_per_tick;
         world.insert_resource(cosmic);
         world.insert_resource(CivilizationalLedger::default());
+        world.insert_resource(nations);
 
         seed_entities(&mut world);
         seed_grid(&mut world);
 
-        let mut schedule = Schedule::default();
-        schedule.add_systems(
-            (
-                cosmic_time_system,
-                ai_state_transition_system,
-                combat_cleanup_system, // Clean up combat from previous tick
-                economy_system,
-                environment_system,
-                civilization_system,
-                technology_system,
-                warfare_system, // Handles starting new combat
-                science_victory_system,
-                climate_system,
-                nuclear_decay_system,
-                peace_recovery_system,
-            )
-                .chain(),
-        );
-        schedule.add_systems(
-            (
-                richness_overlay_system,
-                climate_impact_system,
-                flood_system,
-                supply_chain_system,
-                supply_impact_system,
-                bloc_system,
-                war_fatigue_system,
-                territory_system,
-                cycle_system,
-                security_system,
-                demography_system,
-                event_generation_system,
-                ideology_system,
-                mission_system,
-                diplomacy_system,
-                logging_system,
-            )
-                .chain(),
-        );
-        schedule.add_systems(extinction_system);
-
         Self {
             world,
-            schedule,
+            schedule: build_schedule(),
             observer,
+            interventions: Vec::new(),
         }
     }
 
@@ -123,31 +130,46 @@ impl SimulationWorld {
             let mut time = self.world.resource_mut::<WorldTime>();
             time.tick += 1;
         }
+        let interventions = std::mem::take(&mut self.interventions);
+        apply_interventions(&mut self.world, interventions);
 
         self.schedule.run(&mut self.world);
+        self.record_ledger();
         self.refresh_observer_snapshot();
     }
 
+    /// True once the interstellar victory track has completed.
+    pub fn is_finished(&self) -> bool {
+        self.world.resource::<ScienceVictory>().finished
+    }
+
     pub fn set_timescale(&mut self, years_per_tick: f64) {
         if let Some(mut cosmic) = self.world.get_resource_mut::<CosmicTimeline>() {
             cosmic.timescale_years_per_tick = years_per_tick;
         }
     }
 
-    fn refresh_observer_snapshot(&mut self) {
-        let tick = self.world.resource::<WorldTime>().tick;
-        let world_meta = self.world.resource::<WorldMetadata>().clone();
-        let metrics = self.world.resource::<AllNationMetrics>().clone();
-        let civ_state = self.world.resource::<AllNationCivState>().clone();
-        let nuclear = self.world.resource::<NuclearBlasts>().0.clone();
-        let war_fatigue = self.world.resource::<WarFatigue>().clone();
-        let richness = self.world.resource::<WorldRichness>().clone();
-        let climate = self.world.resource::<ClimateState>().clone();
-        let ideology = self.world.resource::<IdeologyMatrix>().clone();
-        let diplo = self.world.resource::<DiplomaticRelations>().clone();
-        let cosmic = self.world.resource::<CosmicTimeline>().clone();
-        let mut ledger = self.world.resource_mut::<CivilizationalLedger>();
+    pub fn nation_named(&self, name: &str) -> Option<Nation> {
+        self.world.resource::<NationRegistry>().find(name)
+    }
+
+    /// Queues a player order; it is carried out on the next tick.
+    pub fn command(&mut self, command: PlayerCommand) {
+        self.world
+            .resource_mut::<PlayerControl>()
+            .pending
+            .push(command);
+    }
+
+    /// Queues an observer intervention; it takes effect at the start of the
+    /// next tick.
+    pub fn intervene(&mut self, intervention: Intervention) {
+        self.interventions.push(intervention);
+    }
+
+    fn record_ledger(&mut self) {
         let (total_pop, total_gdp) = {
+            let metrics = self.world.resource::<AllNationMetrics>();
             let mut pop = 0u64;
             let mut gdp = 0f32;
             for (_, m) in metrics.0.iter() {
@@ -158,6 +180,7 @@ impl SimulationWorld {
             }
             (pop, gdp)
         };
+        let mut ledger = self.world.resource_mut::<CivilizationalLedger>();
         ledger.population_history.push(total_pop);
         ledger.gdp_history.push(total_gdp);
         if ledger.population_history.len() > 512 {
@@ -176,6 +199,21 @@ impl SimulationWorld {
                 keep
             });
         }
+    }
+
+    fn refresh_observer_snapshot(&mut self) {
+        let tick = self.world.resource::<WorldTime>().tick;
+        let seed = self.world.resource::<SimulationRng>().seed();
+        let world_meta = self.world.resource::<WorldMetadata>().clone();
+        let metrics = self.world.resource::<AllNationMetrics>().clone();
+        let civ_state = self.world.resource::<AllNationCivState>().clone();
+        let nuclear = self.world.resource::<NuclearBlasts>().0.clone();
+        let war_fatigue = self.world.resource::<WarFatigue>().clone();
+        let richness = self.world.resource::<WorldRichness>().clone();
+        let climate = self.world.resource::<ClimateState>().clone();
+        let ideology = self.world.resource::<IdeologyMatrix>().clone();
+        let diplo = self.world.resource::<DiplomaticRelations>().clone();
+        let cosmic = self.world.resource::<CosmicTimeline>().clone();
         let science_victory_snapshot = {
             let tracker = self.world.resource::<ScienceVictory>();
             let ledger = self.world.resource::<CivilizationalLedger>();
@@ -217,7 +255,13 @@ impl SimulationWorld {
             let mut hexes = HashMap::new();
             let mut query = self.world.query::<(&AxialCoord, &Hex)>();
             for (coord, hex) in query.iter(&self.world) {
-                hexes.insert(*coord, observer::HexSnapshot { owner: hex.owner });
+                hexes.insert(
+                    *coord,
+                    observer::HexSnapshot {
+                        owner: hex.owner,
+                        biome: hex.biome,
+                    },
+                );
             }
             observer::HexGridSnapshot {
                 hexes,
@@ -263,6 +307,25 @@ impl SimulationWorld {
             )
             .collect::<Vec<_>>();
 
+        let armies = {
+            let mut query = self.world.query::<(&AxialCoord, &Army)>();
+            let mut armies: Vec<ArmySnapshot> = query
+                .iter(&self.world)
+                .map(|(coord, army)| ArmySnapshot {
+                    id: army.id,
+                    nation: army.nation,
+                    coord: *coord,
+                    strength: army.strength,
+                    supply: army.supply,
+                    morale: army.morale,
+                    enemy: army.enemy,
+                    objective: army.objective,
+                })
+                .collect();
+            armies.sort_by_key(|army| army.id);
+            armies
+        };
+
         let combat_hexes = {
             let mut combat_hexes = HashSet::new();
             let mut query = self.world.query::<(&AxialCoord, &InCombat)>();
@@ -275,6 +338,7 @@ impl SimulationWorld {
         if let Ok(mut snapshot) = self.observer.write() {
             snapshot.update(
                 tick,
+                seed,
                 epoch,
                 season,
                 cosmic.cosmic_age_years,
@@ -305,21 +369,127 @@ impl SimulationWorld {
                         .collect(),
                 },
                 observer::DiplomaticSnapshot {
-                    trust: diplo.trust.iter().map(|(n, v)| (*n, *v)).collect(),
-                    fear: diplo.fear.iter().map(|(n, v)| (*n, *v)).collect(),
+                    opinions: diplo
+                        .standing
+                        .keys()
+                        .map(|&(holder, subject)| {
+                            ((holder, subject), diplo.opinion(holder, subject))
+                        })
+                        .collect(),
+                    trust: diplo.trust.clone(),
+                    fear: diplo.fear.clone(),
+                    rivalry: diplo.rivalry.clone(),
+                    friction: diplo.friction.clone(),
+                    memories: diplo.memories.clone(),
                     alliances: diplo.alliances.clone(),
                     sanctions: diplo.sanctions.clone(),
                 },
                 science_victory_snapshot,
                 entities,
+                armies,
                 events,
                 combat_hexes,
                 nuclear.keys().cloned().collect(),
             );
+            snapshot
+                .nations
+                .clone_from(self.world.resource::<NationRegistry>());
+            snapshot.tech_tree.clone_from(&world_meta.tech_tree);
+            snapshot
+                .wars
+                .clone_from(&self.world.resource::<Wars>().active);
+            snapshot
+                .blocs
+                .clone_from(&self.world.resource::<WorldBlocs>().blocs);
+            snapshot
+                .arsenals
+                .clone_from(self.world.resource::<NuclearArsenals>());
+            snapshot
+                .strategies
+                .clone_from(self.world.resource::<NationStrategies>());
+            snapshot
+                .player
+                .clone_from(self.world.resource::<PlayerControl>());
+            snapshot
+                .markets
+                .clone_from(self.world.resource::<Markets>());
+            snapshot
+                .trade_routes
+                .clone_from(&self.world.resource::<TradeRoutes>().routes);
+            snapshot
+                .supply
+                .clone_from(&self.world.resource::<SupplyState>().nations);
+            snapshot
+                .treasuries
+                .clone_from(&self.world.resource::<Treasuries>().0);
+            snapshot
+                .economies
+                .clone_from(&self.world.resource::<MacroEconomies>().0);
         }
     }
 }
 
+fn build_schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_systems(
+        (
+            cosmic_time_system,
+            ai_state_transition_system,
+            combat_cleanup_system, // Clean up combat from previous tick
+            economy_system,
+            environment_system,
+            civilization_system,
+            technology_system,
+            nuclear_arsenal_system,
+            strategy_system, // Each nation picks its action for the tick
+            player_system,   // ...except the one the player commands
+            fiscal_system,   // Treasuries tax, borrow and spend by budget
+            warfare_system,  // Sends armies on campaign
+            muster_system,
+            army_movement_system,
+            battle_system,
+            peace_talks_system,
+            science_victory_system,
+            climate_system,
+            nuclear_decay_system,
+            peace_recovery_system,
+        )
+            .chain()
+            // Both chains write the same resources; left unordered, the
+            // executor would interleave them differently from run to run.
+            .before(richness_overlay_system),
+    );
+    schedule.add_systems(
+        (
+            richness_overlay_system,
+            climate_impact_system,
+            flood_system,
+            supply_chain_system,
+            supply_impact_system,
+            trade_route_system, // Routes ship goods between nations' markets
+            macro_system,       // Output, prices, jobs and credit through the cycle
+            bloc_system,
+            war_fatigue_system,
+            territory_system,
+            cycle_system,
+            security_system,
+            demography_system,
+            event_generation_system,
+            ideology_system,
+            mission_system,
+            diplomacy_system,
+            logging_system,
+        )
+            .chain(),
+    );
+    schedule.add_systems(
+        extinction_system
+            .after(peace_recovery_system)
+            .before(richness_overlay_system),
+    );
+    schedule
+}
+
 fn seasonal_effect_for(season: &str, tick: u64) -> observer::SeasonEffectSnapshot {
     // Animated seasonal shifts to drive UI and minor simulation flavor.
     // Uses deterministic wave so tick speed affects intensity.
@@ -357,78 +527,90 @@ fn seasonal_effect_for(season: &str, tick: u64) -> observer::SeasonEffectSnapsho
 }
 
 fn seed_grid(world: &mut World) {
-    let config = world.resource::<SimulationConfig>().clone();
-    let radius = config.grid_radius;
+    let radius = world.resource::<SimulationConfig>().grid_radius;
+    let samples = terrain::generate(world.resource::<SimulationRng>(), radius);
+    let climate = world.resource::<ClimateState>();
+    let biomes: HashMap<AxialCoord, Biome> = samples
+        .iter()
+        .map(|(coord, sample)| (*coord, terrain::classify(*sample, climate)))
+        .collect();
+
+    // Move each capital onto the nearest habitable land, then let
+    // every nation flood outward from it.
+    let mut registry = world.resource_mut::<NationRegistry>();
+    let targets: Vec<AxialCoord> = registry.nations.iter().map(|info| info.capital).collect();
+    for (info, capital) in registry
+        .nations
+        .iter_mut()
+        .zip(terrain::land_capitals(&biomes, &targets))
+    {
+        info.capital = capital;
+    }
+    let claims: Vec<(Nation, AxialCoord)> = registry
+        .nations
+        .iter()
+        .map(|info| (info.id, info.capital))
+        .collect();
+    let owners = terrain::allocate_land(&biomes, &claims);
+
+    // Deposits draw from their own stream so they never disturb the landforms.
+    let metadata = world.resource::<WorldMetadata>().clone();
+    let mut prospect = world
+        .resource::<SimulationRng>()
+        .keyed_stream(RngStream::Terrain, 0, 1);
     let mut hex_entities = HashMap::new();
-
-    let sectors = [
-        Nation::Tera,
-        Nation::Sora,
-        Nation::Aqua,
-        Nation::Solar,
-        Nation::Luna,
-    ];
-
-    for q in -radius..=radius {
-        for r in (-radius).max(-q - radius)..=radius.min(-q + radius) {
-            let coord = AxialCoord { q, r };
-
-            // Circular land mask to keep the world round and leave ocean border
-            let axial_distance = |a: AxialCoord, b: AxialCoord| {
-                ((a.q - b.q).abs() + (a.q + a.r - b.q - b.r).abs() + (a.r - b.r).abs()) / 2
-            };
-            let land_radius = (radius - 2).max(1);
-            if axial_distance(coord, AxialCoord::new(0, 0)) > land_radius {
-                continue;
+    for coord in hex_coords(radius) {
+        let sample = samples[&coord];
+        let biome = biomes[&coord];
+        let mut deposits = Vec::new();
+        for resource in metadata
+            .biomes
+            .get(&biome)
+            .map(|meta| meta.resource_profile.as_slice())
+            .unwrap_or_default()
+        {
+            if prospect.gen_bool(0.3) {
+                deposits.push(Deposit {
+                    resource: resource.clone(),
+                    richness: prospect.gen_range(0.1..0.6),
+                });
             }
-
-            // 5-way wedge (pentagon) for equal land division
-            let angle = (r as f32 * (3.0_f32).sqrt() / 2.0)
-                .atan2(q as f32 + r as f32 / 2.0)
-                .to_degrees();
-            let angle = (angle + 360.0) % 360.0;
-            let sector_size = 360.0 / sectors.len() as f32;
-            let index =
-                ((angle + sector_size / 2.0) / sector_size).floor() as usize % sectors.len();
-            let owner = sectors[index];
-
-            // Elevation adds flood dynamics; biome assignment varies by elevation.
-            let elevation =
-                ((angle / 72.0).sin() * 0.3 + 0.7 + (q as f32 * 0.02) + (r as f32 * 0.02))
-                    .clamp(0.1, 1.4);
-            let biome = if elevation < 0.35 {
-                Biome::Market
-            } else if elevation < 0.6 {
-                Biome::Plains
-            } else if elevation < 0.9 {
-                Biome::Forest
-            } else {
-                Biome::Village
-            };
-            let hex_entity = world
-                .spawn((
-                    coord,
-                    Hex {
-                        owner,
-                        elevation,
-                        biome,
-                    },
-                ))
-                .id();
-            hex_entities.insert(coord, hex_entity);
         }
+        let hex_entity = world
+            .spawn((
+                coord,
+                Hex {
+                    owner: owners.get(&coord).copied(),
+                    elevation: sample.elevation,
+                    moisture: sample.moisture,
+                    temperature: sample.temperature,
+                    biome,
+                    yields: metadata.biome_yields(biome),
+                    deposits,
+                },
+            ))
+            .id();
+        hex_entities.insert(coord, hex_entity);
     }
     world.insert_resource(HexGrid {
         hexes: hex_entities,
         radius,
     });
+
+    // Territory is a count of owned land, so it starts from the initial split.
+    let shares = territory_shares(world.query::<&Hex>().iter(world));
+    for (nation, m) in world.resource_mut::<AllNationMetrics>().0.iter_mut() {
+        m.territory = shares.get(nation).copied().unwrap_or(0.0);
+    }
 }
 
 fn seed_entities(world: &mut World) {
     use BehaviorState::*;
-    use Nation::*;
 
     let world_meta = world.resource::<WorldMetadata>().clone();
+    // Templates are spread over the first nations, wrapping in small scenarios.
+    let nation_count = world.resource::<NationRegistry>().nations.len().max(1);
+    let nation = |index: usize| Nation((index % nation_count) as u16);
 
     let npc_templates = [
         (
@@ -436,7 +618,7 @@ fn seed_entities(world: &mut World) {
                 id: 1,
                 name: "Calix".to_string(),
                 faction: Faction::MerchantGuild,
-                nation: Tera,
+                nation: nation(0),
             },
             world_meta.anchor_position(Biome::Market),
             Inventory {
@@ -469,7 +651,7 @@ fn seed_entities(world: &mut World) {
                 id: 2,
                 name: "Rena".to_string(),
                 faction: Faction::BanditClans,
-                nation: Sora,
+                nation: nation(1),
             },
             world_meta.anchor_position(Biome::Forest),
             Inventory {
@@ -502,7 +684,7 @@ fn seed_entities(world: &mut World) {
                 id: 3,
                 name: "Aria".to_string(),
                 faction: Faction::ExplorersLeague,
-                nation: Aqua,
+                nation: nation(2),
             },
             world_meta.anchor_position(Biome::Plains),
             Inventory {
@@ -532,7 +714,7 @@ fn seed_entities(world: &mut World) {
                 id: 4,
                 name: "Lys".to_string(),
                 faction: Faction::TempleOfSuns,
-                nation: Tera,
+                nation: nation(0),
             },
             world_meta.anchor_position(Biome::Village),
             Inventory {
@@ -1,44 +1,139 @@
+use bevy_ecs::prelude::Resource;
 use colored::Color as ColoredColor;
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum Nation {
-    Tera,
-    Sora,
-    Aqua,
-    Solar,
-    Luna,
-}
+use crate::simulation::{AiProfile, AxialCoord, sector_capitals};
+
+pub const MIN_NATIONS: usize = 2;
+pub const MAX_NATIONS: usize = 20;
+
+/// Identifier of a nation. Its name, color and capital live in [`NationRegistry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Nation(pub u16);
 
 impl Nation {
-    pub fn name(&self) -> &'static str {
-        match self {
-            Nation::Tera => "Tera",
-            Nation::Sora => "Sora",
-            Nation::Aqua => "Aqua",
-            Nation::Solar => "Solar",
-            Nation::Luna => "Luna",
-        }
-    }
-
-    pub fn color(&self) -> Color {
-        match self {
-            Nation::Tera => Color::Blue,
-            Nation::Sora => Color::Red,
-            Nation::Aqua => Color::Green,
-            Nation::Solar => Color::Yellow,
-            Nation::Luna => Color::White,
-        }
-    }
-
-    pub fn logging_color(&self) -> ColoredColor {
-        match self {
-            Nation::Tera => ColoredColor::Blue,
-            Nation::Sora => ColoredColor::Red,
-            Nation::Aqua => ColoredColor::Green,
-            Nation::Solar => ColoredColor::Yellow,
-            Nation::Luna => ColoredColor::White,
-        }
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A nation as a scenario describes it; the registry assigns its id and capital.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NationProfile {
+    pub name: String,
+    /// RGB color used on the map, in panels and in the console log.
+    pub color: [u8; 3],
+    /// Leadership temperament; drawn from the run's seed when left out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai: Option<AiProfile>,
+}
+
+impl NationProfile {
+    /// The first `count` nations of the built-in roster (at most [`MAX_NATIONS`]).
+    pub fn roster(count: usize) -> Vec<NationProfile> {
+        ROSTER
+            .iter()
+            .take(count)
+            .map(|(name, color)| NationProfile {
+                name: name.to_string(),
+                color: *color,
+                ai: None,
+            })
+            .collect()
+    }
+}
+
+const ROSTER: [(&str, [u8; 3]); MAX_NATIONS] = [
+    ("Tera", [66, 135, 245]),
+    ("Sora", [230, 57, 70]),
+    ("Aqua", [46, 204, 113]),
+    ("Solar", [241, 196, 15]),
+    ("Luna", [236, 240, 241]),
+    ("Vesta", [155, 89, 182]),
+    ("Ignis", [230, 126, 34]),
+    ("Boreal", [26, 188, 156]),
+    ("Zephyr", [135, 206, 250]),
+    ("Ember", [192, 57, 43]),
+    ("Gaia", [39, 174, 96]),
+    ("Astra", [255, 105, 180]),
+    ("Coral", [255, 127, 80]),
+    ("Onyx", [127, 140, 141]),
+    ("Vale", [189, 183, 107]),
+    ("Cirrus", [176, 196, 222]),
+    ("Umbra", [142, 68, 173]),
+    ("Halo", [255, 215, 0]),
+    ("Nova", [0, 206, 209]),
+    ("Sable", [160, 82, 45]),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NationInfo {
+    pub id: Nation,
+    pub name: String,
+    pub color: [u8; 3],
+    pub capital: AxialCoord,
+}
+
+/// Every nation in the run, indexed by id. Ids are dense, starting at zero.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct NationRegistry {
+    pub nations: Vec<NationInfo>,
+}
+
+impl NationRegistry {
+    /// Assigns ids in profile order and spaces the capitals evenly around the
+    /// grid; seeding the terrain then moves each onto land.
+    pub fn new(profiles: &[NationProfile], grid_radius: i32) -> Self {
+        let capitals = sector_capitals(grid_radius, profiles.len());
+        let nations = profiles
+            .iter()
+            .zip(capitals)
+            .enumerate()
+            .map(|(index, (profile, capital))| NationInfo {
+                id: Nation(index as u16),
+                name: profile.name.clone(),
+                color: profile.color,
+                capital,
+            })
+            .collect();
+        Self { nations }
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = Nation> + '_ {
+        self.nations.iter().map(|info| info.id)
+    }
+
+    pub fn get(&self, nation: Nation) -> Option<&NationInfo> {
+        self.nations.get(nation.index())
+    }
+
+    /// Looks a nation up by name, ignoring case.
+    pub fn find(&self, name: &str) -> Option<Nation> {
+        self.nations
+            .iter()
+            .find(|info| info.name.eq_ignore_ascii_case(name))
+            .map(|info| info.id)
+    }
+
+    pub fn name(&self, nation: Nation) -> &str {
+        self.get(nation)
+            .map_or("Unknown", |info| info.name.as_str())
+    }
+
+    pub fn color(&self, nation: Nation) -> Color {
+        self.get(nation).map_or(Color::Gray, |info| {
+            Color::Rgb(info.color[0], info.color[1], info.color[2])
+        })
+    }
+
+    pub fn logging_color(&self, nation: Nation) -> ColoredColor {
+        self.get(nation)
+            .map_or(ColoredColor::White, |info| ColoredColor::TrueColor {
+                r: info.color[0],
+                g: info.color[1],
+                b: info.color[2],
+            })
     }
 }
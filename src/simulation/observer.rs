@@ -1,11 +1,13 @@
 //! Shared observer snapshot structures exported via the API.
 
 use crate::simulation::{
-    AllNationCivState, AllNationMetrics, AxialCoord, BehaviorState, Biome, Faction, Nation,
-    WorldEvent,
+    AllNationCivState, AllNationMetrics, Alliance, AxialCoord, BehaviorState, Biome, Bloc, Faction,
+    MacroState, Markets, Memory, Nation, NationRegistry, NationStrategies, NationSupply,
+    NuclearArsenals, PlayerControl, Sanction, TechTree, TradeRoute, Treasury, War, WorldEvent,
 };
-use serde::Serialize;
-use std::collections::{HashMap, HashSet};
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct EntitySnapshot {
@@ -24,13 +26,27 @@ pub struct EntitySnapshot {
 
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct HexGridSnapshot {
+    #[serde(serialize_with = "crate::simulation::serde_pairs::serialize")]
     pub hexes: HashMap<AxialCoord, HexSnapshot>,
     pub radius: i32,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct HexSnapshot {
-    pub owner: Nation,
+    pub owner: Option<Nation>,
+    pub biome: Biome,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArmySnapshot {
+    pub id: u64,
+    pub nation: Nation,
+    pub coord: AxialCoord,
+    pub strength: f32,
+    pub supply: f32,
+    pub morale: f32,
+    pub enemy: Option<Nation>,
+    pub objective: Option<AxialCoord>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -59,12 +75,23 @@ pub struct WorldOverlaySnapshot {
     pub ideology_volatility: Vec<(Nation, f32)>,
 }
 
+/// Directed matrices are keyed `(holder, subject)`; rivalry and friction
+/// are symmetric and keyed by the ordered pair.
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct DiplomaticSnapshot {
-    pub trust: Vec<(Nation, f32)>,
-    pub fear: Vec<(Nation, f32)>,
-    pub alliances: Vec<(Nation, Nation)>,
-    pub sanctions: Vec<(Nation, Nation)>,
+    #[serde(serialize_with = "crate::simulation::serde_pairs::serialize")]
+    pub opinions: BTreeMap<(Nation, Nation), f32>,
+    #[serde(serialize_with = "crate::simulation::serde_pairs::serialize")]
+    pub trust: BTreeMap<(Nation, Nation), f32>,
+    #[serde(serialize_with = "crate::simulation::serde_pairs::serialize")]
+    pub fear: BTreeMap<(Nation, Nation), f32>,
+    #[serde(serialize_with = "crate::simulation::serde_pairs::serialize")]
+    pub rivalry: BTreeMap<(Nation, Nation), f32>,
+    #[serde(serialize_with = "crate::simulation::serde_pairs::serialize")]
+    pub friction: BTreeMap<(Nation, Nation), f32>,
+    pub memories: Vec<Memory>,
+    pub alliances: Vec<Alliance>,
+    pub sanctions: Vec<Sanction>,
 }
 
 #[derive(Debug, Clone, Serialize, Default)]
@@ -96,6 +123,7 @@ pub struct ScienceVictorySnapshot {
 #[derive(Debug, Clone, Serialize)]
 pub struct ObserverSnapshot {
     pub tick: u64,
+    pub seed: u64,
     pub epoch: String,
     pub season: String,
     pub cosmic_age_years: f64,
@@ -103,6 +131,7 @@ pub struct ObserverSnapshot {
     pub geologic_stage: String,
     pub extinction_events: u32,
     pub season_effect: SeasonEffectSnapshot,
+    pub nations: NationRegistry,
     pub all_metrics: AllNationMetrics,
     pub civ_state: AllNationCivState,
     pub grid: HexGridSnapshot,
@@ -110,15 +139,42 @@ pub struct ObserverSnapshot {
     pub diplomacy: DiplomaticSnapshot,
     pub science_victory: ScienceVictorySnapshot,
     pub entities: Vec<EntitySnapshot>,
+    /// Field armies, ordered by id.
+    pub armies: Vec<ArmySnapshot>,
+    /// Wars being fought, oldest first.
+    pub wars: Vec<War>,
+    /// Standing blocs, oldest first.
+    pub blocs: Vec<Bloc>,
+    pub arsenals: NuclearArsenals,
+    /// Each nation's AI profile, goal and latest action.
+    pub strategies: NationStrategies,
+    /// The nation the player commands and its pending orders.
+    pub player: PlayerControl,
+    /// Each nation's goods market.
+    pub markets: Markets,
+    /// Open trade routes, oldest first.
+    pub trade_routes: Vec<TradeRoute>,
+    /// Each nation's supply chain.
+    pub supply: BTreeMap<Nation, NationSupply>,
+    /// Each nation's public finances.
+    pub treasuries: BTreeMap<Nation, Treasury>,
+    /// Each nation's place in the business cycle.
+    pub economies: BTreeMap<Nation, MacroState>,
     pub events: Vec<WorldEvent>,
+    #[serde(serialize_with = "serialize_coord_set")]
     pub combat_hexes: HashSet<AxialCoord>,
+    #[serde(serialize_with = "serialize_coord_set")]
     pub nuclear_hexes: HashSet<AxialCoord>,
+    /// The run's tech tree, for checking interventions; not published.
+    #[serde(skip)]
+    pub tech_tree: TechTree,
 }
 
 impl ObserverSnapshot {
     pub fn new() -> Self {
         Self {
             tick: 0,
+            seed: 0,
             epoch: "Dawn".to_string(),
             season: "Season of Bloom".to_string(),
             cosmic_age_years: 0.0,
@@ -132,6 +188,7 @@ impl ObserverSnapshot {
                 yield_shift: 0.0,
                 risk_shift: 0.0,
             },
+            nations: NationRegistry::default(),
             all_metrics: AllNationMetrics::default(),
             civ_state: AllNationCivState::default(),
             grid: HexGridSnapshot::default(),
@@ -139,15 +196,28 @@ impl ObserverSnapshot {
             diplomacy: DiplomaticSnapshot::default(),
             science_victory: ScienceVictorySnapshot::default(),
             entities: Vec::new(),
+            armies: Vec::new(),
+            wars: Vec::new(),
+            blocs: Vec::new(),
+            arsenals: NuclearArsenals::default(),
+            strategies: NationStrategies::default(),
+            player: PlayerControl::default(),
+            markets: Markets::default(),
+            trade_routes: Vec::new(),
+            supply: BTreeMap::new(),
+            treasuries: BTreeMap::new(),
+            economies: BTreeMap::new(),
             events: Vec::new(),
             combat_hexes: HashSet::new(),
             nuclear_hexes: HashSet::new(),
+            tech_tree: TechTree::default(),
         }
     }
 
     pub fn update(
         &mut self,
         tick: u64,
+        seed: u64,
         epoch: String,
         season: String,
         cosmic_age_years: f64,
@@ -162,11 +232,13 @@ impl ObserverSnapshot {
         diplomacy: DiplomaticSnapshot,
         science_victory: ScienceVictorySnapshot,
         entities: Vec<EntitySnapshot>,
+        armies: Vec<ArmySnapshot>,
         events: Vec<WorldEvent>,
         combat_hexes: HashSet<AxialCoord>,
         nuclear_hexes: HashSet<AxialCoord>,
     ) {
         self.tick = tick;
+        self.seed = seed;
         self.epoch = epoch;
         self.season = season;
         self.cosmic_age_years = cosmic_age_years;
@@ -181,10 +253,133 @@ impl ObserverSnapshot {
         self.diplomacy = diplomacy;
         self.science_victory = science_victory;
         self.entities = entities;
+        self.armies = armies;
         self.events = events;
         self.combat_hexes = combat_hexes;
         self.nuclear_hexes = nuclear_hexes;
     }
+
+    /// What changed between `previous` and this snapshot, for streaming
+    /// clients that should not re-download the whole grid every tick.
+    pub fn delta_since(&self, previous: &ObserverSnapshot) -> TickDelta {
+        let mut hex_owners: Vec<(AxialCoord, Option<Nation>)> = self
+            .grid
+            .hexes
+            .iter()
+            .filter(|(coord, hex)| {
+                previous
+                    .grid
+                    .hexes
+                    .get(coord)
+                    .is_none_or(|old| old.owner != hex.owner)
+            })
+            .map(|(coord, hex)| (*coord, hex.owner))
+            .collect();
+        hex_owners.sort_by_key(|(coord, _)| (coord.q, coord.r));
+        let mut hex_biomes: Vec<(AxialCoord, Biome)> = self
+            .grid
+            .hexes
+            .iter()
+            .filter(|(coord, hex)| {
+                previous
+                    .grid
+                    .hexes
+                    .get(coord)
+                    .is_none_or(|old| old.biome != hex.biome)
+            })
+            .map(|(coord, hex)| (*coord, hex.biome))
+            .collect();
+        hex_biomes.sort_by_key(|(coord, _)| (coord.q, coord.r));
+
+        // Diff metrics field by field through their JSON form so new
+        // `NationMetrics` fields are picked up without touching this code.
+        let mut metrics = BTreeMap::new();
+        for (nation, current) in &self.all_metrics.0 {
+            let Ok(Value::Object(current)) = serde_json::to_value(current) else {
+                continue;
+            };
+            let old = previous
+                .all_metrics
+                .0
+                .get(nation)
+                .and_then(|old| serde_json::to_value(old).ok());
+            let changed: Map<String, Value> = current
+                .into_iter()
+                .filter(|(field, value)| {
+                    old.as_ref()
+                        .and_then(|old| old.get(field))
+                        .is_none_or(|old| old != value)
+                })
+                .collect();
+            if !changed.is_empty() {
+                metrics.insert(*nation, changed);
+            }
+        }
+
+        let armies = self
+            .armies
+            .iter()
+            .filter(|army| !previous.armies.contains(army))
+            .cloned()
+            .collect();
+        let disbanded_armies = previous
+            .armies
+            .iter()
+            .filter(|old| self.armies.iter().all(|army| army.id != old.id))
+            .map(|old| old.id)
+            .collect();
+
+        TickDelta {
+            tick: self.tick,
+            previous_tick: previous.tick,
+            hex_owners,
+            hex_biomes,
+            metrics,
+            armies,
+            disbanded_armies,
+            new_combat_hexes: sorted_coords(self.combat_hexes.difference(&previous.combat_hexes)),
+            cleared_combat_hexes: sorted_coords(
+                previous.combat_hexes.difference(&self.combat_hexes),
+            ),
+            new_nuclear_hexes: sorted_coords(
+                self.nuclear_hexes.difference(&previous.nuclear_hexes),
+            ),
+        }
+    }
+}
+
+fn sorted_coords<'a>(coords: impl Iterator<Item = &'a AxialCoord>) -> Vec<AxialCoord> {
+    let mut coords: Vec<AxialCoord> = coords.copied().collect();
+    coords.sort_by_key(|coord| (coord.q, coord.r));
+    coords
+}
+
+/// Writes a coordinate set in order, so same-seed runs serialize alike.
+fn serialize_coord_set<S: Serializer>(
+    coords: &HashSet<AxialCoord>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(sorted_coords(coords.iter()))
+}
+
+/// Compact change set between two published snapshots.
+#[derive(Debug, Clone, Serialize)]
+pub struct TickDelta {
+    pub tick: u64,
+    pub previous_tick: u64,
+    /// Hexes whose owner changed, with their new owner.
+    pub hex_owners: Vec<(AxialCoord, Option<Nation>)>,
+    /// Hexes reclassified by the climate, with their new biome.
+    pub hex_biomes: Vec<(AxialCoord, Biome)>,
+    /// Per nation, only the `NationMetrics` fields whose value changed.
+    pub metrics: BTreeMap<Nation, Map<String, Value>>,
+    /// Armies that were raised, moved or changed in any way.
+    pub armies: Vec<ArmySnapshot>,
+    /// Ids of armies that no longer exist.
+    pub disbanded_armies: Vec<u64>,
+    pub new_combat_hexes: Vec<AxialCoord>,
+    pub cleared_combat_hexes: Vec<AxialCoord>,
+    pub new_nuclear_hexes: Vec<AxialCoord>,
 }
 
 impl Default for ObserverSnapshot {
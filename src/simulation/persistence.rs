@@ -0,0 +1,303 @@
+//! Save and restore the complete simulation state as a versioned JSON file.
+//!
+//! Every resource is captured, including the run's `WorldMetadata`, plus each
+//! hex, agent and army entity with its components and the observer
+//! interventions still queued for the next tick. `HexGrid` only maps coordinates
+//! to entity ids, so it is rebuilt on load.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use anyhow::{Context, bail};
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// Bump whenever a saved resource or component changes shape.
+pub const SAVE_FORMAT_VERSION: u32 = 21;
+
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    resources: SavedResources,
+    hexes: Vec<SavedHex>,
+    agents: Vec<SavedAgent>,
+    armies: Vec<SavedArmy>,
+    interventions: Vec<Intervention>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedResources {
+    config: SimulationConfig,
+    rng: SimulationRng,
+    time: WorldTime,
+    metrics: AllNationMetrics,
+    civ_state: AllNationCivState,
+    nuclear: NuclearBlasts,
+    arsenals: NuclearArsenals,
+    war_fatigue: WarFatigue,
+    richness: WorldRichness,
+    climate: ClimateState,
+    markets: Markets,
+    trade_routes: TradeRoutes,
+    treasuries: Treasuries,
+    economies: MacroEconomies,
+    blocs: WorldBlocs,
+    metadata: WorldMetadata,
+    nations: NationRegistry,
+    events: WorldEventLog,
+    science_victory: ScienceVictory,
+    ideology: IdeologyMatrix,
+    diplomacy: DiplomaticRelations,
+    cycles: CivilizationalCycles,
+    supply: SupplyState,
+    cosmic: CosmicTimeline,
+    ledger: CivilizationalLedger,
+    armies: ArmyRoster,
+    wars: Wars,
+    strategies: NationStrategies,
+    player: PlayerControl,
+}
+
+impl SavedResources {
+    fn capture(world: &World) -> Self {
+        Self {
+            config: world.resource::<SimulationConfig>().clone(),
+            rng: world.resource::<SimulationRng>().clone(),
+            time: world.resource::<WorldTime>().clone(),
+            metrics: world.resource::<AllNationMetrics>().clone(),
+            civ_state: world.resource::<AllNationCivState>().clone(),
+            nuclear: world.resource::<NuclearBlasts>().clone(),
+            arsenals: world.resource::<NuclearArsenals>().clone(),
+            war_fatigue: world.resource::<WarFatigue>().clone(),
+            richness: world.resource::<WorldRichness>().clone(),
+            climate: world.resource::<ClimateState>().clone(),
+            markets: world.resource::<Markets>().clone(),
+            trade_routes: world.resource::<TradeRoutes>().clone(),
+            treasuries: world.resource::<Treasuries>().clone(),
+            economies: world.resource::<MacroEconomies>().clone(),
+            blocs: world.resource::<WorldBlocs>().clone(),
+            metadata: world.resource::<WorldMetadata>().clone(),
+            nations: world.resource::<NationRegistry>().clone(),
+            events: world.resource::<WorldEventLog>().clone(),
+            science_victory: world.resource::<ScienceVictory>().clone(),
+            ideology: world.resource::<IdeologyMatrix>().clone(),
+            diplomacy: world.resource::<DiplomaticRelations>().clone(),
+            cycles: world.resource::<CivilizationalCycles>().clone(),
+            supply: world.resource::<SupplyState>().clone(),
+            cosmic: world.resource::<CosmicTimeline>().clone(),
+            ledger: world.resource::<CivilizationalLedger>().clone(),
+            armies: world.resource::<ArmyRoster>().clone(),
+            wars: world.resource::<Wars>().clone(),
+            strategies: world.resource::<NationStrategies>().clone(),
+            player: world.resource::<PlayerControl>().clone(),
+        }
+    }
+
+    fn restore(self, world: &mut World) {
+        world.insert_resource(self.config);
+        world.insert_resource(self.rng);
+        world.insert_resource(self.time);
+        world.insert_resource(self.metrics);
+        world.insert_resource(self.civ_state);
+        world.insert_resource(self.nuclear);
+        world.insert_resource(self.arsenals);
+        world.insert_resource(self.war_fatigue);
+        world.insert_resource(self.richness);
+        world.insert_resource(self.climate);
+        world.insert_resource(self.markets);
+        world.insert_resource(self.trade_routes);
+        world.insert_resource(self.treasuries);
+        world.insert_resource(self.economies);
+        world.insert_resource(self.blocs);
+        world.insert_resource(self.metadata);
+        world.insert_resource(self.nations);
+        world.insert_resource(self.events);
+        world.insert_resource(self.science_victory);
+        world.insert_resource(self.ideology);
+        world.insert_resource(self.diplomacy);
+        world.insert_resource(self.cycles);
+        world.insert_resource(self.supply);
+        world.insert_resource(self.cosmic);
+        world.insert_resource(self.ledger);
+        world.insert_resource(self.armies);
+        world.insert_resource(self.wars);
+        world.insert_resource(self.strategies);
+        world.insert_resource(self.player);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedHex {
+    coord: AxialCoord,
+    hex: Hex,
+    combat: Option<(InCombat, Combatants)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedArmy {
+    coord: AxialCoord,
+    army: Army,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedAgent {
+    identity: Identity,
+    position: Position,
+    inventory: Inventory,
+    attributes: Attributes,
+    personality: Personality,
+    behavior: Behavior,
+    goals: Goals,
+}
+
+impl SimulationWorld {
+    /// Writes the full world state to `path`. The file is written next to the
+    /// target first and renamed into place, so a crash never leaves a torn save.
+    pub fn save(&mut self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+
+        // Entities are stored in query order so a reload spawns them back into
+        // the same iteration order and the run continues identically.
+        let hexes = self
+            .world
+            .query::<(&AxialCoord, &Hex, Option<&InCombat>, Option<&Combatants>)>()
+            .iter(&self.world)
+            .map(|(coord, hex, in_combat, combatants)| SavedHex {
+                coord: *coord,
+                hex: hex.clone(),
+                combat: in_combat.cloned().zip(combatants.cloned()),
+            })
+            .collect();
+        let agents = self
+            .world
+            .query::<(
+                &Identity,
+                &Position,
+                &Inventory,
+                &Attributes,
+                &Personality,
+                &Behavior,
+                &Goals,
+            )>()
+            .iter(&self.world)
+            .map(
+                |(identity, position, inventory, attributes, personality, behavior, goals)| {
+                    SavedAgent {
+                        identity: identity.clone(),
+                        position: *position,
+                        inventory: inventory.clone(),
+                        attributes: attributes.clone(),
+                        personality: personality.clone(),
+                        behavior: behavior.clone(),
+                        goals: goals.clone(),
+                    }
+                },
+            )
+            .collect();
+        let armies = self
+            .world
+            .query::<(&AxialCoord, &Army)>()
+            .iter(&self.world)
+            .map(|(coord, army)| SavedArmy {
+                coord: *coord,
+                army: army.clone(),
+            })
+            .collect();
+        let save = SaveFile {
+            version: SAVE_FORMAT_VERSION,
+            resources: SavedResources::capture(&self.world),
+            hexes,
+            agents,
+            armies,
+            interventions: self.interventions.clone(),
+        };
+
+        let staging = path.with_extension("tmp");
+        let file = File::create(&staging)
+            .with_context(|| format!("failed to create {}", staging.display()))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, &save)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&staging, path)
+            .with_context(|| format!("failed to move save into {}", path.display()))?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::load_with_observer(path, Arc::new(RwLock::new(ObserverSnapshot::default())))
+    }
+
+    /// Rebuilds a world from a file written by [`SimulationWorld::save`] and
+    /// publishes its state to `observer` straight away.
+    pub fn load_with_observer(
+        path: impl AsRef<Path>,
+        observer: Arc<RwLock<ObserverSnapshot>>,
+    ) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        let save: SaveFile = serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("failed to parse save file {}", path.display()))?;
+        if save.version != SAVE_FORMAT_VERSION {
+            bail!(
+                "save file {} has format version {}, expected {}",
+                path.display(),
+                save.version,
+                SAVE_FORMAT_VERSION
+            );
+        }
+
+        let mut world = World::default();
+        let radius = save.resources.config.grid_radius;
+        save.resources.restore(&mut world);
+
+        let mut hex_entities = HashMap::new();
+        for saved in save.hexes {
+            // Spawn each hex with its full component set in one go; inserting
+            // combat afterwards would shuffle archetype rows.
+            let entity = match saved.combat {
+                Some((in_combat, combatants)) => world
+                    .spawn((saved.coord, saved.hex, in_combat, combatants))
+                    .id(),
+                None => world.spawn((saved.coord, saved.hex)).id(),
+            };
+            hex_entities.insert(saved.coord, entity);
+        }
+        world.insert_resource(HexGrid {
+            hexes: hex_entities,
+            radius,
+        });
+
+        for agent in save.agents {
+            world.spawn((
+                agent.identity,
+                agent.position,
+                agent.inventory,
+                agent.attributes,
+                agent.personality,
+                agent.behavior,
+                agent.goals,
+            ));
+        }
+
+        for saved in save.armies {
+            world.spawn((saved.coord, saved.army));
+        }
+
+        let mut simulation = Self {
+            world,
+            schedule: build_schedule(),
+            observer,
+            interventions: save.interventions,
+        };
+        simulation.refresh_observer_snapshot();
+        Ok(simulation)
+    }
+}
@@ -0,0 +1,52 @@
+//! Player command: the nation a person plays and the orders waiting to be
+//! carried out on the next tick.
+
+use bevy_ecs::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::{BudgetLine, Nation};
+
+/// An order from the player, carried out on the next tick.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum PlayerCommand {
+    /// Take command of `nation`, or hand the played nation back to its AI.
+    TakeCommand {
+        nation: Option<Nation>,
+    },
+    ShiftBudget {
+        line: BudgetLine,
+        delta: i8,
+    },
+    /// Raise (or lower) the tax rate by `delta` points.
+    ShiftTax {
+        delta: i8,
+    },
+    /// Raise (or lower) spending by `delta` points of revenue.
+    ShiftSpending {
+        delta: i8,
+    },
+    DeclareWar {
+        target: Nation,
+    },
+    /// Sue for peace in the war against `enemy`.
+    OfferPeace {
+        enemy: Nation,
+    },
+    ProposeAlliance {
+        partner: Nation,
+    },
+    Sanction {
+        target: Nation,
+    },
+}
+
+/// The played nation, if any. Every other nation, and this one when nobody
+/// plays it, is left to its strategy.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct PlayerControl {
+    pub nation: Option<Nation>,
+    pub pending: Vec<PlayerCommand>,
+    /// How the latest order turned out.
+    pub report: Option<String>,
+}
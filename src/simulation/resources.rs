@@ -2,11 +2,11 @@
 
 use std::time::Duration;
 
-use crate::simulation::Nation;
 use crate::simulation::{Era, Tech, WeaponTier};
+use crate::simulation::{Nation, NationRegistry, Yields};
 use bevy_ecs::prelude::Resource;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Resource, Serialize, Deserialize)]
 pub struct NationMetrics {
@@ -16,11 +16,14 @@ pub struct NationMetrics {
     pub diplomacy: f32, // Diplomacy
     pub religion: f32,  // Religion
     pub military: f32,
+    /// Percent of the world's land hexes this nation holds.
     pub territory: f32,
     pub is_destroyed: bool,
     pub era: Era,
     pub weapon_tier: WeaponTier,
     pub unlocked_techs: Vec<Tech>,
+    /// Tech that `research_stock` is being saved up for.
+    pub research_target: Option<Tech>,
     pub research_stock: f32,
     pub culture_stock: f32,
     pub population: u64,
@@ -30,6 +33,12 @@ pub struct NationMetrics {
     pub productivity: f32,
     pub unemployment: f32,
     pub trade_penalty: f32,
+    /// Multipliers summed from unlocked tech effects.
+    pub combat_modifier: f32,
+    pub economy_modifier: f32,
+    pub research_modifier: f32,
+    /// Supply produced by the hexes the nation owns, after climate effects.
+    pub output: Yields,
 }
 
 impl Default for NationMetrics {
@@ -46,6 +55,7 @@ impl Default for NationMetrics {
             era: Era::Dawn,
             weapon_tier: WeaponTier::KnappedStone,
             unlocked_techs: vec![Tech::Knapping],
+            research_target: None,
             research_stock: 0.0,
             culture_stock: 0.0,
             population: 3_000_000,
@@ -55,6 +65,10 @@ impl Default for NationMetrics {
             productivity: 1.0,
             unemployment: 6.0,
             trade_penalty: 0.0,
+            combat_modifier: 1.0,
+            economy_modifier: 1.0,
+            research_modifier: 1.0,
+            output: Yields::default(),
         }
     }
 }
@@ -67,6 +81,13 @@ pub struct NationCivState {
     pub production: f32,
 }
 
+/// The warheads one salvo fired and the casualties they caused.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Salvo {
+    pub warheads: u32,
+    pub casualties: u64,
+}
+
 impl Default for NationCivState {
     fn default() -> Self {
         Self {
@@ -78,38 +99,75 @@ impl Default for NationCivState {
     }
 }
 
-#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
-pub struct AllNationCivState(pub HashMap<Nation, NationCivState>);
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct AllNationCivState(pub BTreeMap<Nation, NationCivState>);
+
+impl AllNationCivState {
+    pub fn new(registry: &NationRegistry) -> Self {
+        Self(
+            registry
+                .ids()
+                .map(|nation| (nation, NationCivState::default()))
+                .collect(),
+        )
+    }
+}
 
-impl Default for AllNationCivState {
-    fn default() -> Self {
-        let mut map = HashMap::new();
-        map.insert(Nation::Tera, NationCivState::default());
-        map.insert(Nation::Sora, NationCivState::default());
-        map.insert(Nation::Aqua, NationCivState::default());
-        map.insert(Nation::Solar, NationCivState::default());
-        map.insert(Nation::Luna, NationCivState::default());
-        Self(map)
+#[derive(Debug, Resource, Clone, serde::Serialize, serde::Deserialize, Default)]
+pub struct AllNationMetrics(pub BTreeMap<Nation, NationMetrics>);
+
+impl AllNationMetrics {
+    pub fn new(registry: &NationRegistry) -> Self {
+        Self(
+            registry
+                .ids()
+                .map(|nation| (nation, NationMetrics::default()))
+                .collect(),
+        )
     }
 }
 
-#[derive(Debug, Resource, Clone, serde::Serialize, serde::Deserialize)]
-pub struct AllNationMetrics(pub HashMap<Nation, NationMetrics>);
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct NuclearBlasts(
+    #[serde(with = "crate::simulation::serde_pairs")] pub HashMap<crate::simulation::AxialCoord, u8>,
+);
+
+/// When a nuclear power is willing to fire first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NuclearDoctrine {
+    /// Only ever launches in retaliation.
+    NoFirstUse,
+    /// Will open with a strike when losing on the battlefield.
+    FirstStrike,
+}
 
-impl Default for AllNationMetrics {
-    fn default() -> Self {
-        let mut metrics = HashMap::new();
-        metrics.insert(Nation::Tera, NationMetrics::default());
-        metrics.insert(Nation::Sora, NationMetrics::default());
-        metrics.insert(Nation::Aqua, NationMetrics::default());
-        metrics.insert(Nation::Solar, NationMetrics::default());
-        metrics.insert(Nation::Luna, NationMetrics::default());
-        Self(metrics)
+impl NuclearDoctrine {
+    pub fn label(self) -> &'static str {
+        match self {
+            NuclearDoctrine::NoFirstUse => "No First Use",
+            NuclearDoctrine::FirstStrike => "First Strike",
+        }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Arsenal {
+    pub warheads: u32,
+    pub doctrine: NuclearDoctrine,
+    /// Progress toward the next warhead.
+    pub production: f32,
+}
+
+/// Warhead stockpiles of the nations that have built a nuclear arsenal.
 #[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
-pub struct NuclearBlasts(pub HashMap<crate::simulation::AxialCoord, u8>);
+pub struct NuclearArsenals(pub BTreeMap<Nation, Arsenal>);
+
+impl NuclearArsenals {
+    pub fn warheads(&self, nation: Nation) -> u32 {
+        self.0.get(&nation).map_or(0, |arsenal| arsenal.warheads)
+    }
+}
 
 #[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
 pub struct WarFatigue {
@@ -117,6 +175,12 @@ pub struct WarFatigue {
     pub history: Vec<f32>,
 }
 
+/// Hands out army ids, which are never reused within a run.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct ArmyRoster {
+    pub next_id: u64,
+}
+
 #[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
 pub struct WorldRichness {
     /// Aggregated richness score for TUI overlay (0..1)
@@ -127,39 +191,311 @@ pub struct WorldRichness {
 #[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
 pub struct IdeologyMatrix {
     /// Per-nation ideology leaning (0 = traditionalist, 100 = progressive)
-    pub leaning: HashMap<Nation, f32>,
+    pub leaning: BTreeMap<Nation, f32>,
     /// Cultural cohesion (0..100) representing how tightly narratives bind.
-    pub cohesion: HashMap<Nation, f32>,
+    pub cohesion: BTreeMap<Nation, f32>,
     /// Volatility score (0..100) that raises rebellion risk.
-    pub volatility: HashMap<Nation, f32>,
+    pub volatility: BTreeMap<Nation, f32>,
+}
+
+/// A defensive alliance between two nations, signed for a fixed term.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alliance {
+    pub a: Nation,
+    pub b: Nation,
+    pub signed: u64,
+    /// Tick at which the term runs out and the pair decide whether to renew.
+    pub expires: u64,
 }
 
+/// Trade sanctions `issuer` holds against `target`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sanction {
+    pub issuer: Nation,
+    pub target: Nation,
+    pub imposed: u64,
+    /// Tick of the next round of relief talks.
+    pub review: u64,
+}
+
+/// Why an alliance came to an end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllianceEnd {
+    /// The term ran out and relations no longer justified renewing it.
+    Expired,
+    /// Relations fell too far to keep it.
+    Soured,
+    /// The allies ended up on opposite sides of a war.
+    Betrayal,
+    /// Their ideologies drifted too far apart.
+    IdeologicalRift,
+}
+
+impl AllianceEnd {
+    pub fn label(self) -> &'static str {
+        match self {
+            AllianceEnd::Expired => "term expired",
+            AllianceEnd::Soured => "relations soured",
+            AllianceEnd::Betrayal => "betrayal in war",
+            AllianceEnd::IdeologicalRift => "ideological rift",
+        }
+    }
+}
+
+/// Something one nation holds against, or in favour of, another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryKind {
+    /// The subject declared war on the holder.
+    Invaded,
+    /// The subject put the holder under sanctions.
+    Sanctioned,
+    /// The subject walked out of a treaty the holder belongs to.
+    BrokeTreaty,
+    /// The subject ended up fighting the holder while the two were allied.
+    Betrayed,
+    /// The two fought on the same side of a war.
+    AlliedInWar,
+}
+
+impl MemoryKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            MemoryKind::Invaded => "invaded us",
+            MemoryKind::Sanctioned => "sanctioned us",
+            MemoryKind::BrokeTreaty => "broke a treaty",
+            MemoryKind::Betrayed => "betrayed our alliance",
+            MemoryKind::AlliedInWar => "allied in war",
+        }
+    }
+
+    /// Opinion the memory is worth when it is fresh.
+    pub fn impact(self) -> f32 {
+        match self {
+            MemoryKind::Invaded => -50.0,
+            MemoryKind::Sanctioned => -25.0,
+            MemoryKind::BrokeTreaty => -40.0,
+            MemoryKind::Betrayed => -60.0,
+            MemoryKind::AlliedInWar => 30.0,
+        }
+    }
+
+    /// Share of its weight a memory keeps each tick.
+    pub fn retention(self) -> f32 {
+        match self {
+            MemoryKind::Invaded | MemoryKind::BrokeTreaty => 0.995,
+            MemoryKind::Betrayed => 0.997,
+            MemoryKind::Sanctioned | MemoryKind::AlliedInWar => 0.99,
+        }
+    }
+}
+
+/// A fading memory `holder` keeps of what `subject` did at `tick`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Memory {
+    pub holder: Nation,
+    pub subject: Nation,
+    pub kind: MemoryKind,
+    pub tick: u64,
+    /// What the memory still adds to the holder's opinion of the subject.
+    pub weight: f32,
+}
+
+/// Trust a nation starts out with toward one it has not dealt with.
+const DEFAULT_TRUST: f32 = 40.0;
+/// Fear a nation starts out with toward one it has not dealt with.
+const DEFAULT_FEAR: f32 = 35.0;
+/// Opinion lost per point of rivalry.
+const RIVALRY_WEIGHT: f32 = 0.5;
+
+/// Who thinks what of whom. Every matrix keyed `(holder, subject)` is
+/// directed: it holds `holder`'s view of `subject`.
 #[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
 pub struct DiplomaticRelations {
-    /// Symmetric relationship score (-100..100)
-    pub relations: HashMap<(Nation, Nation), f32>,
-    /// Alliances (unordered pairs)
-    pub alliances: Vec<(Nation, Nation)>,
-    /// Sanctions (ordered: issuer -> target)
-    pub sanctions: Vec<(Nation, Nation)>,
-    /// Trust/fear meters
-    pub trust: HashMap<Nation, f32>,
-    pub fear: HashMap<Nation, f32>,
+    /// Standing (-100..100) `holder` grants `subject` before memories,
+    /// rivalry and border friction are counted.
+    #[serde(with = "crate::simulation::serde_pairs")]
+    pub standing: BTreeMap<(Nation, Nation), f32>,
+    pub memories: Vec<Memory>,
+    /// Symmetric rivalry (0..100) between nations competing for the same
+    /// ground, keyed by the ordered pair.
+    #[serde(with = "crate::simulation::serde_pairs")]
+    pub rivalry: BTreeMap<(Nation, Nation), f32>,
+    /// Symmetric friction from the length of the pair's shared border,
+    /// keyed by the ordered pair.
+    #[serde(with = "crate::simulation::serde_pairs")]
+    pub friction: BTreeMap<(Nation, Nation), f32>,
+    pub alliances: Vec<Alliance>,
+    pub sanctions: Vec<Sanction>,
+    /// How far `holder` trusts `subject` to keep its word (0..100).
+    #[serde(with = "crate::simulation::serde_pairs")]
+    pub trust: BTreeMap<(Nation, Nation), f32>,
+    /// How much `holder` fears `subject`'s power (0..100).
+    #[serde(with = "crate::simulation::serde_pairs")]
+    pub fear: BTreeMap<(Nation, Nation), f32>,
+}
+
+impl DiplomaticRelations {
+    pub fn pair(a: Nation, b: Nation) -> (Nation, Nation) {
+        if a < b { (a, b) } else { (b, a) }
+    }
+
+    /// `holder`'s opinion of `subject` (-100..100): its standing, plus every
+    /// memory it keeps of `subject`, less rivalry and border friction.
+    pub fn opinion(&self, holder: Nation, subject: Nation) -> f32 {
+        let standing = self
+            .standing
+            .get(&(holder, subject))
+            .copied()
+            .unwrap_or(0.0);
+        let remembered: f32 = self
+            .memories
+            .iter()
+            .filter(|memory| memory.holder == holder && memory.subject == subject)
+            .map(|memory| memory.weight)
+            .sum();
+        let pair = Self::pair(holder, subject);
+        let rivalry = self.rivalry.get(&pair).copied().unwrap_or(0.0);
+        let friction = self.friction.get(&pair).copied().unwrap_or(0.0);
+        (standing + remembered - rivalry * RIVALRY_WEIGHT - friction).clamp(-100.0, 100.0)
+    }
+
+    /// Mutual relations: the mean of what each side thinks of the other.
+    pub fn relation(&self, a: Nation, b: Nation) -> f32 {
+        (self.opinion(a, b) + self.opinion(b, a)) / 2.0
+    }
+
+    pub fn adjust_standing(&mut self, holder: Nation, subject: Nation, delta: f32) {
+        let entry = self.standing.entry((holder, subject)).or_insert(0.0);
+        *entry = (*entry + delta).clamp(-100.0, 100.0);
+    }
+
+    /// Records that `subject` did `kind` to `holder` at `tick`. A repeat
+    /// refreshes the existing memory rather than stacking a second one.
+    pub fn remember(&mut self, holder: Nation, subject: Nation, kind: MemoryKind, tick: u64) {
+        if holder == subject {
+            return;
+        }
+        let memory = Memory {
+            holder,
+            subject,
+            kind,
+            tick,
+            weight: kind.impact(),
+        };
+        match self
+            .memories
+            .iter_mut()
+            .find(|old| old.holder == holder && old.subject == subject && old.kind == kind)
+        {
+            Some(old) => *old = memory,
+            None => self.memories.push(memory),
+        }
+    }
+
+    pub fn trust(&self, holder: Nation, subject: Nation) -> f32 {
+        self.trust
+            .get(&(holder, subject))
+            .copied()
+            .unwrap_or(DEFAULT_TRUST)
+    }
+
+    pub fn adjust_trust(&mut self, holder: Nation, subject: Nation, delta: f32) {
+        let entry = self.trust.entry((holder, subject)).or_insert(DEFAULT_TRUST);
+        *entry = (*entry + delta).clamp(0.0, 100.0);
+    }
+
+    pub fn fear(&self, holder: Nation, subject: Nation) -> f32 {
+        self.fear
+            .get(&(holder, subject))
+            .copied()
+            .unwrap_or(DEFAULT_FEAR)
+    }
+
+    pub fn adjust_fear(&mut self, holder: Nation, subject: Nation, delta: f32) {
+        let entry = self.fear.entry((holder, subject)).or_insert(DEFAULT_FEAR);
+        *entry = (*entry + delta).clamp(0.0, 100.0);
+    }
+
+    /// The fear `nation` holds of whichever nation it fears most.
+    pub fn greatest_fear(&self, nation: Nation) -> f32 {
+        self.fear
+            .iter()
+            .filter(|((holder, _), _)| *holder == nation)
+            .map(|(_, fear)| *fear)
+            .fold(None, |most: Option<f32>, fear| {
+                Some(most.map_or(fear, |most| most.max(fear)))
+            })
+            .unwrap_or(DEFAULT_FEAR)
+    }
+
+    pub fn allied(&self, a: Nation, b: Nation) -> bool {
+        self.alliances
+            .iter()
+            .any(|alliance| Self::pair(alliance.a, alliance.b) == Self::pair(a, b))
+    }
+
+    pub fn sanctioning(&self, issuer: Nation, target: Nation) -> bool {
+        self.sanctions
+            .iter()
+            .any(|sanction| sanction.issuer == issuer && sanction.target == target)
+    }
 }
 
 #[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
 pub struct CivilizationalCycles {
-    pub golden_age: HashMap<Nation, f32>,
-    pub decline: HashMap<Nation, f32>,
+    pub golden_age: BTreeMap<Nation, f32>,
+    pub decline: BTreeMap<Nation, f32>,
+}
+
+/// One nation's supply chain on the food, energy and rare channels.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NationSupply {
+    /// Output of the nation's land this tick.
+    pub produced: Yields,
+    /// What its population used up this tick.
+    pub consumed: Yields,
+    /// Reserves carried into the next tick.
+    pub stockpile: Yields,
+    /// Received from trade partners this tick.
+    pub imports: Yields,
+    /// Sent to trade partners this tick.
+    pub exports: Yields,
+    /// Consecutive ticks the nation has fallen short of its demand.
+    pub deficit_ticks: u32,
+    /// Output per tick, oldest first.
+    pub history: Vec<Yields>,
+}
+
+impl NationSupply {
+    /// Supply on hand this tick before it is used: production, reserves and
+    /// net trade.
+    pub fn on_hand(&self) -> Yields {
+        self.stockpile + self.produced + self.imports - self.exports
+    }
 }
 
 #[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
 pub struct SupplyState {
-    pub food: f32,
-    pub energy: f32,
-    pub rare: f32,
-    pub deficit_ticks: u32,
-    pub history: Vec<(f32, f32, f32)>,
+    /// Output per person the world had when supply was first measured; a
+    /// nation needs this much per head to cover its own demand.
+    pub ration: Yields,
+    pub nations: BTreeMap<Nation, NationSupply>,
+}
+
+impl SupplyState {
+    /// What `population` needs per tick, or `None` before the ration is known.
+    pub fn demand(&self, population: u64) -> Option<Yields> {
+        (self.ration != Yields::default()).then(|| self.ration.scaled(population as f32))
+    }
+
+    /// Consecutive ticks `nation` has fallen short of its demand.
+    pub fn deficit_ticks(&self, nation: Nation) -> u32 {
+        self.nations
+            .get(&nation)
+            .map_or(0, |supply| supply.deficit_ticks)
+    }
 }
 
 /// Global Ecological/Climate State
@@ -223,10 +559,10 @@ impl Default for SpaceStage {
 /// Science/Space Victory Progress Tracking.
 #[derive(Debug, Clone, Resource, Serialize, Deserialize)]
 pub struct ScienceVictory {
-    pub progress: HashMap<Nation, f32>,
+    pub progress: BTreeMap<Nation, f32>,
     pub goal: f32,
     pub leader_history: Vec<f32>,
-    pub milestones: HashMap<Nation, u8>,
+    pub milestones: BTreeMap<Nation, u8>,
     pub finished: bool,
     pub winner: Option<Nation>,
     pub interstellar_mode: bool,
@@ -268,23 +604,22 @@ impl Default for CosmicTimeline {
     }
 }
 
+impl ScienceVictory {
+    pub fn new(registry: &NationRegistry) -> Self {
+        Self {
+            progress: registry.ids().map(|nation| (nation, 0.0)).collect(),
+            ..Self::default()
+        }
+    }
+}
+
 impl Default for ScienceVictory {
     fn default() -> Self {
-        let mut progress = HashMap::new();
-        for nation in [
-            Nation::Tera,
-            Nation::Sora,
-            Nation::Aqua,
-            Nation::Solar,
-            Nation::Luna,
-        ] {
-            progress.insert(nation, 0.0);
-        }
         Self {
-            progress,
+            progress: BTreeMap::new(),
             goal: 100.0,
             leader_history: Vec::new(),
-            milestones: HashMap::new(),
+            milestones: BTreeMap::new(),
             finished: false,
             winner: None,
             interstellar_mode: false,
@@ -312,11 +647,13 @@ impl Default for DeltaTime {
     }
 }
 
-#[derive(Debug, Clone, Resource)]
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
 pub struct SimulationConfig {
     pub tick_duration: Duration,
     pub grid_radius: i32,
     pub years_per_tick: f64,
+    /// Master seed every random stream is derived from.
+    pub seed: u64,
 }
 
 impl Default for SimulationConfig {
@@ -325,6 +662,7 @@ impl Default for SimulationConfig {
             tick_duration: Duration::from_secs(1),
             grid_radius: 12,
             years_per_tick: 1_000_000.0,
+            seed: 0,
         }
     }
 }
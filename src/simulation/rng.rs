@@ -0,0 +1,79 @@
+//! Deterministic random streams derived from the run's master seed.
+
+use bevy_ecs::prelude::Resource;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use serde::{Deserialize, Serialize};
+
+/// Independent random streams, one per consumer, so extra draws in one system
+/// never shift the sequence another system sees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    Behavior,
+    Economy,
+    Warfare,
+    Blocs,
+    Events,
+    Technology,
+    Terrain,
+    Territory,
+    Strategy,
+    Intervention,
+    Macro,
+}
+
+impl RngStream {
+    fn id(self) -> u64 {
+        match self {
+            RngStream::Behavior => 97,
+            RngStream::Economy => 131,
+            RngStream::Warfare => 257,
+            RngStream::Blocs => 313,
+            RngStream::Events => 421,
+            RngStream::Technology => 509,
+            RngStream::Terrain => 613,
+            RngStream::Territory => 719,
+            RngStream::Strategy => 827,
+            RngStream::Intervention => 929,
+            RngStream::Macro => 1031,
+        }
+    }
+}
+
+/// Shared RNG derivation service. Every system draws its per-tick generator
+/// from here, so one seed reproduces a whole run and different seeds diverge.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+pub struct SimulationRng {
+    seed: u64,
+}
+
+impl SimulationRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Generator for one system on one tick.
+    pub fn stream(&self, stream: RngStream, tick: u64) -> SmallRng {
+        self.keyed_stream(stream, tick, 0)
+    }
+
+    /// Generator for one system on one tick, further split by `key`
+    /// (an entity id, for example).
+    pub fn keyed_stream(&self, stream: RngStream, tick: u64, key: u64) -> SmallRng {
+        let mut state = splitmix64(self.seed ^ stream.id());
+        state = splitmix64(state ^ tick);
+        state = splitmix64(state ^ key);
+        SmallRng::seed_from_u64(state)
+    }
+}
+
+pub(crate) fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
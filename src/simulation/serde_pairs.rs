@@ -0,0 +1,43 @@
+//! `#[serde(with = "...")]` helper that encodes maps as `[key, value]` pairs.
+//!
+//! JSON object keys must be strings, so maps keyed by coordinates or nation
+//! pairs cannot be written as objects. Hash maps are written in key order so
+//! two runs from the same seed produce identical output.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Maps that can be written out as a sequence of pairs.
+pub trait PairMap {
+    fn serialize_pairs<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+impl<K: Serialize + Ord, V: Serialize, H: BuildHasher> PairMap for HashMap<K, V, H> {
+    fn serialize_pairs<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut pairs: Vec<_> = self.iter().collect();
+        pairs.sort_by_key(|(key, _)| *key);
+        serializer.collect_seq(pairs)
+    }
+}
+
+impl<K: Serialize, V: Serialize> PairMap for BTreeMap<K, V> {
+    fn serialize_pairs<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+pub fn serialize<M: PairMap, S: Serializer>(map: &M, serializer: S) -> Result<S::Ok, S::Error> {
+    map.serialize_pairs(serializer)
+}
+
+pub fn deserialize<'de, M, K, V, D>(deserializer: D) -> Result<M, D::Error>
+where
+    M: FromIterator<(K, V)>,
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Vec::<(K, V)>::deserialize(deserializer).map(|pairs| pairs.into_iter().collect())
+}
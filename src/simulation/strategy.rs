@@ -0,0 +1,239 @@
+//! Nation-level strategy: the actions a nation's leadership weighs each
+//! tick, the temperament that tilts the weighing, and what it chose.
+
+use std::collections::BTreeMap;
+
+use bevy_ecs::prelude::Resource;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::{Nation, NationProfile, RngStream, SimulationRng};
+
+/// One decision a nation's leadership takes in a tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategicAction {
+    /// Save the treasury for later.
+    Hold,
+    InvestMilitary,
+    InvestScience,
+    InvestCulture,
+    DeclareWar {
+        target: Nation,
+    },
+    SueForPeace {
+        war: u64,
+    },
+    JoinBloc {
+        bloc: u64,
+    },
+    Sanction {
+        target: Nation,
+    },
+    FoundCity,
+}
+
+impl StrategicAction {
+    pub fn kind(self) -> ActionKind {
+        match self {
+            StrategicAction::Hold => ActionKind::Hold,
+            StrategicAction::InvestMilitary => ActionKind::InvestMilitary,
+            StrategicAction::InvestScience => ActionKind::InvestScience,
+            StrategicAction::InvestCulture => ActionKind::InvestCulture,
+            StrategicAction::DeclareWar { .. } => ActionKind::DeclareWar,
+            StrategicAction::SueForPeace { .. } => ActionKind::SueForPeace,
+            StrategicAction::JoinBloc { .. } => ActionKind::JoinBloc,
+            StrategicAction::Sanction { .. } => ActionKind::Sanction,
+            StrategicAction::FoundCity => ActionKind::FoundCity,
+        }
+    }
+}
+
+/// A [`StrategicAction`] without its target, for tallies and weights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionKind {
+    Hold,
+    InvestMilitary,
+    InvestScience,
+    InvestCulture,
+    DeclareWar,
+    SueForPeace,
+    JoinBloc,
+    Sanction,
+    FoundCity,
+}
+
+impl ActionKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ActionKind::Hold => "Hold",
+            ActionKind::InvestMilitary => "Invest in military",
+            ActionKind::InvestScience => "Invest in science",
+            ActionKind::InvestCulture => "Invest in culture",
+            ActionKind::DeclareWar => "Declare war",
+            ActionKind::SueForPeace => "Sue for peace",
+            ActionKind::JoinBloc => "Join a bloc",
+            ActionKind::Sanction => "Sanction",
+            ActionKind::FoundCity => "Found a city",
+        }
+    }
+}
+
+/// Leadership temperament. Scales the utility of each kind of action and
+/// sets the goal a nation pursues while it feels safe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AiProfile {
+    Conqueror,
+    Scholar,
+    Diplomat,
+    Builder,
+}
+
+impl AiProfile {
+    pub const ALL: [AiProfile; 4] = [
+        AiProfile::Conqueror,
+        AiProfile::Scholar,
+        AiProfile::Diplomat,
+        AiProfile::Builder,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AiProfile::Conqueror => "Conqueror",
+            AiProfile::Scholar => "Scholar",
+            AiProfile::Diplomat => "Diplomat",
+            AiProfile::Builder => "Builder",
+        }
+    }
+
+    /// Looks a profile up by its label, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|profile| profile.label().eq_ignore_ascii_case(name))
+    }
+
+    /// Multiplier on the utility of `kind`. Kept close to 1 so that the
+    /// situation, not the temperament alone, decides what a nation does.
+    pub fn weight(self, kind: ActionKind) -> f32 {
+        use ActionKind::*;
+        match (self, kind) {
+            (_, Hold) => 1.0,
+            (AiProfile::Conqueror, InvestMilitary) => 1.25,
+            (AiProfile::Conqueror, DeclareWar) => 1.4,
+            (AiProfile::Conqueror, SueForPeace) => 0.7,
+            (AiProfile::Conqueror, Sanction) => 1.1,
+            (AiProfile::Conqueror, InvestCulture) => 0.8,
+            (AiProfile::Conqueror, _) => 0.95,
+            (AiProfile::Scholar, InvestScience) => 1.25,
+            (AiProfile::Scholar, DeclareWar) => 0.6,
+            (AiProfile::Scholar, SueForPeace | JoinBloc) => 1.15,
+            (AiProfile::Scholar, InvestMilitary) => 0.85,
+            (AiProfile::Scholar, _) => 1.0,
+            (AiProfile::Diplomat, SueForPeace | JoinBloc) => 1.25,
+            (AiProfile::Diplomat, InvestCulture | Sanction) => 1.15,
+            (AiProfile::Diplomat, DeclareWar) => 0.6,
+            (AiProfile::Diplomat, InvestMilitary) => 0.85,
+            (AiProfile::Diplomat, _) => 1.0,
+            (AiProfile::Builder, FoundCity) => 1.3,
+            (AiProfile::Builder, InvestCulture | SueForPeace) => 1.1,
+            (AiProfile::Builder, DeclareWar) => 0.75,
+            (AiProfile::Builder, _) => 0.95,
+        }
+    }
+
+    /// What the nation works toward when nothing threatens it.
+    pub fn ambition(self) -> NationGoal {
+        match self {
+            AiProfile::Conqueror => NationGoal::Conquest,
+            AiProfile::Scholar => NationGoal::Science,
+            AiProfile::Diplomat => NationGoal::Culture,
+            AiProfile::Builder => NationGoal::Growth,
+        }
+    }
+}
+
+/// What a nation is working toward; actions serving it score higher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NationGoal {
+    Conquest,
+    Science,
+    Culture,
+    Growth,
+    /// Set aside the ambition while a feared rival looms.
+    Security,
+}
+
+impl NationGoal {
+    pub fn label(self) -> &'static str {
+        match self {
+            NationGoal::Conquest => "Conquest",
+            NationGoal::Science => "Science",
+            NationGoal::Culture => "Culture",
+            NationGoal::Growth => "Growth",
+            NationGoal::Security => "Security",
+        }
+    }
+
+    pub fn serves(self, kind: ActionKind) -> bool {
+        use ActionKind::*;
+        matches!(
+            (self, kind),
+            (NationGoal::Conquest, DeclareWar | InvestMilitary)
+                | (NationGoal::Science, InvestScience)
+                | (NationGoal::Culture, InvestCulture | JoinBloc)
+                | (NationGoal::Growth, FoundCity)
+                | (
+                    NationGoal::Security,
+                    InvestMilitary | JoinBloc | SueForPeace
+                )
+        )
+    }
+}
+
+/// One nation's leadership: its temperament, current goal and record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Strategy {
+    pub profile: AiProfile,
+    pub goal: NationGoal,
+    pub last_action: StrategicAction,
+    /// How often each kind of action was chosen over the run.
+    pub tally: BTreeMap<ActionKind, u32>,
+}
+
+impl Strategy {
+    pub fn new(profile: AiProfile) -> Self {
+        Self {
+            profile,
+            goal: profile.ambition(),
+            last_action: StrategicAction::Hold,
+            tally: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct NationStrategies(pub BTreeMap<Nation, Strategy>);
+
+impl NationStrategies {
+    /// Gives each nation the profile its scenario names, or one drawn from
+    /// the seed.
+    pub fn new(profiles: &[NationProfile], rng: &SimulationRng) -> Self {
+        let mut draw = rng.stream(RngStream::Strategy, 0);
+        Self(
+            profiles
+                .iter()
+                .enumerate()
+                .map(|(index, profile)| {
+                    let ai = profile
+                        .ai
+                        .unwrap_or_else(|| AiProfile::ALL[draw.gen_range(0..AiProfile::ALL.len())]);
+                    (Nation(index as u16), Strategy::new(ai))
+                })
+                .collect(),
+        )
+    }
+}
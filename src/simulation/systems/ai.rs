@@ -1,12 +1,11 @@
 //! AI state transition system.
 
 use bevy_ecs::prelude::*;
-use rand::rngs::SmallRng;
-use rand::{Rng, SeedableRng};
+use rand::Rng;
 
 use crate::simulation::{
-    Behavior, BehaviorState, Identity, Personality, Position, ScienceVictory, WorldMetadata,
-    WorldTime,
+    Behavior, BehaviorState, Identity, Personality, Position, RngStream, ScienceVictory,
+    SimulationRng, WorldMetadata, WorldTime,
 };
 
 const IDLE_TRANSITIONS: &[(BehaviorState, f32)] = &[
@@ -125,6 +124,7 @@ pub fn ai_state_transition_system(
     world_meta: Res<WorldMetadata>,
     time: Res<WorldTime>,
     science: Res<ScienceVictory>,
+    rng_source: Res<SimulationRng>,
 ) {
     let (segment, season) = world_meta.epoch_for_tick(time.tick);
 
@@ -155,12 +155,7 @@ pub fn ai_state_transition_system(
             weighted_options.push((*next_state, weight));
         }
 
-        let mut rng = SmallRng::seed_from_u64(
-            time.tick
-                .wrapping_mul(97)
-                .wrapping_add(identity.id)
-                .wrapping_mul(53),
-        );
+        let mut rng = rng_source.keyed_stream(RngStream::Behavior, time.tick, identity.id);
         let total_weight: f32 = weighted_options.iter().map(|(_, w)| *w).sum();
         let mut threshold = rng.gen_range(0.0..total_weight);
 
@@ -0,0 +1,315 @@
+//! Field armies: raising them from a nation's military, marching them along
+//! hex routes and keeping them supplied. Battles and occupation are resolved
+//! in `battle_system`.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::simulation::{
+    AllNationCivState, AllNationMetrics, Army, ArmyRoster, AxialCoord, Biome, Hex, InCombat,
+    Nation, NationRegistry, Wars,
+};
+use bevy_ecs::prelude::*;
+
+/// Most armies a single nation keeps in the field.
+const MAX_ARMIES: usize = 3;
+/// Each full share of this much military adds one army to the quota.
+const MILITARY_PER_ARMY: f32 = 25.0;
+/// Armies worn down below this strength disband.
+pub const DISBAND_STRENGTH: f32 = 0.5;
+/// Campaigns are abandoned once supply falls below this.
+const RETREAT_SUPPLY: f32 = 0.2;
+/// Campaigns are abandoned once morale falls below this.
+const RETREAT_MORALE: f32 = 0.2;
+/// How far an army looks for enemy armies to engage.
+const ENGAGE_RANGE: i32 = 4;
+
+/// How many armies `military` supports.
+fn army_quota(military: f32) -> usize {
+    (1 + (military / MILITARY_PER_ARMY) as usize).min(MAX_ARMIES)
+}
+
+/// Movement needed to enter a hex of this biome.
+fn step_cost(biome: Biome) -> u32 {
+    match biome {
+        Biome::Ocean | Biome::Coast => 4,
+        Biome::Mountain | Biome::Glacier => 3,
+        Biome::Forest | Biome::Jungle | Biome::Tundra => 2,
+        _ => 1,
+    }
+}
+
+/// Supply lost per tick away from friendly, quiet land.
+fn supply_drain(biome: Biome) -> f32 {
+    match biome {
+        Biome::Ocean | Biome::Coast => 0.12,
+        Biome::Desert | Biome::Glacier | Biome::Tundra | Biome::Mountain | Biome::Jungle => 0.08,
+        _ => 0.05,
+    }
+}
+
+/// Where a nation raises and rests its armies: the capital while it holds
+/// it, otherwise its first hex in coordinate order.
+fn home_hexes(
+    nations: &NationRegistry,
+    owners: &HashMap<AxialCoord, Option<Nation>>,
+) -> HashMap<Nation, AxialCoord> {
+    let mut homes = HashMap::new();
+    for info in &nations.nations {
+        if owners.get(&info.capital) == Some(&Some(info.id)) {
+            homes.insert(info.id, info.capital);
+        }
+    }
+    let mut coords: Vec<&AxialCoord> = owners.keys().collect();
+    coords.sort_by_key(|coord| (coord.q, coord.r));
+    for coord in coords {
+        if let Some(owner) = owners[coord] {
+            homes.entry(owner).or_insert(*coord);
+        }
+    }
+    homes
+}
+
+/// The hex owned by `nation` closest to `from`, ties broken by coordinate.
+pub fn nearest_owned(
+    owners: &HashMap<AxialCoord, Option<Nation>>,
+    from: AxialCoord,
+    nation: Nation,
+) -> Option<AxialCoord> {
+    owners
+        .iter()
+        .filter(|(_, owner)| **owner == Some(nation))
+        .map(|(coord, _)| *coord)
+        .min_by_key(|coord| (coord.distance(from), coord.q, coord.r))
+}
+
+/// The closest army within `range` of `from` that `wanted` accepts, given
+/// each army as `(nation, enemy, position)`.
+fn nearest_army(
+    positions: &[(Nation, Option<Nation>, AxialCoord)],
+    from: AxialCoord,
+    range: i32,
+    wanted: impl Fn(Nation, Option<Nation>, AxialCoord) -> bool,
+) -> Option<AxialCoord> {
+    positions
+        .iter()
+        .filter(|(nation, enemy, coord)| {
+            coord.distance(from) <= range && wanted(*nation, *enemy, *coord)
+        })
+        .map(|(_, _, coord)| *coord)
+        .min_by_key(|coord| (coord.distance(from), coord.q, coord.r))
+}
+
+/// Cheapest route from `from` to `to`, returned with the next step last and
+/// without `from` itself. Empty if `to` cannot be reached.
+fn route(biomes: &HashMap<AxialCoord, Biome>, from: AxialCoord, to: AxialCoord) -> Vec<AxialCoord> {
+    let mut best: HashMap<AxialCoord, u32> = HashMap::from([(from, 0)]);
+    let mut came_from: HashMap<AxialCoord, AxialCoord> = HashMap::new();
+    let mut frontier = BinaryHeap::from([Reverse((0_u32, from.q, from.r))]);
+    while let Some(Reverse((cost, q, r))) = frontier.pop() {
+        let coord = AxialCoord::new(q, r);
+        if coord == to {
+            break;
+        }
+        if cost > best[&coord] {
+            continue;
+        }
+        for next in coord.neighbors() {
+            let Some(&biome) = biomes.get(&next) else {
+                continue;
+            };
+            let next_cost = cost + step_cost(biome);
+            if best.get(&next).is_none_or(|&known| next_cost < known) {
+                best.insert(next, next_cost);
+                came_from.insert(next, coord);
+                frontier.push(Reverse((next_cost, next.q, next.r)));
+            }
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut step = to;
+    while step != from {
+        path.push(step);
+        match came_from.get(&step) {
+            Some(previous) => step = *previous,
+            None => return Vec::new(),
+        }
+    }
+    path
+}
+
+/// Raises a new army at home whenever a nation fields fewer than its
+/// military supports, one per nation per tick, and disbands the armies of
+/// nations that have been destroyed.
+pub fn muster_system(
+    mut commands: Commands,
+    metrics: Res<AllNationMetrics>,
+    nations: Res<NationRegistry>,
+    mut roster: ResMut<ArmyRoster>,
+    hexes: Query<(&AxialCoord, &Hex)>,
+    armies: Query<(Entity, &Army)>,
+) {
+    let owners: HashMap<AxialCoord, Option<Nation>> = hexes
+        .iter()
+        .map(|(coord, hex)| (*coord, hex.owner))
+        .collect();
+    let homes = home_hexes(&nations, &owners);
+
+    let mut fielded: HashMap<Nation, usize> = HashMap::new();
+    for (entity, army) in armies.iter() {
+        let destroyed = metrics.0.get(&army.nation).is_none_or(|m| m.is_destroyed);
+        if destroyed || !homes.contains_key(&army.nation) {
+            commands.entity(entity).despawn();
+        } else {
+            *fielded.entry(army.nation).or_default() += 1;
+        }
+    }
+
+    for info in &nations.nations {
+        let Some(m) = metrics.0.get(&info.id) else {
+            continue;
+        };
+        let Some(&home) = homes.get(&info.id) else {
+            continue;
+        };
+        if m.is_destroyed || m.military <= 1.0 {
+            continue;
+        }
+        let quota = army_quota(m.military);
+        if fielded.get(&info.id).copied().unwrap_or(0) < quota {
+            commands.spawn((
+                home,
+                Army {
+                    id: roster.next_id,
+                    nation: info.id,
+                    strength: m.military / quota as f32,
+                    supply: 1.0,
+                    morale: 0.8,
+                    enemy: None,
+                    objective: None,
+                    path: Vec::new(),
+                    march: 0.0,
+                },
+            ));
+            roster.next_id += 1;
+        }
+    }
+}
+
+/// Supplies every army from the ground it stands on, picks its objective and
+/// moves it one hex along its route once it has banked enough movement for
+/// the terrain. On campaign an army seeks out nearby enemy armies, or else
+/// the nearest enemy hex; at home it intercepts invaders of its own land.
+pub fn army_movement_system(
+    metrics: Res<AllNationMetrics>,
+    civ_state: Res<AllNationCivState>,
+    wars: Res<Wars>,
+    hexes: Query<(&AxialCoord, &Hex, Option<&InCombat>), Without<Army>>,
+    mut armies: Query<(&mut Army, &mut AxialCoord), Without<Hex>>,
+) {
+    let mut biomes = HashMap::new();
+    let mut owners = HashMap::new();
+    let mut fronts = HashSet::new();
+    for (coord, hex, in_combat) in hexes.iter() {
+        biomes.insert(*coord, hex.biome);
+        owners.insert(*coord, hex.owner);
+        if in_combat.is_some() {
+            fronts.insert(*coord);
+        }
+    }
+
+    // Positions as of the start of the tick, so every army reacts to the same picture.
+    let positions: Vec<(Nation, Option<Nation>, AxialCoord)> = armies
+        .iter()
+        .map(|(army, coord)| (army.nation, army.enemy, *coord))
+        .collect();
+
+    for (mut army, mut coord) in armies.iter_mut() {
+        let nation = army.nation;
+        let Some(m) = metrics.0.get(&nation) else {
+            continue;
+        };
+        let biome = biomes.get(&coord).copied().unwrap_or(Biome::Plains);
+        let at_home = owners.get(&coord) == Some(&Some(nation)) && !fronts.contains(&coord);
+
+        if at_home {
+            // Rest and reinforce toward the army's share of the nation's military.
+            let full_strength = m.military / army_quota(m.military) as f32;
+            let rested_morale = civ_state
+                .0
+                .get(&nation)
+                .map_or(0.8, |civ| (civ.stability / 100.0).clamp(0.5, 1.0));
+            army.supply = (army.supply + 0.1).min(1.0);
+            army.strength += (full_strength - army.strength).max(0.0) * 0.1;
+            army.morale += (rested_morale - army.morale) * 0.05;
+        } else {
+            army.supply = (army.supply - supply_drain(biome)).max(0.0);
+            if army.supply <= 0.0 {
+                army.strength *= 0.95;
+                army.morale = (army.morale - 0.02).max(0.0);
+            }
+        }
+
+        if let Some(enemy) = army.enemy {
+            let beaten = metrics.0.get(&enemy).is_none_or(|e| e.is_destroyed);
+            if beaten
+                || !wars.at_war(nation, enemy)
+                || army.supply < RETREAT_SUPPLY
+                || army.morale < RETREAT_MORALE
+            {
+                army.enemy = None;
+                army.objective = None;
+            }
+        }
+
+        let objective = match army.enemy {
+            Some(enemy) => nearest_army(&positions, *coord, ENGAGE_RANGE, |other, _, _| {
+                other == enemy
+            })
+            .or_else(|| nearest_owned(&owners, *coord, enemy)),
+            None => nearest_army(
+                &positions,
+                *coord,
+                ENGAGE_RANGE * 2,
+                |_, other_enemy, other_coord| {
+                    other_enemy == Some(nation) && owners.get(&other_coord) == Some(&Some(nation))
+                },
+            )
+            .or_else(|| {
+                // Stray armies head back to the nearest friendly hex.
+                if owners.get(&coord) == Some(&Some(nation)) {
+                    None
+                } else {
+                    nearest_owned(&owners, *coord, nation)
+                }
+            }),
+        };
+        if army.enemy.is_some() && objective.is_none() {
+            army.enemy = None;
+        }
+        if objective != army.objective {
+            army.objective = objective;
+            army.path.clear();
+        }
+        let Some(objective) = objective else {
+            army.march = 0.0;
+            continue;
+        };
+        if army.path.is_empty() && objective != *coord {
+            army.path = route(&biomes, *coord, objective);
+        }
+
+        let Some(&next) = army.path.last() else {
+            army.march = 0.0;
+            continue;
+        };
+        let cost = biomes.get(&next).map_or(1, |biome| step_cost(*biome)) as f32;
+        army.march += 1.0;
+        if army.march >= cost {
+            army.march -= cost;
+            army.path.pop();
+            *coord = next;
+        }
+    }
+}
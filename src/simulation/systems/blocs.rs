@@ -1,81 +1,289 @@
+//! Blocs as standing organisations: nations found them with partners they
+//! trust, apply to join those their strategy favours, and are voted out when they
+//! fall out with the rest. A bloc with fewer than two members dissolves.
+
 use bevy_ecs::prelude::*;
-use rand::SeedableRng;
-use rand::{Rng, rngs::SmallRng};
+use bevy_ecs::system::SystemParam;
+use rand::Rng;
+use std::collections::BTreeSet;
+
+use crate::simulation::{
+    AllNationMetrics, Bloc, BlocChange, BlocKind, DiplomaticRelations, MemoryKind, Nation,
+    NationRegistry, RngStream, SimulationRng, Wars, WorldBlocs, WorldEvent, WorldEventLog,
+    WorldMetadata, WorldTime,
+};
+
+/// Chance per tick that a pair of willing nations founds a bloc.
+const FOUNDING_CHANCE: f64 = 0.05;
+/// Opinion a research partner must hold of every member.
+const RESEARCH_RELATION: f32 = 30.0;
+/// Opinion a defense partner needs of members it is not allied with.
+const DEFENSE_RELATION: f32 = 60.0;
+/// Opinion of a target below which a nation wants it sanctioned.
+const SANCTION_RELATION: f32 = -45.0;
+/// Members whose relations with the rest sink below this are expelled.
+const EXPULSION_RELATION: f32 = -25.0;
+/// Trust a treaty breaker loses in the eyes of every member.
+const BREACH_TRUST: f32 = 20.0;
+
+/// Whether `nation` wants to stand in a bloc of `kind` alongside `others`.
+pub(crate) fn willing(
+    diplo: &DiplomaticRelations,
+    kind: BlocKind,
+    target: Option<Nation>,
+    nation: Nation,
+    others: &BTreeSet<Nation>,
+) -> bool {
+    match kind {
+        BlocKind::ResearchPact => others
+            .iter()
+            .all(|other| diplo.opinion(nation, *other) >= RESEARCH_RELATION),
+        BlocKind::DefenseTreaty => others.iter().all(|other| {
+            diplo.allied(nation, *other) || diplo.opinion(nation, *other) >= DEFENSE_RELATION
+        }),
+        BlocKind::Sanction => target.is_some_and(|target| {
+            target != nation && diplo.opinion(nation, target) <= SANCTION_RELATION
+        }),
+    }
+}
 
-use crate::simulation::{AllNationMetrics, BlocKind, WorldBlocs, WorldTime};
+/// Average relations between `nation` and the rest of `members`.
+fn standing(diplo: &DiplomaticRelations, nation: Nation, members: &BTreeSet<Nation>) -> f32 {
+    let others: Vec<f32> = members
+        .iter()
+        .filter(|member| **member != nation)
+        .map(|member| diplo.relation(nation, *member))
+        .collect();
+    if others.is_empty() {
+        0.0
+    } else {
+        others.iter().sum::<f32>() / others.len() as f32
+    }
+}
+
+/// Expels `breaker` from treaty `bloc`; every remaining member remembers
+/// the breach and trusts it less. Returns the treaty's name, or `None` if
+/// `breaker` was not a member.
+pub fn break_treaty(
+    blocs: &mut WorldBlocs,
+    diplo: &mut DiplomaticRelations,
+    breaker: Nation,
+    bloc: u64,
+    tick: u64,
+) -> Option<String> {
+    let bloc = blocs.blocs.iter_mut().find(|b| b.id == bloc)?;
+    if !bloc.members.remove(&breaker) {
+        return None;
+    }
+    for member in &bloc.members {
+        diplo.remember(*member, breaker, MemoryKind::BrokeTreaty, tick);
+        diplo.adjust_trust(*member, breaker, -BREACH_TRUST);
+    }
+    Some(bloc.name.clone())
+}
+
+/// What members weigh, but never change, as they admit, expel and found.
+#[derive(SystemParam)]
+pub struct BlocInputs<'w> {
+    metrics: Res<'w, AllNationMetrics>,
+    wars: Res<'w, Wars>,
+    registry: Res<'w, NationRegistry>,
+    time: Res<'w, WorldTime>,
+    world_meta: Res<'w, WorldMetadata>,
+    rng_source: Res<'w, SimulationRng>,
+}
 
-/// Periodically adjusts blocs for cooperation/embargo effects.
+/// Runs bloc membership: expels members who turned on the bloc, dissolves
+/// blocs left without a quorum, admits applicants the members vote for and
+/// lets willing pairs found new blocs. Then rates each bloc's strength.
 pub fn bloc_system(
     mut blocs: ResMut<WorldBlocs>,
-    metrics: Res<AllNationMetrics>,
-    fatigue: Res<crate::simulation::WarFatigue>,
-    climate: Res<crate::simulation::ClimateState>,
-    time: Res<WorldTime>,
+    mut diplo: ResMut<DiplomaticRelations>,
+    mut event_log: ResMut<WorldEventLog>,
+    inputs: BlocInputs,
 ) {
-    let mut rng = SmallRng::seed_from_u64(time.tick.wrapping_mul(313));
-    let war_tension = (fatigue.intensity / 100.0).clamp(0.0, 1.0);
-    let climate_stress = (climate.climate_risk / 100.0).clamp(0.0, 1.0);
-    let coop_bias = 0.5 + (1.0 - war_tension) * 0.2 + climate_stress * 0.1;
-    let sanction_bias = 0.3 + war_tension * 0.4 + climate_stress * 0.1;
-
-    // Build desired blocs without holding the map borrow
-    let research_template = crate::simulation::Bloc {
-        kind: BlocKind::ResearchPact,
-        leader: None,
-        members: metrics
-            .0
-            .keys()
-            .cloned()
-            .filter(|_| rng.gen_bool(coop_bias as f64))
-            .collect(),
-        strength: 1.0,
-    };
-    let sanction_template = crate::simulation::Bloc {
-        kind: BlocKind::Sanction,
-        leader: None,
-        members: metrics
-            .0
-            .keys()
-            .cloned()
-            .filter(|_| rng.gen_bool(sanction_bias as f64))
-            .collect(),
-        strength: 1.0,
-    };
-
-    let research_bloc_ptr = {
-        let entry = blocs
-            .blocs
-            .entry(BlocKind::ResearchPact)
-            .or_insert(research_template);
-        entry as *mut _
-    };
-    let sanction_bloc_ptr = {
-        let entry = blocs
-            .blocs
-            .entry(BlocKind::Sanction)
-            .or_insert(sanction_template);
-        entry as *mut _
-    };
-    // SAFETY: We only use raw pointers to avoid simultaneous mutable borrow on HashMap entries,
-    // and dereference sequentially below.
-    let research_bloc: &mut crate::simulation::Bloc = unsafe { &mut *research_bloc_ptr };
-    let sanction_bloc: &mut crate::simulation::Bloc = unsafe { &mut *sanction_bloc_ptr };
-
-    // Recompute strength based on member science
-    for bloc in [&mut *research_bloc, &mut *sanction_bloc] {
+    let BlocInputs {
+        metrics,
+        wars,
+        registry,
+        time,
+        world_meta,
+        rng_source,
+    } = inputs;
+    let mut rng = rng_source.stream(RngStream::Blocs, time.tick);
+    let (epoch, season) = world_meta.epoch_for_tick(time.tick);
+    let alive = |nation: &Nation| metrics.0.get(nation).is_some_and(|m| !m.is_destroyed);
+    let mut changes: Vec<(String, BlocKind, Nation, BlocChange)> = Vec::new();
+
+    // 1. Expulsions: the fallen, members who attacked a fellow member, and
+    // members the rest no longer get along with.
+    for bloc in blocs.blocs.iter_mut() {
+        bloc.members.retain(alive);
+        let expelled: Vec<Nation> = bloc
+            .members
+            .iter()
+            .copied()
+            .filter(|member| {
+                let aggressor = wars.active.iter().any(|war| {
+                    war.attackers.contains(member)
+                        && war.defenders.iter().any(|d| bloc.members.contains(d))
+                });
+                let reconciled = bloc.kind == BlocKind::Sanction
+                    && !willing(&diplo, bloc.kind, bloc.target, *member, &bloc.members);
+                aggressor
+                    || reconciled
+                    || standing(&diplo, *member, &bloc.members) < EXPULSION_RELATION
+            })
+            .collect();
+        for nation in expelled {
+            bloc.members.remove(&nation);
+            changes.push((bloc.name.clone(), bloc.kind, nation, BlocChange::Expelled));
+        }
+    }
+
+    // 2. Dissolution once no quorum is left, or a sanctioned nation is gone.
+    blocs.blocs.retain(|bloc| {
+        let quorum = bloc.members.len() >= 2 && bloc.target.is_none_or(|t| alive(&t));
+        if !quorum {
+            let last = bloc
+                .members
+                .first()
+                .or(bloc.leader.as_ref())
+                .or(bloc.founders.first())
+                .copied()
+                .unwrap_or(Nation(0));
+            changes.push((bloc.name.clone(), bloc.kind, last, BlocChange::Dissolved));
+        }
+        quorum
+    });
+
+    // 3. Applications: a nation outside any bloc of a kind that still wants
+    // in is admitted if most members welcome it.
+    for (nation, id) in std::mem::take(&mut blocs.applications) {
+        if !alive(&nation) {
+            continue;
+        }
+        let Some(index) = blocs.blocs.iter().position(|bloc| bloc.id == id) else {
+            continue;
+        };
+        let kind = blocs.blocs[index].kind;
+        if blocs.bloc_of(nation, kind).is_some() {
+            continue;
+        }
+        let bloc = &mut blocs.blocs[index];
+        if bloc
+            .members
+            .iter()
+            .any(|member| wars.at_war(nation, *member))
+            || !willing(&diplo, kind, bloc.target, nation, &bloc.members)
+        {
+            continue;
+        }
+        let votes = bloc
+            .members
+            .iter()
+            .filter(|member| diplo.opinion(**member, nation) > 0.0)
+            .count();
+        if votes * 2 > bloc.members.len() {
+            bloc.members.insert(nation);
+            changes.push((bloc.name.clone(), kind, nation, BlocChange::Joined));
+        }
+    }
+
+    // 4. Foundings: two nations outside any bloc of a kind who are willing
+    // partners set one up together.
+    let nations: Vec<Nation> = metrics.0.keys().copied().filter(alive).collect();
+    for kind in [BlocKind::DefenseTreaty, BlocKind::ResearchPact] {
+        for i in 0..nations.len() {
+            for j in (i + 1)..nations.len() {
+                let (a, b) = (nations[i], nations[j]);
+                if blocs.bloc_of(a, kind).is_some()
+                    || blocs.bloc_of(b, kind).is_some()
+                    || wars.at_war(a, b)
+                    || !willing(&diplo, kind, None, a, &BTreeSet::from([b]))
+                    || !rng.gen_bool(FOUNDING_CHANCE)
+                {
+                    continue;
+                }
+                let name = format!("{} {}", registry.name(a), kind.label());
+                found(&mut blocs, kind, name.clone(), [a, b], None, time.tick);
+                changes.push((name, kind, a, BlocChange::Founded));
+            }
+        }
+    }
+    // A sanctions regime is founded by two nations hostile to the same target.
+    for &target in &nations {
+        if blocs.blocs.iter().any(|bloc| bloc.target == Some(target)) {
+            continue;
+        }
+        let hostile: Vec<Nation> = nations
+            .iter()
+            .copied()
+            .filter(|nation| {
+                blocs.bloc_of(*nation, BlocKind::Sanction).is_none()
+                    && willing(
+                        &diplo,
+                        BlocKind::Sanction,
+                        Some(target),
+                        *nation,
+                        &BTreeSet::new(),
+                    )
+            })
+            .collect();
+        if hostile.len() < 2 || !rng.gen_bool(FOUNDING_CHANCE) {
+            continue;
+        }
+        let name = format!("{} Embargo", registry.name(target));
+        found(
+            &mut blocs,
+            BlocKind::Sanction,
+            name.clone(),
+            [hostile[0], hostile[1]],
+            Some(target),
+            time.tick,
+        );
+        changes.push((name, BlocKind::Sanction, hostile[0], BlocChange::Founded));
+    }
+
+    // The target of a sanctions regime remembers every nation that signs on.
+    for (name, kind, nation, change) in &changes {
+        if *kind != BlocKind::Sanction
+            || !matches!(change, BlocChange::Founded | BlocChange::Joined)
+        {
+            continue;
+        }
+        let Some(bloc) = blocs.blocs.iter().find(|bloc| &bloc.name == name) else {
+            continue;
+        };
+        let signers: Vec<Nation> = match change {
+            BlocChange::Founded => bloc.founders.iter().copied().collect(),
+            _ => vec![*nation],
+        };
+        if let Some(target) = bloc.target {
+            for signer in signers {
+                diplo.remember(target, signer, MemoryKind::Sanctioned, time.tick);
+            }
+        }
+    }
+
+    for (bloc, kind, nation, change) in changes {
+        event_log.push(WorldEvent::bloc_changed(
+            time.tick, epoch, season, bloc, kind, nation, change,
+        ));
+    }
+
+    // 5. Strength from the members' science and economy; the leader is the
+    // member furthest ahead in science.
+    for bloc in blocs.blocs.iter_mut() {
         let mut total_science = 0.0;
         let mut total_econ = 0.0;
         for nation in bloc.members.iter() {
             if let Some(m) = metrics.0.get(nation) {
-                if !m.is_destroyed {
-                    total_science += m.science;
-                    total_econ += m.economy;
-                }
+                total_science += m.science;
+                total_econ += m.economy;
             }
         }
         bloc.strength = (total_science * 0.01 + total_econ * 0.005).min(10.0);
-
-        // Pick leader as highest science member
         bloc.leader = bloc
             .members
             .iter()
@@ -84,3 +292,26 @@ pub fn bloc_system(
             .map(|(n, _)| *n);
     }
 }
+
+fn found(
+    blocs: &mut WorldBlocs,
+    kind: BlocKind,
+    name: String,
+    founders: [Nation; 2],
+    target: Option<Nation>,
+    tick: u64,
+) {
+    let founders: BTreeSet<Nation> = founders.into_iter().collect();
+    blocs.blocs.push(Bloc {
+        id: blocs.next_id,
+        kind,
+        name,
+        members: founders.clone(),
+        founders,
+        leader: None,
+        target,
+        founded: tick,
+        strength: 0.0,
+    });
+    blocs.next_id += 1;
+}
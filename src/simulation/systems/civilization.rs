@@ -1,17 +1,18 @@
 use bevy_ecs::prelude::*;
-use rand::rngs::SmallRng;
-use rand::{Rng, SeedableRng};
 
-use crate::simulation::{AllNationCivState, AllNationMetrics, WorldTime};
+use crate::simulation::{AllNationCivState, AllNationMetrics, NationMetrics};
+
+/// Most cities a nation's territory can hold.
+pub fn max_cities(metrics: &NationMetrics) -> u32 {
+    (metrics.territory / 8.0).ceil() as u32 + 1
+}
 
 /// Civilization-style progression: cities drive production, growth, and happiness.
+/// New cities are founded by each nation's strategy.
 pub fn civilization_system(
     mut civ: ResMut<AllNationCivState>,
     mut metrics: ResMut<AllNationMetrics>,
-    time: Res<WorldTime>,
 ) {
-    let mut rng = SmallRng::seed_from_u64(time.tick.wrapping_mul(911));
-
     for (nation, civ_state) in civ.0.iter_mut() {
         if let Some(m) = metrics.0.get_mut(nation) {
             if m.is_destroyed {
@@ -47,18 +48,6 @@ pub fn civilization_system(
             civ_state.happiness = civ_state.happiness.clamp(0.0, 100.0);
             civ_state.stability = civ_state.stability.clamp(0.0, 100.0);
 
-            // City founding chance if there is space (territory proportional)
-            let max_cities = (m.territory / 8.0).ceil() as u32 + 1;
-            if civ_state.cities < max_cities {
-                let chance = 0.04 + m.culture.max(10.0) / 1000.0;
-                if rng.gen_bool(chance as f64) {
-                    civ_state.cities += 1;
-                    civ_state.production += 3.0;
-                    civ_state.happiness += 2.0;
-                    m.population = m.population.saturating_add(120_000);
-                }
-            }
-
             // Small war-weariness decay
             civ_state.happiness += 0.05;
             civ_state.happiness = civ_state.happiness.clamp(0.0, 100.0);
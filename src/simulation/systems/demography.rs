@@ -1,17 +1,10 @@
 use bevy_ecs::prelude::*;
 
-use crate::simulation::{AllNationMetrics, ClimateState, WorldTime};
-
-/// Age structure & productivity cycle updates.
-pub fn demography_system(
-    mut metrics: ResMut<AllNationMetrics>,
-    climate: Res<ClimateState>,
-    time: Res<WorldTime>,
-) {
-    let tick = time.tick as f32;
-    let climate_drag = (climate.climate_risk * 0.003).min(0.25);
-    let cycle = (tick.sin() + 1.0) * 0.5; // simple business cycle proxy 0..1
+use crate::simulation::AllNationMetrics;
 
+/// Age structure updates, and the output of the employed workforce at the
+/// productivity and unemployment the business cycle set.
+pub fn demography_system(mut metrics: ResMut<AllNationMetrics>) {
     for (_, m) in metrics.0.iter_mut() {
         if m.is_destroyed {
             continue;
@@ -35,10 +28,6 @@ pub fn demography_system(
 
         m.population = m.youth + m.adult + m.elder;
 
-        // Productivity shaped by cycle and climate
-        m.productivity = (1.0 + cycle * 0.2 - climate_drag).max(0.4);
-        m.unemployment = (6.0 + (1.0 - cycle) * 5.0 + climate_drag * 10.0).min(40.0);
-
         // Tie back into economy/science
         let effective_workers = m.adult as f32 * (1.0 - m.unemployment / 100.0);
         m.economy += (effective_workers / 1_000_000.0) * m.productivity * 0.8;
@@ -1,96 +1,340 @@
 use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemParam;
 
 use crate::simulation::{
-    AllNationMetrics, DiplomaticRelations, Nation, WorldEvent, WorldEventKind, WorldEventLog,
-    WorldTime,
+    AllNationMetrics, Alliance, AllianceEnd, AxialCoord, DiplomaticRelations, Hex, IdeologyMatrix,
+    MemoryKind, Nation, NationRegistry, Sanction, Wars, WorldEvent, WorldEventKind, WorldEventLog,
+    WorldTime, border_lengths,
 };
 
-/// Evolves diplomatic relations, alliances, and sanctions based on power balance.
+/// Ticks an alliance runs before the pair decide whether to renew it.
+const ALLIANCE_TERM: u64 = 200;
+/// Trust each side must hold of the other to sign an alliance.
+const ALLIANCE_TRUST: f32 = 30.0;
+/// Opinion of a proposer a nation needs to take up the alliance it offers.
+const PROPOSAL_OPINION: f32 = 35.0;
+/// Relations needed to renew an alliance whose term has run out.
+const RENEWAL_RELATION: f32 = 40.0;
+/// Relations below which an alliance breaks up before its term is out.
+const SOURED_RELATION: f32 = 20.0;
+/// Ideology gap past which allies drift apart.
+const IDEOLOGICAL_RIFT: f32 = 40.0;
+/// Ticks between rounds of sanction relief talks.
+const SANCTION_REVIEW: u64 = 40;
+/// Opinion at which an issuer lifts its sanctions unprompted.
+const RELIEF_RELATION: f32 = -20.0;
+/// Trade penalty that drives a sanctioned nation to offer concessions.
+const CONCESSION_PAIN: f32 = 10.0;
+/// Share of its economy a sanctioned nation pays to have them lifted.
+const CONCESSION_SHARE: f32 = 0.05;
+/// Standing the issuer grants a target that made concessions.
+const CONCESSION_GOODWILL: f32 = 30.0;
+/// Memories weaker than this are forgotten.
+const MEMORY_FLOOR: f32 = 1.0;
+/// Friction added by each hex edge two nations share.
+const FRICTION_PER_EDGE: f32 = 0.5;
+/// Most friction a border can cause, however long.
+const MAX_FRICTION: f32 = 25.0;
+/// Rivalry two nations at war settle toward.
+const WAR_RIVALRY: f32 = 80.0;
+/// Rivalry evenly matched neighbours settle toward.
+const BORDER_RIVALRY: f32 = 40.0;
+/// Share of the gap to its target rivalry closes each tick.
+const RIVALRY_DRIFT: f32 = 0.02;
+
+/// The wars, ideologies and borders that opinions drift with.
+#[derive(SystemParam)]
+pub struct DiplomacyInputs<'w, 's> {
+    registry: Res<'w, NationRegistry>,
+    time: Res<'w, WorldTime>,
+    wars: Res<'w, Wars>,
+    ideology: Res<'w, IdeologyMatrix>,
+    hexes: Query<'w, 's, (&'static AxialCoord, &'static Hex)>,
+}
+
+/// Evolves directed opinions from power balance, fading memories, rivalry
+/// and border friction and signs alliances, then breaks up alliances and
+/// lifts sanctions whose reasons have passed. Imposing sanctions is left to
+/// each nation's strategy.
 pub fn diplomacy_system(
     mut diplo: ResMut<DiplomaticRelations>,
-    metrics: Res<AllNationMetrics>,
-    time: Res<WorldTime>,
+    mut metrics: ResMut<AllNationMetrics>,
+    inputs: DiplomacyInputs,
     mut log: ResMut<WorldEventLog>,
 ) {
-    // Initialize trust/fear
-    for nation in metrics.0.keys() {
-        diplo.trust.entry(*nation).or_insert(40.0);
-        diplo.fear.entry(*nation).or_insert(35.0);
+    let DiplomacyInputs {
+        registry,
+        time,
+        wars,
+        ideology,
+        hexes,
+    } = inputs;
+    // Memories fade until they no longer matter.
+    for memory in diplo.memories.iter_mut() {
+        memory.weight *= memory.kind.retention();
     }
+    diplo
+        .memories
+        .retain(|memory| memory.weight.abs() >= MEMORY_FLOOR);
+
+    // Border friction from the hex edges each pair shares.
+    let edges = border_lengths(hexes.iter());
+    diplo.friction = edges
+        .iter()
+        .map(|(pair, count)| (*pair, (*count as f32 * FRICTION_PER_EDGE).min(MAX_FRICTION)))
+        .collect();
 
-    // Update pairwise relations
+    // Standing, trust and fear drift with the balance of power, and rivalry
+    // with wars and contested borders.
     let nations: Vec<Nation> = metrics.0.keys().copied().collect();
     for i in 0..nations.len() {
         for j in (i + 1)..nations.len() {
             let a = nations[i];
             let b = nations[j];
-            let key = ordered_pair(a, b);
-            let entry = diplo.relations.entry(key).or_insert(10.0);
+            diplo.standing.entry((a, b)).or_insert(10.0);
+            diplo.standing.entry((b, a)).or_insert(10.0);
             let (a_power, b_power) = (
                 metrics.0.get(&a).map(power_score).unwrap_or(0.0),
                 metrics.0.get(&b).map(power_score).unwrap_or(0.0),
             );
             let balance = (a_power - b_power).abs();
-            let parity_bonus = if balance < 8.0 { 2.0 } else { -1.5 };
-            *entry = (*entry + parity_bonus).clamp(-100.0, 100.0);
-            // trust/fear drift
-            *diplo.trust.entry(a).or_insert(40.0) += parity_bonus * 0.5;
-            *diplo.trust.entry(b).or_insert(40.0) += parity_bonus * 0.5;
-            *diplo.fear.entry(a).or_insert(35.0) += (b_power - a_power) * 0.02;
-            *diplo.fear.entry(b).or_insert(35.0) += (a_power - b_power) * 0.02;
+            let parity = balance < 8.0;
+            let parity_bonus = if parity { 2.0 } else { -1.5 };
+            diplo.adjust_standing(a, b, parity_bonus);
+            diplo.adjust_standing(b, a, parity_bonus);
+            diplo.adjust_trust(a, b, parity_bonus * 0.5);
+            diplo.adjust_trust(b, a, parity_bonus * 0.5);
+            diplo.adjust_fear(a, b, (b_power - a_power) * 0.02);
+            diplo.adjust_fear(b, a, (a_power - b_power) * 0.02);
+
+            let target = if wars.at_war(a, b) {
+                WAR_RIVALRY
+            } else if parity && edges.contains_key(&(a, b)) {
+                BORDER_RIVALRY
+            } else {
+                0.0
+            };
+            let rivalry = diplo.rivalry.entry((a, b)).or_insert(0.0);
+            *rivalry += (target - *rivalry) * RIVALRY_DRIFT;
         }
     }
 
-    // Spawn alliances when relations strong and fear moderate
-    for ((a, b), score) in diplo.relations.clone() {
-        if score > 55.0 && !diplo.alliances.contains(&(a, b)) {
-            diplo.alliances.push((a, b));
-            log.push(WorldEvent {
-                tick: time.tick,
-                epoch: "Diplomacy".to_string(),
-                season: "Alliance".to_string(),
-                kind: WorldEventKind::Social {
-                    convener: crate::simulation::EventActor {
-                        id: 0,
-                        name: format!("{}-{} Treaty", a.name(), b.name()),
-                        nation: a,
-                        faction: crate::simulation::Faction::Neutral,
-                        faction_label: "Treaty".to_string(),
-                        biome: crate::simulation::Biome::Plains,
-                        biome_label: "Plains".to_string(),
-                        behavior_hint: crate::simulation::BehaviorState::Idle,
-                        behavior_hint_label: "Treaty".to_string(),
-                    },
-                    gathering_theme: "Alliance signed".to_string(),
-                    cohesion_level: format!("score {:.0}", score),
-                },
-            });
+    // Spawn alliances when relations are strong and each side trusts the
+    // other, between nations not at war and close enough in ideology.
+    for i in 0..nations.len() {
+        for j in (i + 1)..nations.len() {
+            let (a, b) = (nations[i], nations[j]);
+            if diplo.relation(a, b) <= 55.0
+                || diplo.trust(a, b) < ALLIANCE_TRUST
+                || diplo.trust(b, a) < ALLIANCE_TRUST
+                || diplo.allied(a, b)
+                || wars.at_war(a, b)
+                || ideological_rift(&ideology, a, b)
+            {
+                continue;
+            }
+            sign_alliance(&mut diplo, &mut log, &registry, a, b, time.tick);
+        }
+    }
+
+    // Break up alliances whose reasons have gone, and renew the rest when
+    // their term runs out.
+    let destroyed: Vec<Nation> = metrics
+        .0
+        .iter()
+        .filter(|(_, m)| m.is_destroyed)
+        .map(|(nation, _)| *nation)
+        .collect();
+    let alive = |nation: &Nation| !destroyed.contains(nation);
+    let mut ended = Vec::new();
+    for alliance in std::mem::take(&mut diplo.alliances) {
+        let (a, b) = (alliance.a, alliance.b);
+        if !alive(&a) || !alive(&b) {
+            continue;
+        }
+        let relation = diplo.relation(a, b);
+        let end = if wars.active.iter().any(|war| {
+            war.side(a)
+                .zip(war.side(b))
+                .is_some_and(|(side_a, side_b)| side_a != side_b)
+        }) {
+            Some(AllianceEnd::Betrayal)
+        } else if relation < SOURED_RELATION {
+            Some(AllianceEnd::Soured)
+        } else if ideological_rift(&ideology, a, b) {
+            Some(AllianceEnd::IdeologicalRift)
+        } else if time.tick >= alliance.expires && relation < RENEWAL_RELATION {
+            Some(AllianceEnd::Expired)
+        } else {
+            None
+        };
+        match end {
+            Some(reason) => ended.push((a, b, reason)),
+            None if time.tick >= alliance.expires => diplo.alliances.push(Alliance {
+                expires: time.tick + ALLIANCE_TERM,
+                ..alliance
+            }),
+            None => diplo.alliances.push(alliance),
+        }
+    }
+    for (a, b, reason) in ended {
+        if reason == AllianceEnd::Betrayal {
+            diplo.remember(a, b, MemoryKind::Betrayed, time.tick);
+            diplo.remember(b, a, MemoryKind::Betrayed, time.tick);
+        }
+        log.push(WorldEvent::alliance_dissolved(
+            time.tick,
+            "Diplomacy",
+            "Alliance",
+            a,
+            b,
+            reason,
+        ));
+    }
+
+    // Relief talks: sanctions are lifted once relations recover, or when a
+    // target hurting from them buys relief with a concession.
+    let mut lifted = Vec::new();
+    for mut sanction in std::mem::take(&mut diplo.sanctions) {
+        let (issuer, target) = (sanction.issuer, sanction.target);
+        if !alive(&issuer) || !alive(&target) {
+            continue;
+        }
+        if time.tick < sanction.review {
+            diplo.sanctions.push(sanction);
+            continue;
         }
-        if score < -45.0 && !diplo.sanctions.contains(&(a, b)) {
-            diplo.sanctions.push((a, b));
-            log.push(WorldEvent {
-                tick: time.tick,
-                epoch: "Diplomacy".to_string(),
-                season: "Sanction".to_string(),
-                kind: WorldEventKind::MacroShock {
-                    stressor: format!("{} sanctions {}", a.name(), b.name()),
-                    catalyst: "Trade blockade".to_string(),
-                    projected_impact: "Economic contraction".to_string(),
-                    casualties: None,
-                },
+        if diplo.opinion(issuer, target) >= RELIEF_RELATION {
+            lifted.push((issuer, target, 0.0));
+            continue;
+        }
+        let pain = metrics.0.get(&target).map_or(0.0, |m| m.trade_penalty);
+        if pain >= CONCESSION_PAIN {
+            let concession = metrics.0.get_mut(&target).map_or(0.0, |m| {
+                let amount = m.economy.max(0.0) * CONCESSION_SHARE;
+                m.economy -= amount;
+                amount
             });
+            if let Some(m) = metrics.0.get_mut(&issuer) {
+                m.economy += concession;
+            }
+            diplo.adjust_standing(issuer, target, CONCESSION_GOODWILL);
+            lifted.push((issuer, target, concession));
+        } else {
+            sanction.review = time.tick + SANCTION_REVIEW;
+            diplo.sanctions.push(sanction);
         }
     }
+    for (issuer, target, concession) in lifted {
+        log.push(WorldEvent::sanctions_lifted(
+            time.tick,
+            "Diplomacy",
+            "Sanction",
+            issuer,
+            target,
+            concession,
+        ));
+    }
 }
 
-fn power_score(m: &crate::simulation::NationMetrics) -> f32 {
-    (m.military * 0.4 + m.economy * 0.3 + m.science * 0.2 + m.diplomacy * 0.1) / 10.0
+/// Whether `partner` takes up an alliance `proposer` offers it: it must
+/// think well enough of and trust the proposer, be at peace with it and not
+/// stand too far apart in ideology.
+pub fn accepts_alliance(
+    diplo: &DiplomaticRelations,
+    wars: &Wars,
+    ideology: &IdeologyMatrix,
+    proposer: Nation,
+    partner: Nation,
+) -> bool {
+    proposer != partner
+        && diplo.opinion(partner, proposer) > PROPOSAL_OPINION
+        && diplo.trust(partner, proposer) >= ALLIANCE_TRUST
+        && !diplo.allied(proposer, partner)
+        && !wars.at_war(proposer, partner)
+        && !ideological_rift(ideology, proposer, partner)
+}
+
+/// Signs an alliance between `a` and `b` for the standard term.
+pub fn sign_alliance(
+    diplo: &mut DiplomaticRelations,
+    log: &mut WorldEventLog,
+    registry: &NationRegistry,
+    a: Nation,
+    b: Nation,
+    tick: u64,
+) {
+    let score = diplo.relation(a, b);
+    diplo.alliances.push(Alliance {
+        a,
+        b,
+        signed: tick,
+        expires: tick + ALLIANCE_TERM,
+    });
+    log.push(WorldEvent {
+        tick,
+        epoch: "Diplomacy".to_string(),
+        season: "Alliance".to_string(),
+        kind: WorldEventKind::Social {
+            convener: crate::simulation::EventActor {
+                id: 0,
+                name: format!("{}-{} Treaty", registry.name(a), registry.name(b)),
+                nation: a,
+                faction: crate::simulation::Faction::Neutral,
+                faction_label: "Treaty".to_string(),
+                biome: crate::simulation::Biome::Plains,
+                biome_label: "Plains".to_string(),
+                behavior_hint: crate::simulation::BehaviorState::Idle,
+                behavior_hint_label: "Treaty".to_string(),
+            },
+            gathering_theme: "Alliance signed".to_string(),
+            cohesion_level: format!("score {:.0}", score),
+        },
+    });
 }
 
-fn ordered_pair(a: Nation, b: Nation) -> (Nation, Nation) {
-    if (a as u32) < (b as u32) {
-        (a, b)
-    } else {
-        (b, a)
+/// Puts `target` under sanctions from `issuer`; the target remembers it.
+pub fn impose_sanction(
+    diplo: &mut DiplomaticRelations,
+    log: &mut WorldEventLog,
+    registry: &NationRegistry,
+    issuer: Nation,
+    target: Nation,
+    tick: u64,
+) {
+    if issuer == target || diplo.sanctioning(issuer, target) {
+        return;
     }
+    diplo.sanctions.push(Sanction {
+        issuer,
+        target,
+        imposed: tick,
+        review: tick + SANCTION_REVIEW,
+    });
+    diplo.remember(target, issuer, MemoryKind::Sanctioned, tick);
+    log.push(WorldEvent {
+        tick,
+        epoch: "Diplomacy".to_string(),
+        season: "Sanction".to_string(),
+        kind: WorldEventKind::MacroShock {
+            stressor: format!(
+                "{} sanctions {}",
+                registry.name(issuer),
+                registry.name(target)
+            ),
+            catalyst: "Trade blockade".to_string(),
+            projected_impact: "Economic contraction".to_string(),
+            casualties: None,
+        },
+    });
+}
+
+/// Whether `a` and `b` lean too far apart ideologically to stay allies.
+fn ideological_rift(ideology: &IdeologyMatrix, a: Nation, b: Nation) -> bool {
+    let leaning = |nation| ideology.leaning.get(&nation).copied().unwrap_or(50.0);
+    (leaning(a) - leaning(b)).abs() > IDEOLOGICAL_RIFT
+}
+
+fn power_score(m: &crate::simulation::NationMetrics) -> f32 {
+    (m.military * 0.4 + m.economy * 0.3 + m.science * 0.2 + m.diplomacy * 0.1) / 10.0
 }
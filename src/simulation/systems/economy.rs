@@ -1,14 +1,21 @@
-//! Trade and economy placeholder system.
+//! Nation income and decay, and the NPC gathering and trading that stock
+//! the goods markets. Upkeep and investment are paid out of each nation's
+//! treasury by the fiscal system.
 
 use bevy_ecs::prelude::*;
-use rand::rngs::SmallRng;
-use rand::{Rng, SeedableRng};
+use bevy_ecs::system::SystemParam;
+use rand::Rng;
 
 use crate::simulation::{
-    AllNationMetrics, Behavior, BehaviorState, BlocKind, Identity, Inventory, Position, WorldBlocs,
-    WorldMetadata, WorldTime,
+    AllNationMetrics, Behavior, BehaviorState, BlocKind, Identity, Inventory, ItemKind, Markets,
+    Position, RngStream, SimulationRng, SupplyState, WorldBlocs, WorldMetadata, WorldTime, Yields,
 };
 
+/// Most units of one good a trader sells or buys in a tick.
+const TRADE_LOT: u32 = 5;
+/// Units of each resource a million people want per tick.
+const RESOURCE_DEMAND: f32 = 0.05;
+
 fn season_trade_modifier(season: &str) -> f32 {
     match season {
         "Flower Bloom" => 1.1,
@@ -39,80 +46,84 @@ fn upkeep_penalty(base: f32, upkeep: f32) -> f32 {
     base * upkeep.max(0.5)
 }
 
+/// Market worth of a bundle of supply; scarcer channels are worth more.
+fn supply_worth(yields: Yields) -> f32 {
+    yields.food + yields.energy * 1.5 + yields.rare * 3.0
+}
+
+/// Units of `item` a nation wants per tick: resources by head, equipment by
+/// army size, artifacts by culture and faith.
+fn wants(item: &ItemKind, population: u64, military: f32, culture: f32, religion: f32) -> f32 {
+    match item {
+        ItemKind::Resource(_) => population as f32 / 1_000_000.0 * RESOURCE_DEMAND,
+        ItemKind::Equipment(_) => military * 0.01,
+        ItemKind::Artifact(_) => (culture + religion) * 0.002,
+    }
+}
+
+/// The blocs, supply and calendar that shape each tick's trade.
+#[derive(SystemParam)]
+pub struct EconomyInputs<'w> {
+    blocs: Res<'w, WorldBlocs>,
+    supply: Res<'w, SupplyState>,
+    world_meta: Res<'w, WorldMetadata>,
+    time: Res<'w, WorldTime>,
+    rng_source: Res<'w, SimulationRng>,
+}
+
 pub fn economy_system(
     mut query: Query<(&Identity, &Position, &Behavior, &mut Inventory)>,
     mut all_metrics: ResMut<AllNationMetrics>,
-    blocs: Res<WorldBlocs>,
-    world_meta: Res<WorldMetadata>,
-    time: Res<WorldTime>,
+    mut markets: ResMut<Markets>,
+    inputs: EconomyInputs,
 ) {
+    let EconomyInputs {
+        blocs,
+        supply,
+        world_meta,
+        time,
+        rng_source,
+    } = inputs;
     let (segment, season) = world_meta.epoch_for_tick(time.tick);
 
-    // First, handle nation-level economic updates (upkeep, investment, growth, decay)
+    // First, handle nation-level economic updates (income, decay); upkeep and
+    // investment are left to each nation's budget
     for (nation_key, metrics) in all_metrics.0.iter_mut() {
-        // 1. Military Upkeep
-        let military_upkeep = metrics.military * 0.05;
-        metrics.economy -= military_upkeep;
-
-        // 2. Other Metrics Upkeep (they cost a little bit of economy)
-        let science_upkeep = metrics.science * 0.02;
-        let culture_upkeep = metrics.culture * 0.01;
-        let diplomacy_upkeep = metrics.diplomacy * 0.02;
-        metrics.economy -= science_upkeep + culture_upkeep + diplomacy_upkeep;
-
-        // If economy is negative after upkeep, it hurts the military
-        if metrics.economy < 0.0 {
-            metrics.military += metrics.economy * 0.2; // Negative economy reduces military
-            metrics.economy = 0.0;
-        }
-
-        // 3. Investment and Growth based on Economy
-        if metrics.economy > 70.0 {
-            // High Economy Boosts
-            metrics.military += 0.5;
-            metrics.science += 0.4;
-            metrics.diplomacy += 0.3;
-            metrics.economy -= 5.0; // Investment cost
-        } else if metrics.economy > 40.0 {
-            // Medium Economy
-            metrics.military += 0.1;
-            metrics.science += 0.15;
-            metrics.culture += 0.2;
-            metrics.religion += 0.1;
-            metrics.diplomacy += 0.1;
-            metrics.economy -= 1.0;
+        // 0. Income from owned land: about one point per tick for a nation that
+        // produces what its population needs, more for a surplus.
+        if let Some(demand) = supply.demand(metrics.population) {
+            let cover = supply_worth(metrics.output) / supply_worth(demand).max(f32::EPSILON);
+            metrics.economy += cover.min(3.0);
         }
 
-        // 4. General Decay
+        // 1. General Decay
         metrics.science *= 0.999;
         metrics.culture *= 0.998;
         metrics.diplomacy *= 0.999;
         metrics.religion *= 0.9995;
 
         // Bloc influences
-        if let Some(research) = blocs.blocs.get(&BlocKind::ResearchPact) {
-            if research.members.contains(nation_key) {
-                metrics.science += research.strength * 0.3;
-                metrics.diplomacy += research.strength * 0.15;
-            }
-        }
-        if let Some(sanction) = blocs.blocs.get(&BlocKind::Sanction) {
-            if sanction.members.contains(nation_key) {
-                metrics.economy -= sanction.strength * 0.6;
-                metrics.science -= sanction.strength * 0.25;
-            }
+        if let Some(research) = blocs.bloc_of(*nation_key, BlocKind::ResearchPact) {
+            metrics.science += research.strength * 0.3;
+            metrics.diplomacy += research.strength * 0.15;
         }
+        let embargo = blocs.embargo_on(*nation_key);
+        metrics.economy -= embargo * 0.6;
+        metrics.science -= embargo * 0.25;
     }
 
-    // Second, handle individual NPC actions contributing to economy
+    // Second, NPCs gather goods and trade them on their nation's market
     for (identity, position, behavior, mut inventory) in &mut query {
         let nation = identity.nation;
-        let metrics = all_metrics.0.get_mut(&nation).unwrap();
+        let (Some(metrics), Some(market)) =
+            (all_metrics.0.get_mut(&nation), markets.0.get_mut(&nation))
+        else {
+            continue;
+        };
 
         let biome = position.biome;
         let faction = identity.faction;
 
-        let base_trade_yield = 6.0;
         let base_gather_value = 3.0;
         let trade_multiplier = world_meta.biome_trade_opportunity(biome)
             * world_meta.faction_trade_yield(faction)
@@ -125,34 +136,69 @@ pub fn economy_system(
 
         let upkeep = world_meta.faction_upkeep_burden(faction);
 
-        let mut rng = SmallRng::seed_from_u64(
-            time.tick
-                .wrapping_mul(131)
-                .wrapping_add(identity.id * 7)
-                .wrapping_mul(59),
-        );
+        let mut rng = rng_source.keyed_stream(RngStream::Economy, time.tick, identity.id);
 
         if matches!(behavior.state, BehaviorState::Trade) {
-            let volatility: f32 = rng.gen_range(-2.0..2.0) * risk_factor;
-            let trade_gain =
-                base_trade_yield * trade_multiplier - upkeep_penalty(0.75, upkeep) + volatility;
-            inventory.currency =
-                (inventory.currency + trade_gain.max(-inventory.currency)).max(0.0);
-
-            // NPC actions now contribute less directly, but still add to the economy
-            metrics.economy += trade_gain * 0.05;
+            // Sell the held good fetching most against its base price...
+            let lot = inventory
+                .items
+                .iter()
+                .map(|stack| stack.item.clone())
+                .max_by(|a, b| {
+                    let pressure = |item: &ItemKind| {
+                        market.price(item.name()).unwrap_or(item.base_price()) / item.base_price()
+                    };
+                    pressure(a).total_cmp(&pressure(b))
+                });
+            if let Some(item) = &lot {
+                let sold = inventory.take(item.name(), TRADE_LOT);
+                let volatility: f32 = 1.0 + rng.gen_range(-0.1..0.1) * risk_factor;
+                let proceeds = market.sell(item, sold) * trade_multiplier * volatility
+                    - upkeep_penalty(0.75, upkeep);
+                inventory.currency = (inventory.currency + proceeds).max(0.0);
+
+                // NPC actions now contribute less directly, but still add to the economy
+                metrics.economy += proceeds * 0.015;
+            }
+
+            // ...and restock with the cheapest other good on offer.
+            let restock = market
+                .cheapest(lot.as_ref().map(ItemKind::name))
+                .map(|good| good.item.name().to_string());
+            if let Some((item, bought, cost)) =
+                restock.and_then(|name| market.buy(&name, TRADE_LOT, inventory.currency * 0.25))
+            {
+                inventory.currency -= cost;
+                inventory.add(item, bought);
+            }
         }
 
         if matches!(behavior.state, BehaviorState::Gather) {
-            let gather_gain = base_gather_value * resource_multiplier
-                - upkeep_penalty(0.35, upkeep)
-                + rng.gen_range(0.0..2.0);
-            inventory.currency += gather_gain.max(0.0);
-
-            metrics.economy += gather_gain * 0.03;
+            let found = world_meta
+                .biomes
+                .get(&biome)
+                .map(|meta| &meta.resource_profile)
+                .filter(|profile| !profile.is_empty())
+                .map(|profile| profile[rng.gen_range(0..profile.len())].clone());
+            let gathered = (base_gather_value * resource_multiplier - upkeep_penalty(0.35, upkeep)
+                + rng.gen_range(0.0..2.0))
+            .max(0.0)
+            .round() as u32;
+            if let Some(name) = found {
+                inventory.add(ItemKind::Resource(name), gathered);
+                metrics.economy += gathered as f32 * 0.03;
+            }
         }
     }
 
+    // Third, each nation consumes its tick of goods and prices settle
+    for (nation, market) in markets.0.iter_mut() {
+        let Some(m) = all_metrics.0.get(nation).filter(|m| !m.is_destroyed) else {
+            continue;
+        };
+        market.clear(|item| wants(item, m.population, m.military, m.culture, m.religion));
+    }
+
     // Finally, clamp all metrics to a 0-100 range
     for metrics in all_metrics.0.values_mut() {
         metrics.economy = metrics.economy.clamp(0.0, 100.0);
@@ -77,8 +77,7 @@ pub fn climate_impact_system(
         // Productivity penalty from risk
         let penalty = (risk * 0.08).min(25.0);
         m.economy = (m.economy - penalty).max(0.0);
-        // Coastal loss reduces territory and economy softly.
-        m.territory = (m.territory * (0.98 - sea * 0.25)).max(5.0);
+        // Coastal loss weighs on the economy; the land itself drowns in flood_system.
         m.economy *= (0.99 - sea * 0.15).max(0.6);
         m.military *= (0.995 - land_loss_factor * 0.15).max(0.5);
         // Habitat and food stress
@@ -98,7 +97,6 @@ pub fn climate_impact_system(
         // Recovery when seas stabilize and ice retreats
         if habitability > 0.8 && risk < 25.0 {
             m.economy = (m.economy + 0.8).min(220.0);
-            m.territory = (m.territory + 0.2).min(120.0);
         }
 
         if let Some(civ) = civ_state.0.get_mut(nation) {
@@ -1,12 +1,13 @@
 //! Generates structured world events tied to metadata influences.
 
 use bevy_ecs::prelude::*;
-use rand::rngs::SmallRng;
-use rand::{Rng, SeedableRng};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::simulation::{
-    AllNationMetrics, Attributes, Behavior, BehaviorState, EventActor, Inventory, Position,
-    WorldEvent, WorldEventLog, WorldMetadata, WorldTime, behavior_label, faction_label,
+    AllNationMetrics, Attributes, Behavior, BehaviorState, EventActor, Inventory, Markets,
+    Position, RngStream, SimulationRng, WorldEvent, WorldEventLog, WorldMetadata, WorldTime,
+    behavior_label, faction_label,
 };
 
 pub fn event_generation_system(
@@ -14,6 +15,8 @@ pub fn event_generation_system(
     world_meta: Res<WorldMetadata>,
     mut event_log: ResMut<WorldEventLog>,
     mut all_metrics: ResMut<AllNationMetrics>,
+    markets: Res<Markets>,
+    rng_source: Res<SimulationRng>,
     query: Query<(
         &crate::simulation::Identity,
         &Behavior,
@@ -24,7 +27,7 @@ pub fn event_generation_system(
 ) {
     let tick = time.tick;
     let (epoch, season) = world_meta.epoch_for_tick(tick);
-    let mut rng = SmallRng::seed_from_u64(tick.wrapping_mul(421) + 17);
+    let mut rng = rng_source.stream(RngStream::Events, tick);
 
     // Trade event sampling
     let mut trade_choice: Option<(crate::simulation::Identity, Position, f32, BehaviorState)> =
@@ -61,17 +64,19 @@ pub fn event_generation_system(
             })
             .unwrap_or_else(|| "General Goods".to_string());
 
-        let pressure = world_meta
-            .faction_profile(identity.faction)
-            .and_then(|f| {
-                if f.influence_vectors.is_empty() {
-                    None
-                } else {
-                    Some(
-                        f.influence_vectors[rng.gen_range(0..f.influence_vectors.len())]
-                            .to_string(),
-                    )
-                }
+        // Quote the traded good on the trader's home market, or the good
+        // whose price is furthest off its base if the market does not deal in it.
+        let pressure = markets
+            .0
+            .get(&identity.nation)
+            .and_then(|market| market.goods.get(&focus).or_else(|| market.most_pressed()))
+            .map(|good| {
+                format!(
+                    "{} at {:.1} ({:+.0}% vs base)",
+                    good.item.name(),
+                    good.price,
+                    good.pressure() * 100.0
+                )
             })
             .unwrap_or_else(|| "Local Demand Index".to_string());
 
@@ -90,7 +95,7 @@ pub fn event_generation_system(
         };
 
         let market_label = biome_profile
-            .map(|meta| meta.label)
+            .map(|meta| meta.label.as_str())
             .unwrap_or("Unknown Market");
         let trade_summary = format!(
             "{} brokers {} flow at {} (liquidity {:.1})",
@@ -178,44 +183,6 @@ pub fn event_generation_system(
         ));
     }
 
-    // Macro shock event (pulse each tick)
-    let stressors = &world_meta.economy.stressors;
-    let catalysts = &world_meta.economy.catalysts;
-    let circulation = &world_meta.economy.circulation_cycle;
-
-    if !(stressors.is_empty() || catalysts.is_empty() || circulation.is_empty()) {
-        let stressor = stressors[(tick as usize) % stressors.len()].to_string();
-        let catalyst =
-            catalysts[((tick as usize) + circulation.len()) % catalysts.len()].to_string();
-        let circulation_stage =
-            circulation[(tick as usize + catalysts.len()) % circulation.len()].to_string();
-
-        let casualties = if stressor.contains("plague") || stressor.contains("disease") {
-            // Plagues now hit much harder — large, sporadic die-off events.
-            Some(rng.gen_range(50_000..750_000))
-        } else {
-            None
-        };
-
-        if let Some(total) = casualties {
-            let per = total / (all_metrics.0.len() as u64).max(1);
-            for metrics in all_metrics.0.values_mut() {
-                if !metrics.is_destroyed {
-                    metrics.population = metrics.population.saturating_sub(per);
-                }
-            }
-        }
-
-        let impact = format!(
-            "{} is pressuring the world | Catalyst: {} | Phase {}",
-            stressor, catalyst, circulation_stage
-        );
-
-        event_log.push(WorldEvent::macro_shock(
-            tick, epoch, season, stressor, catalyst, impact, casualties,
-        ));
-    }
-
     // Catastrophic pulse: rare megaplague / cataclysm that slashes populations.
     if rng.gen_bool(0.05) {
         let severity = rng.gen_range(0.12..0.35); // 12–35% population loss
@@ -249,46 +216,90 @@ pub fn event_generation_system(
 
     // Omega-level disaster: meteor strike, gamma-ray burst, or supervolcano chain.
     if rng.gen_bool(0.01) {
-        let roll = rng.gen_range(0..3);
-        let (stressor, catalyst, severity) = match roll {
-            0 => (
-                "Planet-killer meteor strike",
-                "Impact winter + ejecta plume",
-                rng.gen_range(0.90..0.999), // up to 99.9% loss
-            ),
-            1 => (
-                "Gamma-ray burst exposure",
-                "Atmospheric stripping / radiation storm",
-                0.999, // force ~99.9% loss
-            ),
-            _ => (
-                "Supervolcano cascade",
-                "Ash cloud + famine + climate dive",
-                rng.gen_range(0.70..0.92), // 70–92% loss
-            ),
-        };
-
-        let mut total_casualties = 0u64;
-        for metrics in all_metrics.0.values_mut() {
-            if metrics.is_destroyed {
-                continue;
-            }
-            let loss = ((metrics.population as f32) * severity) as u64;
-            total_casualties = total_casualties.saturating_add(loss);
-            metrics.population = metrics.population.saturating_sub(loss).max(1_000);
-        }
-
-        event_log.push(WorldEvent::macro_shock(
+        let catastrophe = Catastrophe::ALL[rng.gen_range(0..Catastrophe::ALL.len())];
+        event_log.push(strike_catastrophe(
+            catastrophe,
+            catastrophe.severity(&mut rng),
+            &mut all_metrics,
             tick,
             epoch,
             season,
-            stressor.to_string(),
-            catalyst.to_string(),
-            format!(
-                "Omega event — population crash {:.1}% | infrastructure collapse",
-                severity * 100.0
-            ),
-            Some(total_casualties),
         ));
     }
 }
+
+/// The omega-level disasters: rare, world-wide and near-extinction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Catastrophe {
+    Meteor,
+    GammaRayBurst,
+    Supervolcano,
+}
+
+impl Catastrophe {
+    pub const ALL: [Catastrophe; 3] = [
+        Catastrophe::Meteor,
+        Catastrophe::GammaRayBurst,
+        Catastrophe::Supervolcano,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Catastrophe::Meteor => "Planet-killer meteor strike",
+            Catastrophe::GammaRayBurst => "Gamma-ray burst exposure",
+            Catastrophe::Supervolcano => "Supervolcano cascade",
+        }
+    }
+
+    fn catalyst(self) -> &'static str {
+        match self {
+            Catastrophe::Meteor => "Impact winter + ejecta plume",
+            Catastrophe::GammaRayBurst => "Atmospheric stripping / radiation storm",
+            Catastrophe::Supervolcano => "Ash cloud + famine + climate dive",
+        }
+    }
+
+    /// Share of every nation's population it kills.
+    pub fn severity(self, rng: &mut impl Rng) -> f32 {
+        match self {
+            Catastrophe::Meteor => rng.gen_range(0.90..0.999), // up to 99.9% loss
+            Catastrophe::GammaRayBurst => 0.999,               // force ~99.9% loss
+            Catastrophe::Supervolcano => rng.gen_range(0.70..0.92), // 70–92% loss
+        }
+    }
+}
+
+/// Kills `severity` of every living nation's population and returns the
+/// event recording it.
+pub fn strike_catastrophe(
+    catastrophe: Catastrophe,
+    severity: f32,
+    all_metrics: &mut AllNationMetrics,
+    tick: u64,
+    epoch: &str,
+    season: &str,
+) -> WorldEvent {
+    let mut total_casualties = 0u64;
+    for metrics in all_metrics.0.values_mut() {
+        if metrics.is_destroyed {
+            continue;
+        }
+        let loss = ((metrics.population as f32) * severity) as u64;
+        total_casualties = total_casualties.saturating_add(loss);
+        metrics.population = metrics.population.saturating_sub(loss).max(1_000);
+    }
+
+    WorldEvent::macro_shock(
+        tick,
+        epoch,
+        season,
+        catastrophe.label().to_string(),
+        catastrophe.catalyst().to_string(),
+        format!(
+            "Omega event — population crash {:.1}% | infrastructure collapse",
+            severity * 100.0
+        ),
+        Some(total_casualties),
+    )
+}
@@ -0,0 +1,237 @@
+//! Public finance: every nation taxes its employed workers, pays the
+//! interest on its debt and spends by its budget, borrowing the difference.
+//! Each line's spending has to cover the upkeep of what it already holds
+//! before it builds anything new. A state whose interest swallows too much of
+//! its taxes defaults.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemParam;
+
+use crate::simulation::{
+    AllNationCivState, AllNationMetrics, BudgetLine, DEFAULT_TAX, DiplomaticRelations, MAX_TAX,
+    NationStrategies, PlayerControl, StrategicAction, Treasuries, Treasury, Wars, WorldEvent,
+    WorldEventLog, WorldMetadata, WorldTime,
+};
+
+/// Economy a million employed workers yield per tick at a tax rate of 1.
+const TAX_YIELD: f32 = 5.0;
+/// Economy it takes to raise a metric by one point.
+const POINT_COST: f32 = 4.0;
+/// Share of each metric its line must spend every tick just to hold it.
+const MILITARY_UPKEEP: f32 = 0.05;
+const SCIENCE_UPKEEP: f32 = 0.02;
+const CULTURE_UPKEEP: f32 = 0.01;
+const DIPLOMACY_UPKEEP: f32 = 0.02;
+/// Happiness one point of welfare spending buys.
+const WELFARE_HAPPINESS: f32 = 0.2;
+/// Happiness lost per tick for each unit of tax rate above the default.
+const TAX_DISCONTENT: f32 = 0.5;
+/// Share of its assessed taxes a state's interest may reach before it
+/// defaults.
+const DEFAULT_SHARE: f32 = 0.5;
+/// Share of its debt a defaulting state writes off.
+const HAIRCUT: f32 = 0.5;
+/// Ticks lenders refuse credit after a default.
+const SHUT_OUT_TICKS: u64 = 120;
+/// Share of its economy a default wipes out.
+const DEFAULT_SHOCK: f32 = 0.3;
+/// Happiness a default costs, and the trust every other nation loses in
+/// the defaulter.
+const DEFAULT_UNREST: f32 = 15.0;
+const DEFAULT_DISTRUST: f32 = 10.0;
+/// Budget points a nation's leaders move to the line they choose to invest
+/// in, and to welfare when the people are restless.
+const STRATEGY_SHIFT: i8 = 2;
+/// Happiness below which leaders move money into welfare.
+const RESTLESS: f32 = 35.0;
+/// Share of the budget, in percent, past which leaders stop moving money
+/// into any one line.
+const FOCUS_CAP: u8 = 50;
+/// Extra spending, as a share of revenue, a state runs at war.
+const WAR_SPENDING: f32 = 0.3;
+/// Debt load, in ticks of full taxation, past which a state reins in
+/// spending and raises taxes.
+const COMFORT_LOAD: f32 = 20.0;
+/// Spending a state gives up once past its comfortable load.
+const RESTRAINT: f32 = 0.2;
+/// How far leaders move the tax rate and the spending ratio in a tick.
+const TAX_STEP: f32 = 0.005;
+const SPENDING_STEP: f32 = 0.02;
+
+/// How a nation's leaders steer its treasury: the budget follows the
+/// investment their strategy chose, up to a cap on any one line, spending
+/// rises at war and, once the debt grows uncomfortable, taxes rise and
+/// spending falls.
+fn steer(treasury: &mut Treasury, action: Option<StrategicAction>, at_war: bool, happiness: f32) {
+    let line = match action {
+        Some(StrategicAction::InvestMilitary) => Some(BudgetLine::Military),
+        Some(StrategicAction::InvestScience) => Some(BudgetLine::Science),
+        Some(StrategicAction::InvestCulture) => Some(BudgetLine::Culture),
+        _ => None,
+    };
+    let restless = (happiness < RESTLESS).then_some(BudgetLine::Welfare);
+    for line in [line, restless].into_iter().flatten() {
+        // Never more than the cap, so the cast cannot wrap.
+        let room = FOCUS_CAP.saturating_sub(treasury.budget.share(line)) as i8;
+        treasury.budget.shift(line, STRATEGY_SHIFT.min(room));
+    }
+
+    let load = treasury.debt_load();
+    let spending = 1.0 + if at_war { WAR_SPENDING } else { 0.0 }
+        - if load > COMFORT_LOAD { RESTRAINT } else { 0.0 };
+    treasury
+        .shift_spending((spending - treasury.spending_ratio).clamp(-SPENDING_STEP, SPENDING_STEP));
+    let tax = (DEFAULT_TAX + load / COMFORT_LOAD * 0.1).min(MAX_TAX);
+    treasury.shift_tax((tax - treasury.tax_rate).clamp(-TAX_STEP, TAX_STEP));
+}
+
+/// What leaders read, but never change, as they steer and spend their
+/// budgets.
+#[derive(SystemParam)]
+pub struct FiscalInputs<'w> {
+    strategies: Res<'w, NationStrategies>,
+    player: Res<'w, PlayerControl>,
+    wars: Res<'w, Wars>,
+    world_meta: Res<'w, WorldMetadata>,
+    time: Res<'w, WorldTime>,
+}
+
+/// Runs one tick of every living nation's public finances: leaders not
+/// under player command steer their budgets, taxes are collected from the
+/// economy, interest is paid, the budget is spent line by line and any
+/// deficit is added to the debt. Surpluses pay the debt down and, once it
+/// is gone, go back into the economy.
+pub fn fiscal_system(
+    mut treasuries: ResMut<Treasuries>,
+    mut all_metrics: ResMut<AllNationMetrics>,
+    mut civ: ResMut<AllNationCivState>,
+    mut diplo: ResMut<DiplomaticRelations>,
+    mut event_log: ResMut<WorldEventLog>,
+    inputs: FiscalInputs,
+) {
+    let FiscalInputs {
+        strategies,
+        player,
+        wars,
+        world_meta,
+        time,
+    } = inputs;
+    let (epoch, season) = world_meta.epoch_for_tick(time.tick);
+    let nations: Vec<_> = all_metrics.0.keys().copied().collect();
+
+    for &nation in &nations {
+        let Some(m) = all_metrics.0.get_mut(&nation).filter(|m| !m.is_destroyed) else {
+            continue;
+        };
+        let treasury = treasuries.0.entry(nation).or_default();
+        let mut happiness = civ.0.get(&nation).map_or(50.0, |c| c.happiness);
+
+        if player.nation != Some(nation) {
+            let at_war = wars.active.iter().any(|war| war.side(nation).is_some());
+            let action = strategies.0.get(&nation).map(|s| s.last_action);
+            steer(treasury, action, at_war, happiness);
+        }
+
+        // 1. Taxes, out of whatever economy there is to tax.
+        let workers = m.adult as f32 * (1.0 - m.unemployment / 100.0) / 1_000_000.0;
+        let base = workers * m.productivity * TAX_YIELD;
+        let assessed = base * treasury.tax_rate;
+        let revenue = assessed.min(m.economy.max(0.0));
+        m.economy -= revenue;
+        treasury.capacity = base * MAX_TAX;
+
+        // 2. Interest falls due; spending beyond what is left is borrowed
+        // for as long as lenders allow it.
+        let interest = treasury.debt * treasury.interest_rate();
+        let mut spending = revenue * treasury.spending_ratio;
+        if !treasury.can_borrow(time.tick) {
+            spending = spending.min((revenue - interest).max(0.0));
+        }
+        let balance = revenue - spending - interest;
+        m.economy += (balance - treasury.debt).max(0.0);
+        treasury.debt = (treasury.debt - balance).max(0.0);
+        treasury.revenue = revenue;
+        treasury.spending = spending;
+        treasury.interest = interest;
+
+        // 3. Each line pays the upkeep of what it holds and builds with the
+        // rest; short of the upkeep, the metric wears away.
+        for line in BudgetLine::ALL {
+            let spent = spending * f32::from(treasury.budget.share(line)) / 100.0;
+            match line {
+                BudgetLine::Military => {
+                    m.military += (spent - m.military * MILITARY_UPKEEP) / POINT_COST;
+                }
+                BudgetLine::Science => {
+                    m.science += (spent - m.science * SCIENCE_UPKEEP) / POINT_COST;
+                }
+                BudgetLine::Culture => {
+                    m.culture += (spent * 0.8 - m.culture * CULTURE_UPKEEP) / POINT_COST;
+                    m.religion += spent * 0.2 / POINT_COST;
+                }
+                BudgetLine::Diplomacy => {
+                    m.diplomacy += (spent - m.diplomacy * DIPLOMACY_UPKEEP) / POINT_COST;
+                }
+                BudgetLine::Welfare => happiness += spent * WELFARE_HAPPINESS,
+            }
+        }
+        happiness -= (treasury.tax_rate - DEFAULT_TAX).max(0.0) * TAX_DISCONTENT;
+        m.military = m.military.max(0.0);
+        m.science = m.science.max(0.0);
+        m.culture = m.culture.max(0.0);
+        m.diplomacy = m.diplomacy.max(0.0);
+
+        // 4. A state whose interest eats too much of its taxes defaults: it
+        // writes off part of the debt and pays in output, unrest and trust.
+        if treasury.debt > 0.0
+            && interest > assessed * DEFAULT_SHARE
+            && treasury.can_borrow(time.tick)
+        {
+            let haircut = treasury.debt * HAIRCUT;
+            event_log.push(WorldEvent::sovereign_default(
+                time.tick,
+                epoch,
+                season,
+                nation,
+                treasury.debt,
+                haircut,
+            ));
+            treasury.debt -= haircut;
+            treasury.shut_out_until = time.tick + SHUT_OUT_TICKS;
+            treasury.defaults += 1;
+            m.economy *= 1.0 - DEFAULT_SHOCK;
+            happiness -= DEFAULT_UNREST;
+            for &other in nations.iter().filter(|other| **other != nation) {
+                diplo.adjust_trust(other, nation, -DEFAULT_DISTRUST);
+            }
+        }
+
+        if let Some(c) = civ.0.get_mut(&nation) {
+            c.happiness = happiness.clamp(0.0, 100.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steering_never_pushes_a_line_past_the_cap() {
+        // Restless and investing, and calm and investing.
+        for happiness in [10.0, 60.0] {
+            let mut treasury = Treasury::default();
+            for _ in 0..200 {
+                steer(
+                    &mut treasury,
+                    Some(StrategicAction::InvestMilitary),
+                    false,
+                    happiness,
+                );
+                for line in BudgetLine::ALL {
+                    assert!(treasury.budget.share(line) <= FOCUS_CAP);
+                }
+            }
+        }
+    }
+}
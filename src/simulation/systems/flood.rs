@@ -1,25 +1,29 @@
 use bevy_ecs::prelude::*;
 
-use crate::simulation::{AxialCoord, ClimateState, HexGrid, grid::Hex};
+use crate::simulation::{ClimateState, WorldMetadata, grid::Hex, terrain};
 
-/// Applies sea level/ice line impact on hex ownership (soft flood tagging) and returns submerged coords.
+/// Reclassifies every hex under the current sea level and ice line, so rising
+/// seas drown low coasts and a retreating ice line thaws the cold latitudes.
+/// A reclassified hex takes on its new biome's base yields, and land lost to
+/// the sea is lost to its owner too.
 pub fn flood_system(
     climate: Res<ClimateState>,
-    grid: Res<HexGrid>,
-    mut query: Query<(&mut Hex, &AxialCoord)>,
+    world_meta: Res<WorldMetadata>,
+    mut query: Query<&mut Hex>,
 ) {
-    let sea = climate.sea_level;
-    let ice = climate.ice_line;
-    for (mut hex, coord) in query.iter_mut() {
-        // Map axial r to normalized vertical position.
-        let norm_y = 0.5 + coord.r as f32 / (grid.radius as f32 * 2.0 + 1.0);
-        // Soft flood: elevation under sea raises biome degradation (handled by consumers).
-        if hex.elevation < sea + 0.1 && norm_y > sea {
-            hex.biome = crate::simulation::Biome::Market; // treated as lowland/coastal
-        }
-        // Ice creep near poles.
-        if norm_y < ice {
-            hex.biome = crate::simulation::Biome::Desert; // frozen wasteland stand-in
+    for mut hex in query.iter_mut() {
+        let sample = terrain::TerrainSample {
+            elevation: hex.elevation,
+            moisture: hex.moisture,
+            temperature: hex.temperature,
+        };
+        let biome = terrain::classify(sample, &climate);
+        if hex.biome != biome {
+            hex.biome = biome;
+            hex.yields = world_meta.biome_yields(biome);
+            if biome.is_water() {
+                hex.owner = None;
+            }
         }
     }
 }
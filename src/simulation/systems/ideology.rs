@@ -1,8 +1,8 @@
 use bevy_ecs::prelude::*;
 
 use crate::simulation::{
-    AllNationMetrics, BehaviorState, Biome, Faction, IdeologyMatrix, Nation, WorldEvent,
-    WorldEventKind, WorldEventLog, WorldTime,
+    AllNationMetrics, BehaviorState, Biome, Faction, IdeologyMatrix, Nation, NationRegistry,
+    WorldEvent, WorldEventKind, WorldEventLog, WorldTime,
 };
 
 /// Spreads ideology leaning and cohesion through adjacency of power (economy/culture)
@@ -10,6 +10,7 @@ use crate::simulation::{
 pub fn ideology_system(
     mut matrix: ResMut<IdeologyMatrix>,
     metrics: Res<AllNationMetrics>,
+    nations: Res<NationRegistry>,
     time: Res<WorldTime>,
     mut log: ResMut<WorldEventLog>,
 ) {
@@ -61,7 +62,7 @@ pub fn ideology_system(
                 kind: WorldEventKind::Social {
                     convener: crate::simulation::EventActor {
                         id: 0,
-                        name: format!("{} inner faction", nation.name()),
+                        name: format!("{} inner faction", nations.name(*nation)),
                         nation: *nation,
                         faction: Faction::Neutral,
                         faction_label: "Internal".to_string(),
@@ -5,9 +5,9 @@ use colored::{Color, Colorize};
 use tracing::info;
 
 use crate::simulation::{
-    Behavior, Identity, Position, Sentiment, WorldEvent, WorldEventLog, WorldMetadata, WorldTime,
-    behavior_color, behavior_label, faction_color, faction_label, format_number_commas,
-    sentiment_color, sentiment_label,
+    Behavior, Identity, NationRegistry, Position, Sentiment, WorldEvent, WorldEventLog,
+    WorldMetadata, WorldTime, behavior_color, behavior_label, faction_color, faction_label,
+    format_number_commas, sentiment_color, sentiment_label,
 };
 
 fn badge(label: &str, color: Color) -> String {
@@ -20,8 +20,11 @@ fn category_color(category: &str) -> Color {
         "Social" => Color::BrightMagenta,
         "MacroShock" => Color::BrightRed,
         "War" => Color::Red,
+        "Peace" => Color::BrightGreen,
         "Era" => Color::BrightBlue,
         "Science" => Color::BrightCyan,
+        "Observer" => Color::BrightYellow,
+        "Finance" => Color::Yellow,
         _ => Color::White,
     }
 }
@@ -30,7 +33,7 @@ fn sentiment_tag(sentiment: Sentiment) -> String {
     badge(sentiment_label(sentiment), sentiment_color(sentiment))
 }
 
-fn format_event_line(event: &WorldEvent) -> String {
+fn format_event_line(event: &WorldEvent, nations: &NationRegistry) -> String {
     let category_badge = badge(event.category(), category_color(event.category()));
     let sentiment_badge = sentiment_tag(event.sentiment());
     let tick_badge = badge(&format!("Tick {}", event.tick), Color::BrightBlack);
@@ -134,12 +137,13 @@ fn format_event_line(event: &WorldEvent) -> String {
         crate::simulation::WorldEventKind::Warfare {
             winner,
             loser,
-            territory_change,
+            hexes_captured,
             casualties,
             nuclear,
+            ..
         } => {
-            let winner_badge = badge(winner.name(), winner.logging_color());
-            let loser_badge = badge(loser.name(), loser.logging_color());
+            let winner_badge = badge(nations.name(*winner), nations.logging_color(*winner));
+            let loser_badge = badge(nations.name(*loser), nations.logging_color(*loser));
             let casualty_badge = badge(
                 &format!("Casualties {}", format_number_commas(*casualties)),
                 Color::BrightRed,
@@ -151,7 +155,7 @@ fn format_event_line(event: &WorldEvent) -> String {
             };
 
             format!(
-                "{} {} {} {} {} {} wins War vs {} gaining territory {:.2}. {}{}",
+                "{} {} {} {} {} {} wins battle vs {} capturing {} hexes. {}{}",
                 category_badge,
                 sentiment_badge,
                 tick_badge,
@@ -159,17 +163,193 @@ fn format_event_line(event: &WorldEvent) -> String {
                 season_badge,
                 winner_badge,
                 loser_badge,
-                territory_change,
+                hexes_captured,
                 casualty_badge,
                 nuke_badge
             )
         }
+        crate::simulation::WorldEventKind::NuclearStrike {
+            attacker,
+            target,
+            warheads,
+            casualties,
+            retaliation,
+        } => {
+            let attacker_badge = badge(nations.name(*attacker), nations.logging_color(*attacker));
+            let target_badge = badge(nations.name(*target), nations.logging_color(*target));
+            let casualty_badge = badge(
+                &format!("Casualties {}", format_number_commas(*casualties)),
+                Color::BrightRed,
+            );
+            format!(
+                "{} {} {} {} {} {} {} {} warheads at {}. {} \u{0007}",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                attacker_badge,
+                if *retaliation {
+                    "retaliates with"
+                } else {
+                    "launches"
+                },
+                warheads,
+                target_badge,
+                casualty_badge
+            )
+        }
+        crate::simulation::WorldEventKind::WarDeclared {
+            attacker,
+            defender,
+            casus_belli,
+            allies,
+            ..
+        } => {
+            let attacker_badge = badge(nations.name(*attacker), nations.logging_color(*attacker));
+            let defender_badge = badge(nations.name(*defender), nations.logging_color(*defender));
+            let cause_badge = badge(casus_belli.label(), Color::Yellow);
+            format!(
+                "{} {} {} {} {} {} declares war on {} {} with {} allies drawn in",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                attacker_badge,
+                defender_badge,
+                cause_badge,
+                allies
+            )
+        }
+        crate::simulation::WorldEventKind::HexesCeded { from, to, hexes } => {
+            let from_badge = badge(nations.name(*from), nations.logging_color(*from));
+            let to_badge = badge(nations.name(*to), nations.logging_color(*to));
+            format!(
+                "{} {} {} {} {} {} cedes {} hexes to {}",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                from_badge,
+                hexes,
+                to_badge
+            )
+        }
+        crate::simulation::WorldEventKind::ReparationsPaid {
+            payer,
+            payee,
+            amount,
+        } => {
+            let payer_badge = badge(nations.name(*payer), nations.logging_color(*payer));
+            let payee_badge = badge(nations.name(*payee), nations.logging_color(*payee));
+            format!(
+                "{} {} {} {} {} {} pays {:.1} in reparations to {}",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                payer_badge,
+                amount,
+                payee_badge
+            )
+        }
+        crate::simulation::WorldEventKind::TruceSigned { a, b, until } => {
+            let a_badge = badge(nations.name(*a), nations.logging_color(*a));
+            let b_badge = badge(nations.name(*b), nations.logging_color(*b));
+            format!(
+                "{} {} {} {} {} {} and {} sign a truce until tick {}",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                a_badge,
+                b_badge,
+                until
+            )
+        }
+        crate::simulation::WorldEventKind::BlocChanged {
+            bloc,
+            bloc_kind,
+            nation,
+            change,
+        } => {
+            let nation_badge = badge(nations.name(*nation), nations.logging_color(*nation));
+            format!(
+                "{} {} {} {} {} {} {} the {} ({})",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                nation_badge,
+                change.label(),
+                bloc,
+                bloc_kind.label()
+            )
+        }
+        crate::simulation::WorldEventKind::TreatyBroken {
+            breaker,
+            ally,
+            treaty,
+        } => {
+            let breaker_badge = badge(nations.name(*breaker), nations.logging_color(*breaker));
+            let ally_badge = badge(nations.name(*ally), nations.logging_color(*ally));
+            format!(
+                "{} {} {} {} {} {} breaks the {} and abandons {}",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                breaker_badge,
+                treaty,
+                ally_badge
+            )
+        }
+        crate::simulation::WorldEventKind::AllianceDissolved { a, b, reason } => {
+            let a_badge = badge(nations.name(*a), nations.logging_color(*a));
+            let b_badge = badge(nations.name(*b), nations.logging_color(*b));
+            format!(
+                "{} {} {} {} {} alliance of {} and {} ends: {}",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                a_badge,
+                b_badge,
+                reason.label()
+            )
+        }
+        crate::simulation::WorldEventKind::SanctionsLifted {
+            issuer,
+            target,
+            concession,
+        } => {
+            let issuer_badge = badge(nations.name(*issuer), nations.logging_color(*issuer));
+            let target_badge = badge(nations.name(*target), nations.logging_color(*target));
+            format!(
+                "{} {} {} {} {} {} lifts sanctions on {} (concession {:.1})",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                issuer_badge,
+                target_badge,
+                concession
+            )
+        }
         crate::simulation::WorldEventKind::EraShift {
             nation,
             era,
             weapon,
         } => {
-            let nation_badge = badge(nation.name(), nation.logging_color());
+            let nation_badge = badge(nations.name(*nation), nations.logging_color(*nation));
             let era_badge = badge(era.label(), Color::BrightBlue);
             let weapon_badge = badge(weapon.label(), Color::Yellow);
 
@@ -185,8 +365,22 @@ fn format_event_line(event: &WorldEvent) -> String {
                 weapon_badge
             )
         }
+        crate::simulation::WorldEventKind::TechDiscovered { nation, tech } => {
+            let nation_badge = badge(nations.name(*nation), nations.logging_color(*nation));
+            let tech_badge = badge(tech.label(), Color::BrightBlue);
+            format!(
+                "{} {} {} {} {} {} discovers {}",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                nation_badge,
+                tech_badge
+            )
+        }
         crate::simulation::WorldEventKind::ScienceProgress { nation, progress } => {
-            let nation_badge = badge(nation.name(), nation.logging_color());
+            let nation_badge = badge(nations.name(*nation), nations.logging_color(*nation));
             let progress_badge = badge(
                 &format!("{:.1}% / 100%", progress.min(100.0)),
                 Color::BrightCyan,
@@ -202,7 +396,7 @@ fn format_event_line(event: &WorldEvent) -> String {
             )
         }
         crate::simulation::WorldEventKind::ScienceVictory { winner, progress } => {
-            let winner_badge = badge(winner.name(), winner.logging_color());
+            let winner_badge = badge(nations.name(*winner), nations.logging_color(*winner));
             let progress_badge = badge(
                 &format!("{:.1}% / 100%", progress.min(100.0)),
                 Color::BrightGreen,
@@ -218,7 +412,7 @@ fn format_event_line(event: &WorldEvent) -> String {
             )
         }
         crate::simulation::WorldEventKind::InterstellarProgress { leader, progress } => {
-            let leader_badge = badge(leader.name(), leader.logging_color());
+            let leader_badge = badge(nations.name(*leader), nations.logging_color(*leader));
             let progress_badge = badge(&format!("{:.1}% / 100%", progress.min(100.0)), Color::Cyan);
             format!(
                 "{} {} {} {} {} {} interstellar migration progress",
@@ -231,7 +425,7 @@ fn format_event_line(event: &WorldEvent) -> String {
             )
         }
         crate::simulation::WorldEventKind::InterstellarVictory { winner, progress } => {
-            let winner_badge = badge(winner.name(), winner.logging_color());
+            let winner_badge = badge(nations.name(*winner), nations.logging_color(*winner));
             let progress_badge = badge(
                 &format!("{:.1}% / 100%", progress.min(100.0)),
                 Color::BrightGreen,
@@ -246,6 +440,107 @@ fn format_event_line(event: &WorldEvent) -> String {
                 format!("{} {}", winner_badge, progress_badge)
             )
         }
+        crate::simulation::WorldEventKind::TradeRoute {
+            a,
+            b,
+            change,
+            volume,
+        } => {
+            let a_badge = badge(nations.name(*a), nations.logging_color(*a));
+            let b_badge = badge(nations.name(*b), nations.logging_color(*b));
+            format!(
+                "{} {} {} {} {} the route between {} and {} {} (volume {:.1})",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                a_badge,
+                b_badge,
+                change.label(),
+                volume
+            )
+        }
+        crate::simulation::WorldEventKind::SovereignDefault {
+            nation,
+            debt,
+            haircut,
+        } => {
+            let nation_badge = badge(nations.name(*nation), nations.logging_color(*nation));
+            format!(
+                "{} {} {} {} {} {} defaults on a debt of {:.1}, writing off {:.1}",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                nation_badge,
+                debt,
+                haircut
+            )
+        }
+        crate::simulation::WorldEventKind::EconomicCrisis {
+            nation,
+            crisis,
+            origin,
+            output_gap,
+        } => {
+            let nation_badge = badge(nations.name(*nation), nations.logging_color(*nation));
+            let spread = origin
+                .map(|origin| {
+                    format!(
+                        " spreading from {}",
+                        badge(nations.name(origin), nations.logging_color(origin))
+                    )
+                })
+                .unwrap_or_default();
+            format!(
+                "{} {} {} {} {} {} falls into a {}{} (output gap {:+.1}%)",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                nation_badge,
+                crisis.label().to_lowercase(),
+                spread,
+                output_gap
+            )
+        }
+        crate::simulation::WorldEventKind::EconomicRecovery {
+            nation,
+            crisis,
+            ticks,
+        } => {
+            let nation_badge = badge(nations.name(*nation), nations.logging_color(*nation));
+            format!(
+                "{} {} {} {} {} {} recovers from its {} after {} ticks",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                nation_badge,
+                crisis.label().to_lowercase(),
+                ticks
+            )
+        }
+        crate::simulation::WorldEventKind::Intervention {
+            intervention,
+            summary,
+        } => {
+            let label_badge = badge(intervention.label(), Color::BrightYellow);
+            format!(
+                "{} {} {} {} {} {} the observer {}",
+                category_badge,
+                sentiment_badge,
+                tick_badge,
+                epoch_badge,
+                season_badge,
+                label_badge,
+                summary
+            )
+        }
     }
 }
 
@@ -283,14 +578,14 @@ fn format_sample_line(
     );
 
     if let Some(meta) = biome_meta {
-        let epithet_badge = badge(meta.epithet, Color::BrightBlue);
+        let epithet_badge = badge(&meta.epithet, Color::BrightBlue);
         let description = meta.description.color(Color::BrightBlack).to_string();
         line.push_str(&format!(" | {} {}", epithet_badge, description));
     }
 
     if let Some(faction_meta) = world_meta.faction_profile(identity.faction) {
-        let motto_badge = badge(faction_meta.motto, Color::BrightYellow);
-        let doctrine_badge = badge(faction_meta.doctrine, Color::Yellow);
+        let motto_badge = badge(&faction_meta.motto, Color::BrightYellow);
+        let doctrine_badge = badge(&faction_meta.doctrine, Color::Yellow);
 
         line.push_str(&format!(" | {} {}", motto_badge, doctrine_badge));
 
@@ -302,7 +597,7 @@ fn format_sample_line(
                     world_meta
                         .biomes
                         .get(biome)
-                        .map(|b| b.label)
+                        .map(|b| b.label.as_str())
                         .unwrap_or("Unknown stronghold")
                 })
                 .collect::<Vec<_>>()
@@ -322,22 +617,23 @@ pub fn logging_system(
     time: Res<WorldTime>,
     world_meta: Res<WorldMetadata>,
     events: Res<WorldEventLog>,
+    nations: Res<NationRegistry>,
     query: Query<(&Identity, &Behavior, &Position)>,
 ) {
     let (epoch, season) = world_meta.epoch_for_tick(time.tick);
     let catalyst_index = (time.tick as usize) % world_meta.economy.catalysts.len();
-    let catalyst = world_meta.economy.catalysts[catalyst_index];
+    let catalyst = &world_meta.economy.catalysts[catalyst_index];
     let circulation_stage = world_meta
         .economy
         .circulation_cycle
         .get(catalyst_index % world_meta.economy.circulation_cycle.len())
-        .copied()
+        .map(String::as_str)
         .unwrap_or("Balanced trade");
     let stressor = world_meta
         .economy
         .stressors
         .get(catalyst_index % world_meta.economy.stressors.len())
-        .copied()
+        .map(String::as_str)
         .unwrap_or("Stable phase");
 
     let header_line = format!(
@@ -373,7 +669,7 @@ pub fn logging_system(
         .into_iter()
         .rev()
         .take(3)
-        .map(|event| format_event_line(&event));
+        .map(|event| format_event_line(&event, &nations));
 
     let mut has_event = false;
     for line in recent_events {
@@ -0,0 +1,352 @@
+//! The business cycle. Each tick every nation's output gap answers to its
+//! credit cycle, its budget deficit, its central bank's real rate, its
+//! trading partners' fortunes and a random demand shock. Inflation follows
+//! the gap and expectations, unemployment follows the gap by Okun's law and
+//! the central bank follows a Taylor rule. Credit booms end in busts, and
+//! busts and currency collapses spread along trade routes, bringing down
+//! partners caught in booms of their own.
+
+use std::collections::BTreeMap;
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemParam;
+use rand::Rng;
+
+use crate::simulation::{
+    AllNationMetrics, ClimateState, Crisis, CrisisKind, INFLATION_TARGET, MacroEconomies,
+    MacroState, NATURAL_UNEMPLOYMENT, NEUTRAL_RATE, Nation, NationMetrics, RngStream,
+    SimulationRng, SupplyState, TradeRoutes, Treasuries, WorldEvent, WorldEventLog, WorldMetadata,
+    WorldTime,
+};
+
+/// Share of last tick's output gap that carries over.
+const GAP_PERSISTENCE: f32 = 0.8;
+/// Points of output gap per unit of credit growth.
+const CREDIT_DEMAND: f32 = 20.0;
+/// Points of output gap per tick of full taxation by which the deficit
+/// widens.
+const FISCAL_MULTIPLIER: f32 = 4.0;
+/// Points of output gap lost per point the real rate sits above neutral.
+const RATE_SENSITIVITY: f32 = 0.3;
+/// Share of its partners' trade-weighted output gap a nation takes on.
+const TRADE_SPILLOVER: f32 = 0.15;
+/// Bound on the random demand shock each tick, in points of output gap.
+const DEMAND_NOISE: f32 = 0.5;
+/// Bound on how far the output gap can run either way.
+const GAP_LIMIT: f32 = 20.0;
+/// Points of inflation per point of output gap.
+const PHILLIPS: f32 = 0.15;
+/// Share of the gap between inflation and expectations that expectations
+/// close each tick, and the share of the way back to target a credible
+/// central bank pulls them.
+const EXPECTATIONS: f32 = 0.1;
+const ANCHOR: f32 = 0.05;
+/// Inflation added while a nation is short of supply.
+const SUPPLY_PUSH: f32 = 0.3;
+/// The Taylor rule's weights on inflation above target and on the output
+/// gap, and the share of the way to its target the rate moves in a tick.
+const TAYLOR_INFLATION: f32 = 0.5;
+const TAYLOR_GAP: f32 = 0.5;
+const RATE_ADJUST: f32 = 0.2;
+/// Points of unemployment per point of output gap, and the share of the way
+/// to that level unemployment moves in a tick.
+const OKUN: f32 = 0.5;
+const UNEMPLOYMENT_ADJUST: f32 = 0.2;
+/// Share of last tick's credit growth that carries over, and the growth a
+/// point of output gap (less the real rate above neutral) adds and the
+/// growth lost for each unit of credit above its trend.
+const CREDIT_MOMENTUM: f32 = 0.8;
+const CREDIT_APPETITE: f32 = 0.0015;
+const CREDIT_REVERSION: f32 = 0.01;
+/// Bound on the random swing in lenders' appetite each tick, and on credit
+/// growth itself.
+const CREDIT_NOISE: f32 = 0.01;
+const CREDIT_LIMIT: f32 = 0.1;
+/// Share of the way to credit its trend moves in a tick.
+const CREDIT_TREND_ADJUST: f32 = 0.02;
+/// Share of productivity gained per point of output gap.
+const PRODUCTIVITY_CYCLE: f32 = 0.02;
+/// Output gap below which a slump becomes a recession, and above which a
+/// nation has recovered.
+const RECESSION_GAP: f32 = -3.0;
+const RECOVERED_GAP: f32 = -1.0;
+/// Chance per tick of a bust for each unit of credit past the boom
+/// threshold, and the extra chance once output starts to fall.
+const BUST_ODDS: f32 = 0.5;
+const TURN_ODDS: f32 = 0.05;
+/// Credit growth while banks are deleveraging.
+const CRUNCH_GROWTH: f32 = -0.03;
+/// Inflation at which the currency gives way, and below which it has
+/// steadied.
+const RUNAWAY_INFLATION: f32 = 15.0;
+const TAMED_INFLATION: f32 = 6.0;
+/// Shortest a crisis lasts, in ticks.
+const CRISIS_MIN_TICKS: u64 = 10;
+/// Share of trade with a stricken partner past which a boom goes bust too,
+/// and the points of output gap lost at full exposure.
+const CONTAGION_EXPOSURE: f32 = 0.25;
+const CONTAGION_GAP: f32 = 3.0;
+
+/// Each nation's trading partners, with the share of its trade volume each
+/// route carries.
+fn trade_weights(routes: &TradeRoutes) -> BTreeMap<Nation, Vec<(Nation, f32)>> {
+    let mut totals: BTreeMap<Nation, f32> = BTreeMap::new();
+    for route in &routes.routes {
+        *totals.entry(route.a).or_default() += route.volume;
+        *totals.entry(route.b).or_default() += route.volume;
+    }
+    let mut weights: BTreeMap<Nation, Vec<(Nation, f32)>> = BTreeMap::new();
+    for route in &routes.routes {
+        for (nation, partner) in [(route.a, route.b), (route.b, route.a)] {
+            let total = totals.get(&nation).copied().unwrap_or(0.0);
+            if total > 0.0 {
+                weights
+                    .entry(nation)
+                    .or_default()
+                    .push((partner, route.volume / total));
+            }
+        }
+    }
+    weights
+}
+
+/// Puts `state` into a crisis of `kind` and takes its first toll: a
+/// banking crisis freezes credit and a currency crisis wrecks demand.
+fn start_crisis(
+    state: &mut MacroState,
+    m: &mut NationMetrics,
+    kind: CrisisKind,
+    origin: Option<Nation>,
+    tick: u64,
+) {
+    let (gap_hit, economy_loss) = match kind {
+        CrisisKind::Recession => (0.0, 0.05),
+        CrisisKind::BankingCrisis => (4.0, 0.15),
+        CrisisKind::CurrencyCrisis => (2.0, 0.1),
+    };
+    state.output_gap -= gap_hit;
+    if kind == CrisisKind::BankingCrisis {
+        state.credit_growth = CRUNCH_GROWTH;
+    }
+    m.economy *= 1.0 - economy_loss;
+    state.crisis = Some(Crisis {
+        kind,
+        since: tick,
+        origin,
+    });
+}
+
+/// Whether `state` has come through its crisis.
+fn recovered(state: &MacroState, crisis: &Crisis, tick: u64) -> bool {
+    tick >= crisis.since + CRISIS_MIN_TICKS
+        && match crisis.kind {
+            CrisisKind::Recession => state.output_gap > RECOVERED_GAP,
+            CrisisKind::BankingCrisis => {
+                state.output_gap > RECOVERED_GAP && state.credit_gap() <= 0.0
+            }
+            CrisisKind::CurrencyCrisis => state.inflation < TAMED_INFLATION,
+        }
+}
+
+/// The treasuries, trade, supply and climate that drive each business
+/// cycle.
+#[derive(SystemParam)]
+pub struct MacroInputs<'w> {
+    treasuries: Res<'w, Treasuries>,
+    routes: Res<'w, TradeRoutes>,
+    supply: Res<'w, SupplyState>,
+    climate: Res<'w, ClimateState>,
+    world_meta: Res<'w, WorldMetadata>,
+    time: Res<'w, WorldTime>,
+    rng_source: Res<'w, SimulationRng>,
+}
+
+/// Runs one tick of every living nation's business cycle, sets its
+/// productivity and unemployment from it, opens and closes crises and
+/// spreads new busts to exposed trading partners.
+pub fn macro_system(
+    mut economies: ResMut<MacroEconomies>,
+    mut all_metrics: ResMut<AllNationMetrics>,
+    mut event_log: ResMut<WorldEventLog>,
+    inputs: MacroInputs,
+) {
+    let MacroInputs {
+        treasuries,
+        routes,
+        supply,
+        climate,
+        world_meta,
+        time,
+        rng_source,
+    } = inputs;
+    let (epoch, season) = world_meta.epoch_for_tick(time.tick);
+    let climate_drag = (climate.climate_risk * 0.003).min(0.25);
+    let weights = trade_weights(&routes);
+    let gaps: BTreeMap<Nation, f32> = economies
+        .0
+        .iter()
+        .map(|(nation, state)| (*nation, state.output_gap))
+        .collect();
+    let mut busts: Vec<Nation> = Vec::new();
+
+    for (nation, m) in all_metrics.0.iter_mut() {
+        if m.is_destroyed {
+            continue;
+        }
+        let state = economies.0.entry(*nation).or_default();
+        let mut rng = rng_source.keyed_stream(RngStream::Macro, time.tick, u64::from(nation.0));
+
+        // 1. Demand: credit, the budget, the real rate, partners and luck.
+        let deficit = treasuries
+            .0
+            .get(nation)
+            .map_or(0.0, |t| -t.balance() / t.capacity.max(0.1));
+        let spillover: f32 = weights.get(nation).map_or(0.0, |partners| {
+            partners
+                .iter()
+                .map(|(partner, weight)| gaps.get(partner).copied().unwrap_or(0.0) * weight)
+                .sum()
+        });
+        let real_rate = state.real_rate() - NEUTRAL_RATE;
+        let impulse = deficit - state.fiscal_stance;
+        state.fiscal_stance = deficit;
+        state.output_gap = (GAP_PERSISTENCE * state.output_gap
+            + CREDIT_DEMAND * state.credit_growth
+            + FISCAL_MULTIPLIER * impulse
+            - RATE_SENSITIVITY * real_rate
+            + TRADE_SPILLOVER * spillover
+            + rng.gen_range(-DEMAND_NOISE..DEMAND_NOISE))
+        .clamp(-GAP_LIMIT, GAP_LIMIT);
+
+        // 2. Credit grows with demand and cheap money, slows as it runs
+        // ahead of its trend, and shrinks while the banks are in crisis.
+        let crunch = state
+            .crisis
+            .is_some_and(|c| c.kind == CrisisKind::BankingCrisis)
+            && state.credit_gap() > 0.0;
+        state.credit_growth = if crunch {
+            CRUNCH_GROWTH
+        } else {
+            (CREDIT_MOMENTUM * state.credit_growth
+                + CREDIT_APPETITE * (state.output_gap - real_rate)
+                - CREDIT_REVERSION * state.credit_gap()
+                + rng.gen_range(-CREDIT_NOISE..CREDIT_NOISE))
+            .clamp(-CREDIT_LIMIT, CREDIT_LIMIT)
+        };
+        state.credit = (state.credit * (1.0 + state.credit_growth)).max(0.1);
+        state.credit_trend += (state.credit - state.credit_trend) * CREDIT_TREND_ADJUST;
+
+        // 3. Prices, the central bank and jobs.
+        let shortage = if supply.deficit_ticks(*nation) > 0 {
+            SUPPLY_PUSH
+        } else {
+            0.0
+        };
+        state.inflation = state.expected_inflation + PHILLIPS * state.output_gap + shortage;
+        state.expected_inflation += (state.inflation - state.expected_inflation) * EXPECTATIONS
+            + (INFLATION_TARGET - state.expected_inflation) * ANCHOR;
+        let target_rate = NEUTRAL_RATE
+            + state.inflation
+            + TAYLOR_INFLATION * (state.inflation - INFLATION_TARGET)
+            + TAYLOR_GAP * state.output_gap;
+        state.policy_rate =
+            (state.policy_rate + (target_rate - state.policy_rate) * RATE_ADJUST).max(0.0);
+        let natural = NATURAL_UNEMPLOYMENT + climate_drag * 10.0;
+        let target_unemployment = natural - OKUN * state.output_gap;
+        state.unemployment = (state.unemployment
+            + (target_unemployment - state.unemployment) * UNEMPLOYMENT_ADJUST)
+            .clamp(1.0, 40.0);
+
+        m.productivity = (1.0 + state.output_gap * PRODUCTIVITY_CYCLE - climate_drag).max(0.4)
+            * m.economy_modifier;
+        m.unemployment = state.unemployment;
+
+        // 4. Crises open and close.
+        if let Some(crisis) = state.crisis
+            && recovered(state, &crisis, time.tick)
+        {
+            event_log.push(WorldEvent::economic_recovery(
+                time.tick,
+                epoch,
+                season,
+                *nation,
+                crisis.kind,
+                time.tick - crisis.since,
+            ));
+            state.crisis = None;
+        }
+        let current = state.crisis.map(|c| c.kind);
+        let bust_odds = state.boom() * BUST_ODDS
+            + if state.output_gap < 0.0 {
+                TURN_ODDS
+            } else {
+                0.0
+            };
+        let kind =
+            if state.inflation > RUNAWAY_INFLATION && current != Some(CrisisKind::CurrencyCrisis) {
+                Some(CrisisKind::CurrencyCrisis)
+            } else if state.boom() > 0.0
+                && current != Some(CrisisKind::BankingCrisis)
+                && rng.gen_bool(f64::from(bust_odds.min(1.0)))
+            {
+                Some(CrisisKind::BankingCrisis)
+            } else if state.output_gap < RECESSION_GAP && current.is_none() {
+                Some(CrisisKind::Recession)
+            } else {
+                None
+            };
+        if let Some(kind) = kind {
+            start_crisis(state, m, kind, None, time.tick);
+            event_log.push(WorldEvent::economic_crisis(
+                time.tick,
+                epoch,
+                season,
+                *nation,
+                kind,
+                None,
+                state.output_gap,
+            ));
+            if kind != CrisisKind::Recession {
+                busts.push(*nation);
+            }
+        }
+    }
+
+    // 5. Busts and currency collapses drag on every partner in proportion
+    // to its exposure; a heavily exposed partner in a boom goes bust too.
+    for source in busts {
+        for (partner, _) in weights.get(&source).into_iter().flatten() {
+            let exposure = weights
+                .get(partner)
+                .and_then(|list| list.iter().find(|(n, _)| *n == source))
+                .map_or(0.0, |(_, weight)| *weight);
+            let (Some(state), Some(m)) =
+                (economies.0.get_mut(partner), all_metrics.0.get_mut(partner))
+            else {
+                continue;
+            };
+            if m.is_destroyed {
+                continue;
+            }
+            state.output_gap -= CONTAGION_GAP * exposure;
+            let caught = exposure > CONTAGION_EXPOSURE
+                && state.boom() > 0.0
+                && state
+                    .crisis
+                    .is_none_or(|c| c.kind != CrisisKind::BankingCrisis);
+            if caught {
+                let kind = CrisisKind::BankingCrisis;
+                start_crisis(state, m, kind, Some(source), time.tick);
+                event_log.push(WorldEvent::economic_crisis(
+                    time.tick,
+                    epoch,
+                    season,
+                    *partner,
+                    kind,
+                    Some(source),
+                    state.output_gap,
+                ));
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod ai;
+pub mod armies;
 pub mod blocs;
 pub mod civilization;
 pub mod climate;
@@ -9,22 +10,29 @@ pub mod diplomacy;
 pub mod economy;
 pub mod environment;
 pub mod events;
+pub mod fiscal;
 pub mod flood;
 pub mod ideology;
 pub mod logging;
+pub mod macroeconomy;
 pub mod missions;
 pub mod nuclear;
 pub mod peace;
+pub mod player;
 pub mod richness;
 pub mod security;
+pub mod strategy;
 pub mod supply;
 pub mod technology;
 pub mod territory;
+pub mod trade;
+pub mod treaties;
 pub mod victory;
 pub mod warfare;
 pub mod warfatigue;
 
 pub use ai::*;
+pub use armies::*;
 pub use blocs::*;
 pub use civilization::*;
 pub use climate::*;
@@ -35,16 +43,22 @@ pub use diplomacy::*;
 pub use economy::*;
 pub use environment::*;
 pub use events::*;
+pub use fiscal::*;
 pub use flood::*;
 pub use ideology::*;
 pub use logging::*;
+pub use macroeconomy::*;
 pub use missions::*;
 pub use nuclear::*;
 pub use peace::*;
+pub use player::*;
 pub use security::*;
+pub use strategy::*;
 pub use supply::*;
 pub use technology::*;
 pub use territory::*;
+pub use trade::*;
+pub use treaties::*;
 pub use victory::*;
 pub use warfare::*;
 pub use warfatigue::*;
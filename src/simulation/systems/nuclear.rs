@@ -1,15 +1,173 @@
+//! Nuclear arsenals: building warheads, deciding when to fire them, and the
+//! fallout that drifts downwind afterwards.
+
 use bevy_ecs::prelude::*;
-use std::collections::HashSet;
+use rand::Rng;
+use rand::prelude::SliceRandom;
+use std::collections::{HashMap, HashSet};
+
+use crate::simulation::{
+    AllNationMetrics, Arsenal, AxialCoord, HexGrid, Nation, NuclearArsenals, NuclearBlasts,
+    NuclearDoctrine, Salvo, Wars, WeaponTier, terrain,
+};
 
-use crate::simulation::{AxialCoord, NuclearBlasts};
+/// Largest stockpile a nation will build.
+const MAX_WARHEADS: u32 = 60;
+/// Economy spent on each warhead, a little every tick.
+const WARHEAD_COST: f32 = 400.0;
+/// Most warheads fired in a single salvo.
+const SALVO_SIZE: u32 = 3;
+/// Chance that a first-strike power beaten in battle opens fire, before the
+/// enemy's second strike deters it.
+const FIRST_STRIKE_CHANCE: f64 = 0.3;
+/// Chance that each battle of a war that has already gone nuclear sees the
+/// beaten side fire again.
+const ESCALATION_CHANCE: f64 = 0.5;
+/// A second strike of this many warheads halves the odds of war or first use.
+const DETERRENT_WARHEADS: f64 = 10.0;
+const BLAST_RADIUS: i32 = 1;
+const BLAST_DURATION: u8 = 8;
+/// Fallout at least this heavy drifts onto the downwind hex.
+const DRIFT_THRESHOLD: u8 = 4;
 
-#[allow(dead_code)]
 fn axial_distance(a: AxialCoord, b: AxialCoord) -> i32 {
     ((a.q - b.q).abs() + (a.q + a.r - b.q - b.r).abs() + (a.r - b.r).abs()) / 2
 }
 
-/// Decays nuclear blast markers over time.
-pub fn nuclear_decay_system(mut blasts: ResMut<NuclearBlasts>) {
+/// How much of an aggressor's resolve survives facing `warheads` fired back.
+fn second_strike(warheads: u32) -> f64 {
+    1.0 / (1.0 + warheads as f64 / DETERRENT_WARHEADS)
+}
+
+/// Share of wars between `a` and `b` that still break out: untouched unless
+/// both can strike back, then falling as the smaller stockpile grows.
+pub fn deterrence(arsenals: &NuclearArsenals, a: Nation, b: Nation) -> f64 {
+    let smaller = arsenals.warheads(a).min(arsenals.warheads(b));
+    if smaller == 0 {
+        1.0
+    } else {
+        second_strike(smaller)
+    }
+}
+
+/// Whether `nation`, just beaten in battle by `enemy`, fires its arsenal.
+/// Before a war goes nuclear only first-strike doctrines launch, and less
+/// often the more the enemy could fire back; after that anyone may.
+pub fn launches(
+    arsenals: &NuclearArsenals,
+    nation: Nation,
+    enemy: Nation,
+    war_gone_nuclear: bool,
+    rng: &mut impl Rng,
+) -> bool {
+    let Some(arsenal) = arsenals.0.get(&nation) else {
+        return false;
+    };
+    if arsenal.warheads == 0 {
+        false
+    } else if war_gone_nuclear {
+        rng.gen_bool(ESCALATION_CHANCE)
+    } else {
+        arsenal.doctrine == NuclearDoctrine::FirstStrike
+            && rng.gen_bool(FIRST_STRIKE_CHANCE * second_strike(arsenals.warheads(enemy)))
+    }
+}
+
+/// Fires a salvo from `attacker` at hexes `target` holds, marking each blast
+/// and killing part of its population. Nothing is fired at a nation with no
+/// land left.
+pub fn launch_salvo(
+    arsenals: &mut NuclearArsenals,
+    blasts: &mut NuclearBlasts,
+    metrics: &mut AllNationMetrics,
+    owners: &HashMap<AxialCoord, Nation>,
+    attacker: Nation,
+    target: Nation,
+    rng: &mut impl Rng,
+) -> Salvo {
+    let Some(arsenal) = arsenals.0.get_mut(&attacker) else {
+        return Salvo::default();
+    };
+    let mut targets: Vec<AxialCoord> = owners
+        .iter()
+        .filter(|(_, owner)| **owner == target)
+        .map(|(coord, _)| *coord)
+        .collect();
+    // Sort first so the shuffle does not inherit HashMap iteration order.
+    targets.sort_by_key(|c| (c.q, c.r));
+    targets.shuffle(rng);
+    targets.truncate(arsenal.warheads.min(SALVO_SIZE) as usize);
+    let fired = targets.len() as u32;
+    arsenal.warheads -= fired;
+
+    let mut casualties: u64 = 0;
+    for center in targets {
+        mark_nuclear_blast(blasts, center, BLAST_RADIUS, BLAST_DURATION);
+        casualties += rng.gen_range(150_000..450_000);
+    }
+    if let Some(m) = metrics.0.get_mut(&target) {
+        casualties = casualties.min(m.population);
+        m.population -= casualties;
+        m.economy *= 0.9_f32.powi(fired as i32);
+    }
+    Salvo {
+        warheads: fired,
+        casualties,
+    }
+}
+
+/// Lets nations that have reached the nuclear weapon tier build warheads
+/// out of their economy. Each picks a doctrine once, when its programme
+/// starts: nations that get the bomb in wartime plan to fire first, those
+/// at peace only to answer.
+pub fn nuclear_arsenal_system(
+    mut arsenals: ResMut<NuclearArsenals>,
+    metrics: Res<AllNationMetrics>,
+    wars: Res<Wars>,
+) {
+    for (nation, m) in metrics.0.iter() {
+        if m.is_destroyed {
+            arsenals.0.remove(nation);
+            continue;
+        }
+        if m.weapon_tier != WeaponTier::NuclearArsenal {
+            continue;
+        }
+        let arsenal = arsenals.0.entry(*nation).or_insert_with(|| Arsenal {
+            warheads: 0,
+            doctrine: if wars.active.iter().any(|war| war.side(*nation).is_some()) {
+                NuclearDoctrine::FirstStrike
+            } else {
+                NuclearDoctrine::NoFirstUse
+            },
+            production: 0.0,
+        });
+        if arsenal.warheads >= MAX_WARHEADS {
+            continue;
+        }
+        arsenal.production += m.economy.max(0.0) / WARHEAD_COST;
+        while arsenal.production >= 1.0 && arsenal.warheads < MAX_WARHEADS {
+            arsenal.production -= 1.0;
+            arsenal.warheads += 1;
+        }
+    }
+}
+
+/// Decays nuclear blast markers over time. Heavy fallout first drifts onto
+/// the hex downwind of it, so a strike leaves a plume across its neighbours.
+pub fn nuclear_decay_system(mut blasts: ResMut<NuclearBlasts>, grid: Res<HexGrid>) {
+    let drifting: Vec<(AxialCoord, u8)> = blasts
+        .0
+        .iter()
+        .filter(|(_, timer)| **timer >= DRIFT_THRESHOLD)
+        .map(|(coord, timer)| (terrain::downwind(*coord, grid.radius), *timer / 2))
+        .filter(|(coord, _)| grid.hexes.contains_key(coord))
+        .collect();
+    for (coord, timer) in drifting {
+        let fallout = blasts.0.entry(coord).or_insert(0);
+        *fallout = (*fallout).max(timer);
+    }
+
     let mut to_remove = Vec::new();
     for (coord, timer) in blasts.0.iter_mut() {
         if *timer == 0 {
@@ -24,7 +182,6 @@ pub fn nuclear_decay_system(mut blasts: ResMut<NuclearBlasts>) {
 }
 
 /// Marks surrounding hexes when a nuclear strike hits.
-#[allow(dead_code)]
 pub fn mark_nuclear_blast(
     blasts: &mut NuclearBlasts,
     center: AxialCoord,
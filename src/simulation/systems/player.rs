@@ -0,0 +1,150 @@
+//! Carries out the played nation's orders, including the changes to its
+//! budget, taxes and spending that its strategy would otherwise make.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemParam;
+
+use crate::simulation::systems::diplomacy;
+use crate::simulation::{
+    AllNationMetrics, DiplomaticRelations, IdeologyMatrix, NationRegistry, PlayerCommand,
+    PlayerControl, ScienceVictory, Treasuries, Wars, WorldEventLog, WorldTime,
+};
+
+/// What the played nation's orders are checked against.
+#[derive(SystemParam)]
+pub struct PlayerInputs<'w> {
+    all_metrics: Res<'w, AllNationMetrics>,
+    ideology: Res<'w, IdeologyMatrix>,
+    registry: Res<'w, NationRegistry>,
+    time: Res<'w, WorldTime>,
+    science_victory: Res<'w, ScienceVictory>,
+}
+
+/// Applies the orders queued since the last tick. Budget, tax and spending
+/// orders change the played nation's treasury, which the fiscal system
+/// spends.
+///
+/// Declarations go to the warfare system, which still checks truces; a peace
+/// offer opens talks once the war is worn down; an alliance is signed only
+/// if the partner accepts it.
+pub fn player_system(
+    mut player: ResMut<PlayerControl>,
+    mut treasuries: ResMut<Treasuries>,
+    mut wars: ResMut<Wars>,
+    mut diplo: ResMut<DiplomaticRelations>,
+    mut event_log: ResMut<WorldEventLog>,
+    inputs: PlayerInputs,
+) {
+    let PlayerInputs {
+        all_metrics,
+        ideology,
+        registry,
+        time,
+        science_victory,
+    } = inputs;
+    let alive = |metrics: &AllNationMetrics, nation| {
+        metrics.0.get(&nation).is_some_and(|m| !m.is_destroyed)
+    };
+    for command in std::mem::take(&mut player.pending) {
+        if let PlayerCommand::TakeCommand { nation } = command {
+            player.report = Some(match (nation, player.nation) {
+                (Some(nation), _) if alive(&all_metrics, nation) => {
+                    player.nation = Some(nation);
+                    format!("Took command of {}", registry.name(nation))
+                }
+                (Some(nation), _) => format!("{} has fallen", registry.name(nation)),
+                (None, Some(played)) => {
+                    player.nation = None;
+                    format!("Handed {} back to its leaders", registry.name(played))
+                }
+                (None, None) => "No nation in command".to_string(),
+            });
+            continue;
+        }
+        let Some(nation) = player.nation.filter(|n| alive(&all_metrics, *n)) else {
+            player.report = Some("No nation in command".to_string());
+            continue;
+        };
+        player.report = Some(match command {
+            PlayerCommand::ShiftBudget { line, delta } => {
+                let budget = &mut treasuries.0.entry(nation).or_default().budget;
+                budget.shift(line, delta);
+                format!("{} budget at {}%", line.label(), budget.share(line))
+            }
+            PlayerCommand::ShiftTax { delta } => {
+                let treasury = treasuries.0.entry(nation).or_default();
+                treasury.shift_tax(f32::from(delta) / 100.0);
+                format!("Tax rate at {:.0}%", treasury.tax_rate * 100.0)
+            }
+            PlayerCommand::ShiftSpending { delta } => {
+                let treasury = treasuries.0.entry(nation).or_default();
+                treasury.shift_spending(f32::from(delta) / 100.0);
+                format!(
+                    "Spending at {:.0}% of revenue",
+                    treasury.spending_ratio * 100.0
+                )
+            }
+            PlayerCommand::DeclareWar { target } => {
+                if science_victory.finished {
+                    "The science race is won; no more wars".to_string()
+                } else if target == nation || !alive(&all_metrics, target) {
+                    "No one to declare war on".to_string()
+                } else if wars.at_war(nation, target) {
+                    format!("Already at war with {}", registry.name(target))
+                } else if wars.in_truce(nation, target) {
+                    format!("A truce binds us to {}", registry.name(target))
+                } else {
+                    wars.declarations.push((nation, target));
+                    format!("Declared war on {}", registry.name(target))
+                }
+            }
+            PlayerCommand::OfferPeace { enemy } => match wars.between_mut(nation, enemy) {
+                Some(war) if !war.leads(nation) => {
+                    "Only the war leader can sue for peace".to_string()
+                }
+                Some(war) => {
+                    war.peace_offered = true;
+                    format!("Sued {} for peace", registry.name(enemy))
+                }
+                None => format!("Not at war with {}", registry.name(enemy)),
+            },
+            PlayerCommand::ProposeAlliance { partner } => {
+                if diplo.allied(nation, partner) {
+                    format!("Already allied with {}", registry.name(partner))
+                } else if alive(&all_metrics, partner)
+                    && diplomacy::accepts_alliance(&diplo, &wars, &ideology, nation, partner)
+                {
+                    diplomacy::sign_alliance(
+                        &mut diplo,
+                        &mut event_log,
+                        &registry,
+                        nation,
+                        partner,
+                        time.tick,
+                    );
+                    format!("{} accepts the alliance", registry.name(partner))
+                } else {
+                    format!("{} declines the alliance", registry.name(partner))
+                }
+            }
+            PlayerCommand::Sanction { target } => {
+                if target == nation || !alive(&all_metrics, target) {
+                    "No one to sanction".to_string()
+                } else if diplo.sanctioning(nation, target) {
+                    format!("Already sanctioning {}", registry.name(target))
+                } else {
+                    diplomacy::impose_sanction(
+                        &mut diplo,
+                        &mut event_log,
+                        &registry,
+                        nation,
+                        target,
+                        time.tick,
+                    );
+                    format!("Sanctioned {}", registry.name(target))
+                }
+            }
+            PlayerCommand::TakeCommand { .. } => continue,
+        });
+    }
+}
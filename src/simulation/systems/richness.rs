@@ -1,11 +1,12 @@
 use bevy_ecs::prelude::*;
 
-use crate::simulation::{Hex, WorldRichness, grid::HexGrid};
+use crate::simulation::{Hex, NationRegistry, WorldRichness, grid::HexGrid};
 
 /// Aggregates a simple "resource richness" overlay based on hex ownership diversity.
 #[allow(dead_code)]
 pub fn richness_overlay_system(
     grid: Res<HexGrid>,
+    nations: Res<NationRegistry>,
     hexes: Query<&Hex>,
     mut overlay: ResMut<WorldRichness>,
 ) {
@@ -13,17 +14,21 @@ pub fn richness_overlay_system(
         overlay.richness = 0.0;
         return;
     }
-    let mut counts = std::collections::HashMap::new();
+    let mut counts = std::collections::BTreeMap::new();
     for entity in grid.hexes.values() {
-        if let Ok(hex) = hexes.get(*entity) {
-            *counts.entry(hex.owner).or_insert(0usize) += 1;
+        if let Ok(Hex {
+            owner: Some(owner), ..
+        }) = hexes.get(*entity)
+        {
+            *counts.entry(*owner).or_insert(0usize) += 1;
         }
     }
-    let total = grid.hexes.len() as f32;
-    let diversity = counts.len() as f32 / 5.0; // 5 nations baseline
+    let total = counts.values().sum::<usize>().max(1) as f32;
+    let nation_count = nations.nations.len().max(1) as f32;
+    let diversity = counts.len() as f32 / nation_count;
     let balance = counts
         .values()
-        .map(|c| (*c as f32 / total - 0.2).abs())
+        .map(|c| (*c as f32 / total - 1.0 / nation_count).abs())
         .sum::<f32>();
     overlay.richness = (diversity - balance).clamp(0.0, 1.0);
 }
@@ -13,8 +13,8 @@ pub fn security_system(
 ) {
     // Sanction penalties
     let mut sanction_count: Vec<(Nation, u32)> = Vec::new();
-    for (_issuer, target) in diplo.sanctions.iter() {
-        sanction_count.push((*target, 1));
+    for sanction in diplo.sanctions.iter() {
+        sanction_count.push((sanction.target, 1));
     }
     for (nation, penalty_times) in sanction_count {
         if let Some(m) = metrics.0.get_mut(&nation) {
@@ -36,7 +36,8 @@ pub fn security_system(
     }
 
     // Alliance reassurance gives slight diplomacy boost
-    for (a, b) in diplo.alliances.iter() {
+    for alliance in diplo.alliances.iter() {
+        let (a, b) = (&alliance.a, &alliance.b);
         if let Some(ma) = metrics.0.get_mut(a) {
             ma.diplomacy = (ma.diplomacy + 2.0).min(200.0);
         }
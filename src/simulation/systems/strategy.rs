@@ -0,0 +1,447 @@
+//! Nation-level decisions: each tick every nation scores the actions open to
+//! it by the utility they promise, scaled by its leadership's temperament,
+//! and draws one, the better scores the likelier.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemParam;
+use rand::Rng;
+
+use crate::simulation::systems::{blocs, civilization, diplomacy, nuclear, treaties};
+use crate::simulation::{
+    AiProfile, AllNationCivState, AllNationMetrics, AxialCoord, BlocKind, DiplomaticRelations, Hex,
+    IdeologyMatrix, Nation, NationGoal, NationMetrics, NationRegistry, NationStrategies,
+    NuclearArsenals, PlayerControl, RngStream, ScienceVictory, SimulationRng, StrategicAction,
+    TradeRoutes, Wars, WorldBlocs, WorldEventLog, WorldTime, border_lengths,
+};
+
+/// Economy spent settling and building a new city.
+const CITY_COST: f32 = 10.0;
+/// Fear of the most feared rival, 0..1, above which a nation puts its
+/// ambition aside and looks to its security.
+const SECURITY_THREAT: f32 = 0.6;
+/// Utility added to actions that serve the nation's goal.
+const GOAL_BONUS: f32 = 0.15;
+/// Score difference over which one option becomes e times as likely as
+/// another; lower values make nations more single-minded.
+const TEMPERATURE: f32 = 0.1;
+/// Opinion of a nation below which sanctioning it is considered.
+const SANCTION_OPINION: f32 = -45.0;
+/// Combined diplomacy, culture and religion at which a pair's taste for war
+/// is halved.
+const PEACE_CEILING: f32 = 400.0;
+/// Appetite for war against an evenly matched rival with no grudge.
+const WAR_APPETITE: f32 = 0.2;
+/// Share of the appetite for war a nation wholly dependent on its trade
+/// with the rival gives up.
+const TRADE_RESTRAINT: f32 = 0.6;
+
+/// The wars, blocs and standing between nations that their actions change.
+#[derive(SystemParam)]
+pub struct Relations<'w> {
+    wars: ResMut<'w, Wars>,
+    world_blocs: ResMut<'w, WorldBlocs>,
+    diplo: ResMut<'w, DiplomaticRelations>,
+}
+
+/// What a nation weighs, but never changes, when it picks its action.
+#[derive(SystemParam)]
+pub struct StrategyInputs<'w, 's> {
+    player: Res<'w, PlayerControl>,
+    ideology: Res<'w, IdeologyMatrix>,
+    arsenals: Res<'w, NuclearArsenals>,
+    routes: Res<'w, TradeRoutes>,
+    registry: Res<'w, NationRegistry>,
+    science_victory: Res<'w, ScienceVictory>,
+    time: Res<'w, WorldTime>,
+    rng_source: Res<'w, SimulationRng>,
+    hexes: Query<'w, 's, (&'static AxialCoord, &'static Hex)>,
+}
+
+/// Chooses and carries out one strategic action per living nation not
+/// under player command.
+///
+/// New cities and sanctions take effect at once; an investment tilts the
+/// nation's budget toward its line when the fiscal system next spends it.
+/// Declarations are carried out by the warfare system and applications put
+/// to the bloc's vote; suing for peace opens talks once the war is worn
+/// down.
+pub fn strategy_system(
+    mut strategies: ResMut<NationStrategies>,
+    mut all_metrics: ResMut<AllNationMetrics>,
+    mut civ: ResMut<AllNationCivState>,
+    relations: Relations,
+    mut event_log: ResMut<WorldEventLog>,
+    inputs: StrategyInputs,
+) {
+    let StrategyInputs {
+        player,
+        ideology,
+        arsenals,
+        routes,
+        registry,
+        science_victory,
+        time,
+        rng_source,
+        hexes,
+    } = inputs;
+    let Relations {
+        mut wars,
+        mut world_blocs,
+        mut diplo,
+    } = relations;
+    if science_victory.finished {
+        return;
+    }
+    let borders = border_lengths(hexes.iter());
+    let nations: Vec<Nation> = strategies.0.keys().copied().collect();
+
+    for nation in nations {
+        let Some(metrics) = all_metrics.0.get(&nation) else {
+            continue;
+        };
+        if metrics.is_destroyed || player.nation == Some(nation) {
+            continue;
+        }
+        let Some(strategy) = strategies.0.get_mut(&nation) else {
+            continue;
+        };
+        let mut rng = rng_source.keyed_stream(RngStream::Strategy, time.tick, u64::from(nation.0));
+
+        let threat = diplo.greatest_fear(nation) / 100.0;
+        strategy.goal = goal_for(strategy.profile, threat);
+        let progressive = ideology.leaning.get(&nation).copied().unwrap_or(50.0) / 100.0;
+        let at_war = wars.active.iter().any(|war| war.side(nation).is_some());
+        let happiness = civ.0.get(&nation).map_or(50.0, |c| c.happiness);
+        let cities = civ.0.get(&nation).map_or(0, |c| c.cities);
+
+        // How far behind the world's leader in a metric the nation trails,
+        // 0 for the leader; lagging nations see more point in investing.
+        let lag = |metric: fn(&NationMetrics) -> f32| {
+            let best = all_metrics
+                .0
+                .values()
+                .filter(|m| !m.is_destroyed)
+                .map(metric)
+                .fold(0.0_f32, f32::max);
+            if best > 0.0 {
+                1.0 - metric(metrics) / best
+            } else {
+                0.0
+            }
+        };
+
+        let mut options = vec![(StrategicAction::Hold, 0.3)];
+        options.extend(investments(
+            threat,
+            at_war,
+            progressive,
+            happiness,
+            [lag(|m| m.military), lag(|m| m.science), lag(|m| m.culture)],
+        ));
+        if metrics.economy >= CITY_COST && cities < civilization::max_cities(metrics) {
+            options.push((StrategicAction::FoundCity, 0.45));
+        }
+        if !at_war
+            && let Some((target, utility)) = war_target(
+                nation,
+                &all_metrics,
+                &diplo,
+                &wars,
+                &arsenals,
+                &routes,
+                &borders,
+            )
+        {
+            options.push((StrategicAction::DeclareWar { target }, utility));
+        }
+        for war in &wars.active {
+            if !war.leads(nation)
+                || war.peace_offered
+                || war.exhaustion <= treaties::TALKS_EXHAUSTION
+            {
+                continue;
+            }
+            // War score from this nation's side; negative when losing.
+            let standing = if war.side(nation) == Some(true) {
+                war.score
+            } else {
+                -war.score
+            };
+            options.push((
+                StrategicAction::SueForPeace { war: war.id },
+                0.1 + (-standing).max(0.0) / 50.0 + war.exhaustion / 200.0,
+            ));
+        }
+        for bloc in &world_blocs.blocs {
+            if bloc.members.contains(&nation)
+                || world_blocs.bloc_of(nation, bloc.kind).is_some()
+                || world_blocs
+                    .applications
+                    .iter()
+                    .any(|(applicant, _)| *applicant == nation)
+                || bloc
+                    .members
+                    .iter()
+                    .any(|member| wars.at_war(nation, *member))
+                || !blocs::willing(&diplo, bloc.kind, bloc.target, nation, &bloc.members)
+            {
+                continue;
+            }
+            let welcome = bloc
+                .members
+                .iter()
+                .filter(|member| diplo.opinion(**member, nation) > 0.0)
+                .count();
+            if welcome * 2 <= bloc.members.len() {
+                continue;
+            }
+            let need = match bloc.kind {
+                BlocKind::DefenseTreaty => threat * 0.4,
+                BlocKind::ResearchPact => progressive * 0.2,
+                BlocKind::Sanction => 0.0,
+            };
+            options.push((StrategicAction::JoinBloc { bloc: bloc.id }, 0.35 + need));
+        }
+        if let Some((target, opinion)) = all_metrics
+            .0
+            .iter()
+            .filter(|(other, m)| {
+                **other != nation && !m.is_destroyed && !diplo.sanctioning(nation, **other)
+            })
+            .map(|(other, _)| (*other, diplo.opinion(nation, *other)))
+            .filter(|(_, opinion)| *opinion < SANCTION_OPINION)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+        {
+            options.push((
+                StrategicAction::Sanction { target },
+                0.3 + (SANCTION_OPINION - opinion) / 100.0,
+            ));
+        }
+
+        let action = choose(options, strategy.profile, strategy.goal, &mut rng);
+        strategy.last_action = action;
+        *strategy.tally.entry(action.kind()).or_insert(0) += 1;
+
+        match action {
+            // The fiscal system shifts the budget toward the chosen line.
+            StrategicAction::Hold
+            | StrategicAction::InvestMilitary
+            | StrategicAction::InvestScience
+            | StrategicAction::InvestCulture => {}
+            StrategicAction::FoundCity => {
+                let (Some(m), Some(c)) = (all_metrics.0.get_mut(&nation), civ.0.get_mut(&nation))
+                else {
+                    continue;
+                };
+                m.economy -= CITY_COST;
+                m.population = m.population.saturating_add(120_000);
+                c.cities += 1;
+                c.production += 3.0;
+                c.happiness += 2.0;
+            }
+            StrategicAction::DeclareWar { target } => wars.declarations.push((nation, target)),
+            StrategicAction::SueForPeace { war } => {
+                if let Some(war) = wars.active.iter_mut().find(|w| w.id == war) {
+                    war.peace_offered = true;
+                }
+            }
+            StrategicAction::JoinBloc { bloc } => world_blocs.applications.push((nation, bloc)),
+            StrategicAction::Sanction { target } => diplomacy::impose_sanction(
+                &mut diplo,
+                &mut event_log,
+                &registry,
+                nation,
+                target,
+                time.tick,
+            ),
+        }
+    }
+}
+
+/// The goal a nation of `profile` pursues when its most feared rival
+/// inspires `threat` (0..1) of fear.
+fn goal_for(profile: AiProfile, threat: f32) -> NationGoal {
+    if threat > SECURITY_THREAT {
+        NationGoal::Security
+    } else {
+        profile.ambition()
+    }
+}
+
+/// Utility of each line a nation can invest in. Threat and war argue for
+/// the military, a progressive leaning for science and unrest for culture;
+/// `lags` (military, science, culture) add to whichever the nation trails
+/// the world in.
+fn investments(
+    threat: f32,
+    at_war: bool,
+    progressive: f32,
+    happiness: f32,
+    lags: [f32; 3],
+) -> [(StrategicAction, f32); 3] {
+    let [military, science, culture] = lags;
+    [
+        (
+            StrategicAction::InvestMilitary,
+            0.2 + threat * 0.4
+                + if at_war { 0.3 } else { 0.0 }
+                + military * 0.3
+                + (1.0 - progressive) * 0.1,
+        ),
+        (
+            StrategicAction::InvestScience,
+            0.2 + science * 0.3 + progressive * 0.2,
+        ),
+        (
+            StrategicAction::InvestCulture,
+            0.2 + culture * 0.3 + (1.0 - happiness / 100.0) * 0.2,
+        ),
+    ]
+}
+
+/// Draws one of `options` by softmax over its score: the utility scaled by
+/// the profile's temperament, plus a bonus if it serves the goal.
+fn choose(
+    options: Vec<(StrategicAction, f32)>,
+    profile: AiProfile,
+    goal: NationGoal,
+    rng: &mut impl Rng,
+) -> StrategicAction {
+    let scored: Vec<(StrategicAction, f32)> = options
+        .into_iter()
+        .map(|(action, utility)| {
+            let kind = action.kind();
+            let bonus = if goal.serves(kind) { GOAL_BONUS } else { 0.0 };
+            (action, utility * profile.weight(kind) + bonus)
+        })
+        .collect();
+    let best = scored
+        .iter()
+        .map(|(_, score)| *score)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let odds: Vec<f32> = scored
+        .iter()
+        .map(|(_, score)| ((score - best) / TEMPERATURE).exp())
+        .collect();
+    let mut draw = rng.gen_range(0.0..odds.iter().sum::<f32>().max(f32::MIN_POSITIVE));
+    for ((action, _), odds) in scored.iter().zip(&odds) {
+        if draw < *odds {
+            return *action;
+        }
+        draw -= odds;
+    }
+    scored
+        .last()
+        .map_or(StrategicAction::Hold, |(action, _)| *action)
+}
+
+/// The rival `nation` would most gladly fight, with the utility of doing
+/// so. Strength, grudges and border friction argue for war; diplomacy,
+/// culture, religion, the trade a war would cut and the other side's
+/// arsenal argue against it.
+fn war_target(
+    nation: Nation,
+    all_metrics: &AllNationMetrics,
+    diplo: &DiplomaticRelations,
+    wars: &Wars,
+    arsenals: &NuclearArsenals,
+    routes: &TradeRoutes,
+    borders: &BTreeMap<(Nation, Nation), u32>,
+) -> Option<(Nation, f32)> {
+    let metrics = all_metrics.0.get(&nation)?;
+    if metrics.military <= 1.0 {
+        return None;
+    }
+    let bordering: BTreeSet<Nation> = borders
+        .keys()
+        .filter_map(|(a, b)| match (*a == nation, *b == nation) {
+            (true, _) => Some(*b),
+            (_, true) => Some(*a),
+            _ => None,
+        })
+        .collect();
+    all_metrics
+        .0
+        .iter()
+        .filter(|(rival, m)| {
+            **rival != nation
+                && !m.is_destroyed
+                && m.military > 1.0
+                && !wars.in_truce(nation, **rival)
+                && !diplo.allied(nation, **rival)
+        })
+        .map(|(rival, m)| {
+            let advantage = (metrics.military - m.military) / (metrics.military + m.military);
+            let grudge = (-diplo.opinion(nation, *rival)).max(0.0) / 100.0;
+            let friction = diplo
+                .friction
+                .get(&DiplomaticRelations::pair(nation, *rival))
+                .copied()
+                .unwrap_or(0.0)
+                / 25.0;
+            let peace = ((metrics.diplomacy + m.diplomacy)
+                + (metrics.culture + m.culture) * 0.5
+                + (metrics.religion + m.religion) * 0.5)
+                / PEACE_CEILING;
+            let reach = if bordering.contains(rival) { 1.0 } else { 0.5 };
+            let appetite = (WAR_APPETITE + advantage * 0.5 + grudge * 0.6 + friction * 0.3) * reach
+                - diplo.fear(nation, *rival) / 400.0;
+            let dependence = routes.dependence(nation, *rival, metrics.economy);
+            let utility = appetite
+                * (1.0 - peace * 0.5).max(0.0)
+                * (1.0 - dependence * TRADE_RESTRAINT)
+                * nuclear::deterrence(arsenals, nation, *rival) as f32;
+            (*rival, utility)
+        })
+        .filter(|(_, utility)| *utility > 0.0)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::ActionKind;
+
+    /// How often a scholar picks each of the usual options over many draws,
+    /// with `threat` of fear and every rival far ahead in arms when
+    /// `outgunned`.
+    fn scholar_picks(threat: f32, outgunned: bool) -> BTreeMap<ActionKind, u32> {
+        let military_lag = if outgunned { 0.8 } else { 0.0 };
+        let mut options = vec![(StrategicAction::Hold, 0.3)];
+        options.extend(investments(
+            threat,
+            false,
+            0.5,
+            60.0,
+            [military_lag, 0.0, 0.0],
+        ));
+        let goal = goal_for(AiProfile::Scholar, threat);
+        let mut picks = BTreeMap::new();
+        for tick in 0..1000 {
+            let mut rng = SimulationRng::new(7).stream(RngStream::Strategy, tick);
+            let action = choose(options.clone(), AiProfile::Scholar, goal, &mut rng);
+            *picks.entry(action.kind()).or_insert(0) += 1;
+        }
+        picks
+    }
+
+    fn favourite(picks: &BTreeMap<ActionKind, u32>) -> ActionKind {
+        picks
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(kind, _)| *kind)
+            .expect("at least one pick")
+    }
+
+    #[test]
+    fn choice_follows_the_situation() {
+        let calm = scholar_picks(0.1, false);
+        let threatened = scholar_picks(0.9, true);
+        assert_eq!(favourite(&calm), ActionKind::InvestScience);
+        assert_eq!(favourite(&threatened), ActionKind::InvestMilitary);
+        // Neither situation locks the scholar into a single action.
+        assert!(calm.len() > 1 && threatened.len() > 1);
+    }
+}
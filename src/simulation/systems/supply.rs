@@ -1,71 +1,163 @@
+use std::collections::BTreeMap;
+
 use bevy_ecs::prelude::*;
 
-use crate::simulation::{AllNationMetrics, ClimateState, SupplyState, WorldRichness};
+use crate::simulation::{
+    AllNationMetrics, AxialCoord, ClimateState, Hex, Nation, SupplyState, TradeRoutes,
+    WorldRichness, Yields, route_reach,
+};
+
+/// Reserves a nation keeps at most, in ticks of its own demand.
+const STOCKPILE_TICKS: f32 = 10.0;
+/// Ticks of demand an exporter holds back before it ships a surplus.
+const RESERVE_TICKS: f32 = 3.0;
+/// Share of its surplus a nation ships down one route in a tick.
+const EXPORT_SHARE: f32 = 0.5;
+/// Share of demand on each channel a nation must have on hand before the
+/// shortage bites.
+const TOLERANCE: Yields = Yields {
+    food: 0.8,
+    energy: 0.6,
+    rare: 0.4,
+};
+/// Ticks of output kept in each nation's history.
+const HISTORY_LEN: usize = 256;
 
-/// Aggregates coarse supply chains (food/energy/rare). Penalizes economy/pop when deficits persist.
+/// Runs each nation's supply chain on the food, energy and rare channels:
+/// its land produces, open trade routes carry surpluses to partners that
+/// fall short, the population consumes what it needs from what is on hand
+/// and the rest is stockpiled. A nation is in deficit while what it has on
+/// hand stays below its demand.
 pub fn supply_chain_system(
     mut supply: ResMut<SupplyState>,
-    metrics: Res<AllNationMetrics>,
+    mut metrics: ResMut<AllNationMetrics>,
+    routes: Res<TradeRoutes>,
     climate: Res<ClimateState>,
     richness: Res<WorldRichness>,
+    hexes: Query<(&AxialCoord, &Hex)>,
 ) {
-    let mut food = 0.0;
-    let mut energy = 0.0;
-    let mut rare = 0.0;
-    for (_nation, m) in metrics.0.iter() {
+    // Sum in coordinate order: combat markers reshuffle query order, and
+    // float sums must not depend on it.
+    let mut owned: Vec<(AxialCoord, Nation, Yields)> = hexes
+        .iter()
+        .filter_map(|(coord, hex)| hex.owner.map(|owner| (*coord, owner, hex.output())))
+        .collect();
+    owned.sort_by_key(|(coord, _, _)| (coord.q, coord.r));
+    let mut produced: BTreeMap<Nation, Yields> = BTreeMap::new();
+    for (_, owner, output) in owned {
+        *produced.entry(owner).or_default() += output;
+    }
+
+    // 1. Production. Climate risk reduces effective supply; richness boosts.
+    // Sea level needs no term here: drowned hexes already produce nothing.
+    let climate_drag = ((climate.climate_risk / 100.0) * 0.4).clamp(0.0, 1.0);
+    let boost = (richness.richness * 0.5).clamp(0.0, 0.6);
+    let mut total = Yields::default();
+    let mut population = 0;
+    for (nation, m) in metrics.0.iter_mut() {
+        let chain = supply.nations.entry(*nation).or_default();
+        chain.imports = Yields::default();
+        chain.exports = Yields::default();
         if m.is_destroyed {
+            m.output = Yields::default();
+            chain.produced = Yields::default();
+            chain.stockpile = Yields::default();
             continue;
         }
-        food += m.territory * 0.5 + m.culture * 0.2;
-        energy += m.economy * 0.4 + m.territory * 0.1;
-        rare += m.science * 0.3 + m.economy * 0.2;
+        let raw = produced.get(nation).copied().unwrap_or_default();
+        let scale = (1.0 + boost) * m.economy_modifier;
+        m.output = Yields::new(
+            (raw.food * (1.0 - climate_drag) * scale).max(0.0),
+            (raw.energy * (1.0 - climate_drag * 0.7) * scale).max(0.0),
+            (raw.rare * (1.0 - climate_drag * 0.5) * scale).max(0.0),
+        );
+        chain.produced = m.output;
+        total += m.output;
+        population += m.population;
     }
-    // Climate pressure reduces effective supply; richness boosts.
-    let climate_drag =
-        (climate.sea_level * 0.6 + (climate.climate_risk / 100.0) * 0.4).clamp(0.0, 1.2);
-    let boost = (richness.richness * 0.5).clamp(0.0, 0.6);
-    supply.food = (food * (1.0 - climate_drag) * (1.0 + boost)).max(0.0);
-    supply.energy = (energy * (1.0 - climate_drag * 0.7) * (1.0 + boost)).max(0.0);
-    supply.rare = (rare * (1.0 - climate_drag * 0.5) * (1.0 + boost)).max(0.0);
-
-    // Simple deficits if any channel is below threshold per nation count.
-    let nations = metrics
+    if supply.demand(population).is_none() && population > 0 {
+        supply.ration = total.scaled(1.0 / population as f32);
+    }
+    let demand: BTreeMap<Nation, Yields> = metrics
         .0
-        .values()
-        .filter(|m| !m.is_destroyed)
-        .count()
-        .max(1) as f32;
-    let demand = nations * 80.0;
-    let deficit =
-        supply.food < demand || supply.energy < demand * 0.8 || supply.rare < demand * 0.6;
-    if deficit {
-        supply.deficit_ticks += 1;
-    } else if supply.deficit_ticks > 0 {
-        supply.deficit_ticks -= 1;
+        .iter()
+        .filter(|(_, m)| !m.is_destroyed)
+        .filter_map(|(nation, m)| Some((*nation, supply.demand(m.population)?)))
+        .collect();
+
+    // 2. Trade. Each route carries, both ways, part of what one side holds
+    // beyond its reserve to cover what the other side lacks.
+    for route in &routes.routes {
+        for (from, to) in [(route.a, route.b), (route.b, route.a)] {
+            let (Some(need_from), Some(need_to)) = (demand.get(&from), demand.get(&to)) else {
+                continue;
+            };
+            let (Some(chain_from), Some(chain_to)) =
+                (supply.nations.get(&from), supply.nations.get(&to))
+            else {
+                continue;
+            };
+            let reach = route_reach(route.distance);
+            let surplus = (chain_from.on_hand() - need_from.scaled(RESERVE_TICKS))
+                .zip(Yields::default(), f32::max)
+                .scaled(EXPORT_SHARE * reach);
+            let shortfall = (*need_to - chain_to.on_hand()).zip(Yields::default(), f32::max);
+            let shipped = surplus.zip(shortfall, f32::min);
+            if shipped == Yields::default() {
+                continue;
+            }
+            if let Some(chain) = supply.nations.get_mut(&from) {
+                chain.exports += shipped;
+            }
+            if let Some(chain) = supply.nations.get_mut(&to) {
+                chain.imports += shipped;
+            }
+        }
     }
 
-    let snapshot = (supply.food, supply.energy, supply.rare);
-    supply.history.push(snapshot);
-    if supply.history.len() > 256 {
-        let excess = supply.history.len() - 256;
-        supply.history.drain(0..excess);
+    // 3. Consumption, reserves and deficits.
+    for (nation, chain) in supply.nations.iter_mut() {
+        match demand.get(nation) {
+            Some(need) => {
+                let on_hand = chain.on_hand();
+                chain.consumed = on_hand
+                    .zip(*need, f32::min)
+                    .zip(Yields::default(), f32::max);
+                chain.stockpile = (on_hand - chain.consumed)
+                    .zip(need.scaled(STOCKPILE_TICKS), f32::min)
+                    .zip(Yields::default(), f32::max);
+                let floor = need.zip(TOLERANCE, |need, share| need * share);
+                let short = on_hand.food < floor.food
+                    || on_hand.energy < floor.energy
+                    || on_hand.rare < floor.rare;
+                if short {
+                    chain.deficit_ticks += 1;
+                } else {
+                    chain.deficit_ticks = chain.deficit_ticks.saturating_sub(1);
+                }
+            }
+            None => chain.consumed = Yields::default(),
+        }
+        chain.history.push(chain.produced);
+        if chain.history.len() > HISTORY_LEN {
+            let excess = chain.history.len() - HISTORY_LEN;
+            chain.history.drain(0..excess);
+        }
     }
 }
 
-/// Applies supply deficits to nation metrics and civ state.
+/// Applies each nation's supply deficit to its metrics and civ state.
 pub fn supply_impact_system(
     supply: Res<SupplyState>,
     mut metrics: ResMut<AllNationMetrics>,
     mut civ: ResMut<crate::simulation::AllNationCivState>,
 ) {
-    if supply.deficit_ticks == 0 {
-        return;
-    }
-    let deficit_strength = (supply.deficit_ticks as f32 * 0.05).min(1.0);
     for (nation, m) in metrics.0.iter_mut() {
-        if m.is_destroyed {
+        let deficit_ticks = supply.deficit_ticks(*nation);
+        if m.is_destroyed || deficit_ticks == 0 {
             continue;
         }
+        let deficit_strength = (deficit_ticks as f32 * 0.05).min(1.0);
         m.economy = (m.economy * (1.0 - 0.02 * deficit_strength)).max(5.0);
         m.population = (m.population as f32 * (1.0 - 0.001 * deficit_strength)).max(5_000.0) as u64;
         m.military = (m.military * (1.0 - 0.015 * deficit_strength)).max(5.0);
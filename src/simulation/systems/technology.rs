@@ -1,53 +1,132 @@
 use bevy_ecs::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
 
-use crate::simulation::{AllNationMetrics, WorldEvent, WorldEventLog, WorldMetadata, WorldTime};
+use crate::simulation::{
+    AllNationMetrics, DiplomaticRelations, IdeologyMatrix, Nation, RngStream, SimulationRng, Tech,
+    TechTree, WeaponTier, WorldEvent, WorldEventLog, WorldMetadata, WorldTime,
+};
 
-/// Advances nations through eras and weapon tiers based on accumulated science/culture/military.
+/// Spends each nation's research stock on a target picked from the tech graph,
+/// then derives era, weapon tier and tech modifiers from what is unlocked.
 pub fn technology_system(
     mut all_metrics: ResMut<AllNationMetrics>,
     world_meta: Res<WorldMetadata>,
+    ideology: Res<IdeologyMatrix>,
+    diplomacy: Res<DiplomaticRelations>,
+    rng_source: Res<SimulationRng>,
     mut event_log: ResMut<WorldEventLog>,
     time: Res<WorldTime>,
 ) {
     let tech_tree = &world_meta.tech_tree;
     let (epoch, season) = world_meta.epoch_for_tick(time.tick);
+    let mut rng = rng_source.stream(RngStream::Technology, time.tick);
 
     for (nation, metrics) in all_metrics.0.iter_mut() {
         if metrics.is_destroyed {
             continue;
         }
 
-        metrics.research_stock += metrics.science * 0.45 + metrics.economy * 0.1;
+        metrics.research_stock +=
+            (metrics.science * 0.45 + metrics.economy * 0.1) * metrics.research_modifier;
         metrics.culture_stock += metrics.culture * 0.35 + metrics.diplomacy * 0.05;
 
         metrics.research_stock *= 0.9985;
         metrics.culture_stock *= 0.9985;
 
-        if let Some(next_tier) = tech_tree.next_tier(metrics.era) {
-            let science_ready = metrics.research_stock >= next_tier.science_gate;
-            let culture_ready = metrics.culture_stock >= next_tier.culture_gate;
-            let military_ready = metrics.military >= next_tier.military_gate;
-
-            if science_ready && culture_ready && military_ready {
-                metrics.era = next_tier.era;
-                metrics.weapon_tier = next_tier.weapon_tier;
-                for tech in &next_tier.unlocks {
-                    if !metrics.unlocked_techs.contains(tech) {
-                        metrics.unlocked_techs.push(*tech);
-                    }
-                }
-                metrics.research_stock -= next_tier.science_gate * 0.4;
-                metrics.culture_stock -= next_tier.culture_gate * 0.3;
-
-                event_log.push(WorldEvent::era_shift(
-                    time.tick,
-                    epoch,
-                    season,
-                    *nation,
-                    next_tier.era,
-                    next_tier.weapon_tier,
-                ));
-            }
+        // Zero-cost roots are known from the start rather than researched.
+        let free: Vec<Tech> = tech_tree
+            .available(&metrics.unlocked_techs)
+            .filter(|node| node.cost <= 0.0)
+            .map(|node| node.tech)
+            .collect();
+        metrics.unlocked_techs.extend(free);
+
+        let target_open = metrics.research_target.is_some_and(|tech| {
+            !metrics.unlocked_techs.contains(&tech) && tech_tree.node(tech).is_some()
+        });
+        if !target_open {
+            let leaning = ideology.leaning.get(nation).copied().unwrap_or(50.0);
+            metrics.research_target = choose_target(
+                tech_tree,
+                &metrics.unlocked_techs,
+                leaning,
+                threat_level(&diplomacy, *nation),
+                &mut rng,
+            );
+        }
+
+        if let Some(node) = metrics
+            .research_target
+            .and_then(|tech| tech_tree.node(tech))
+            && metrics.research_stock >= node.cost
+        {
+            metrics.research_stock -= node.cost;
+            metrics.unlocked_techs.push(node.tech);
+            metrics.research_target = None;
+            event_log.push(WorldEvent::tech_discovered(
+                time.tick, epoch, season, *nation, node.tech,
+            ));
+        }
+
+        let effects = tech_tree.effects_for(&metrics.unlocked_techs);
+        metrics.combat_modifier = 1.0 + effects.combat_bonus;
+        metrics.economy_modifier = 1.0 + effects.economy_bonus;
+        metrics.research_modifier = 1.0 + effects.research_bonus;
+        metrics.weapon_tier = effects.weapon_tier.unwrap_or(WeaponTier::KnappedStone);
+
+        let era = tech_tree.era_for(&metrics.unlocked_techs);
+        if era != metrics.era {
+            metrics.era = era;
+            event_log.push(WorldEvent::era_shift(
+                time.tick,
+                epoch,
+                season,
+                *nation,
+                era,
+                metrics.weapon_tier,
+            ));
         }
     }
 }
+
+/// Weighted pick among researchable techs. Threatened nations lean towards
+/// weapons, progressive ones towards economy and science, and cheaper techs
+/// are favoured either way.
+fn choose_target(
+    tech_tree: &TechTree,
+    unlocked: &[Tech],
+    leaning: f32,
+    threat: f32,
+    rng: &mut impl Rng,
+) -> Option<Tech> {
+    let progressive = (leaning / 100.0).clamp(0.0, 1.0);
+    let candidates: Vec<_> = tech_tree.available(unlocked).collect();
+    candidates
+        .choose_weighted(rng, |node| {
+            let military =
+                node.effects.military_weight() * (0.25 + threat + (1.0 - progressive) * 0.5);
+            let civic = node.effects.civic_weight() * (0.25 + progressive);
+            (0.5 + military + civic).max(0.05) / (1.0 + node.cost / 250.0)
+        })
+        .ok()
+        .map(|node| node.tech)
+}
+
+/// Greatest fear plus average hostility toward others, roughly 0 (safe) to
+/// 2.5 (besieged).
+fn threat_level(diplomacy: &DiplomaticRelations, nation: Nation) -> f32 {
+    let fear = diplomacy.greatest_fear(nation);
+    let hostility: Vec<f32> = diplomacy
+        .standing
+        .keys()
+        .filter(|(holder, _)| *holder == nation)
+        .map(|(_, subject)| (-diplomacy.opinion(nation, *subject)).max(0.0) / 100.0)
+        .collect();
+    let hostility = if hostility.is_empty() {
+        0.0
+    } else {
+        hostility.iter().sum::<f32>() / hostility.len() as f32
+    };
+    (fear / 100.0).clamp(0.0, 1.5) + hostility
+}
@@ -1,37 +1,161 @@
-use crate::simulation::{AllNationMetrics, AxialCoord, Hex, HexGrid, pentagon_centers};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use crate::simulation::{
+    AllNationCivState, AllNationMetrics, AxialCoord, Hex, Nation, NationRegistry, RngStream,
+    SimulationRng, WorldTime,
+};
 use bevy_ecs::prelude::*;
+use rand::Rng;
+use rand::prelude::SliceRandom;
+
+/// Chance per tick that a nation settles one unclaimed land hex on its border.
+const SETTLE_CHANCE: f64 = 0.25;
+/// Stability below which exclaves cut off from the core start to break away.
+const SECESSION_STABILITY: f32 = 30.0;
+/// Chance per tick that each exclave hex of an unstable nation secedes.
+const SECESSION_CHANCE: f64 = 0.05;
 
-fn distance(q1: i32, r1: i32, q2: i32, r2: i32) -> i32 {
-    ((q1 - q2).abs() + (q1 + r1 - q2 - r2).abs() + (r1 - r2).abs()) / 2
+/// Each nation's owned land as a percentage of all land on the map.
+pub fn territory_shares<'a>(hexes: impl Iterator<Item = &'a Hex>) -> BTreeMap<Nation, f32> {
+    let mut counts = BTreeMap::new();
+    let mut land = 0usize;
+    for hex in hexes.filter(|hex| !hex.biome.is_water()) {
+        land += 1;
+        if let Some(owner) = hex.owner {
+            *counts.entry(owner).or_insert(0usize) += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(nation, count)| (nation, count as f32 * 100.0 / land.max(1) as f32))
+        .collect()
 }
 
+/// Number of hex edges each pair of nations shares, keyed by the ordered pair.
+pub fn border_lengths<'a>(
+    hexes: impl Iterator<Item = (&'a AxialCoord, &'a Hex)>,
+) -> BTreeMap<(Nation, Nation), u32> {
+    let owners: HashMap<AxialCoord, Nation> = hexes
+        .filter_map(|(coord, hex)| hex.owner.map(|owner| (*coord, owner)))
+        .collect();
+    let mut edges = BTreeMap::new();
+    for (coord, owner) in &owners {
+        for next in coord.neighbors() {
+            if let Some(other) = owners.get(&next)
+                && owner < other
+            {
+                *edges.entry((*owner, *other)).or_insert(0) += 1;
+            }
+        }
+    }
+    edges
+}
+
+/// Hex ownership is persistent: besides conquest in `warfare_system`, it only
+/// changes here, when nations settle unclaimed land on their border or
+/// unstable nations lose exclaves to secession. Every nation's `territory`
+/// is then recounted from the hexes it holds.
 pub fn territory_system(
-    metrics: Res<AllNationMetrics>,
-    grid: Res<HexGrid>,
+    mut metrics: ResMut<AllNationMetrics>,
+    civ_state: Res<AllNationCivState>,
+    nations: Res<NationRegistry>,
+    time: Res<WorldTime>,
+    rng_source: Res<SimulationRng>,
     mut query: Query<(&mut Hex, &AxialCoord)>,
 ) {
-    let capitals = pentagon_centers(grid.radius);
-
-    for (mut hex, coord) in query.iter_mut() {
-        let mut max_influence = -1.0;
-        let mut new_owner = hex.owner;
-
-        for (nation, center) in &capitals {
-            let territory = metrics.0.get(nation).map_or(0.0, |m| m.territory);
-            let dist = distance(coord.q, coord.r, center.q, center.r);
-
-            // Avoid division by zero and handle distance 0 case
-            let influence = if dist == 0 {
-                f32::MAX
-            } else {
-                territory / (dist as f32).powi(2)
-            };
-
-            if influence > max_influence {
-                max_influence = influence;
-                new_owner = *nation;
+    let mut rng = rng_source.stream(RngStream::Territory, time.tick);
+    let mut land: HashMap<AxialCoord, Option<Nation>> = query
+        .iter()
+        .filter(|(hex, _)| !hex.biome.is_water())
+        .map(|(hex, coord)| (*coord, hex.owner))
+        .collect();
+    let mut changes = Vec::new();
+
+    for info in &nations.nations {
+        let nation = info.id;
+        if metrics.0.get(&nation).is_none_or(|m| m.is_destroyed) {
+            continue;
+        }
+        let mut held: Vec<AxialCoord> = land
+            .iter()
+            .filter(|(_, owner)| **owner == Some(nation))
+            .map(|(coord, _)| *coord)
+            .collect();
+        held.sort_by_key(|coord| (coord.q, coord.r));
+
+        if rng.gen_bool(SETTLE_CHANCE) {
+            let mut frontier: Vec<AxialCoord> = held
+                .iter()
+                .flat_map(|coord| coord.neighbors())
+                .filter(|neighbor| land.get(neighbor) == Some(&None))
+                .collect();
+            frontier.sort_by_key(|coord| (coord.q, coord.r));
+            frontier.dedup();
+            if let Some(&coord) = frontier.choose(&mut rng) {
+                land.insert(coord, Some(nation));
+                changes.push((coord, Some(nation)));
+            }
+        }
+
+        let stability = civ_state.0.get(&nation).map_or(100.0, |civ| civ.stability);
+        if stability < SECESSION_STABILITY {
+            let core = connected_core(&held, info.capital);
+            for &coord in held.iter().filter(|coord| !core.contains(coord)) {
+                if rng.gen_bool(SECESSION_CHANCE) {
+                    land.insert(coord, None);
+                    changes.push((coord, None));
+                }
             }
         }
-        hex.owner = new_owner;
     }
+
+    if !changes.is_empty() {
+        // Later changes win, so land that seceded and was resettled this tick
+        // ends up with its settler.
+        let changes: HashMap<AxialCoord, Option<Nation>> = changes.into_iter().collect();
+        for (mut hex, coord) in query.iter_mut() {
+            if let Some(owner) = changes.get(coord) {
+                hex.owner = *owner;
+            }
+        }
+    }
+
+    let shares = territory_shares(query.iter().map(|(hex, _)| hex));
+    for (nation, m) in metrics.0.iter_mut() {
+        m.territory = shares.get(nation).copied().unwrap_or(0.0);
+    }
+}
+
+/// The hexes connected over the nation's own land to its capital, or its
+/// largest connected block once the capital has fallen.
+fn connected_core(held: &[AxialCoord], capital: AxialCoord) -> HashSet<AxialCoord> {
+    let owned: HashSet<AxialCoord> = held.iter().copied().collect();
+    let component = |start: AxialCoord| {
+        let mut reached = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(coord) = queue.pop_front() {
+            for next in coord.neighbors() {
+                if owned.contains(&next) && reached.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        reached
+    };
+    if owned.contains(&capital) {
+        return component(capital);
+    }
+    let mut seen = HashSet::new();
+    let mut largest = HashSet::new();
+    for &coord in held {
+        if seen.contains(&coord) {
+            continue;
+        }
+        let block = component(coord);
+        seen.extend(block.iter().copied());
+        if block.len() > largest.len() {
+            largest = block;
+        }
+    }
+    largest
 }
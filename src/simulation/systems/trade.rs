@@ -0,0 +1,271 @@
+//! International trade: every pair of nations at peace runs a route between
+//! their capitals' markets, shipping each good toward the side where it
+//! fetches more once tariffs and carriage are paid. War severs a route and
+//! sanctions close it; the further apart the capitals, the less it carries.
+
+use std::collections::BTreeMap;
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemParam;
+
+use crate::simulation::{
+    AllNationMetrics, BlocKind, DiplomaticRelations, Markets, Nation, NationRegistry, RouteChange,
+    TradeRoute, TradeRoutes, Wars, WorldBlocs, WorldEvent, WorldEventLog, WorldMetadata, WorldTime,
+    route_reach,
+};
+
+/// Units a route between neighbouring capitals carries per tick.
+const ROUTE_CAPACITY: f32 = 20.0;
+/// Carriage per unit per hex, as a share of the good's base price.
+const CARRIAGE: f32 = 0.01;
+/// Most of a good's stock an exporter ships in one tick.
+const EXPORT_SHARE: f32 = 0.2;
+/// Tariff between nations with no opinion of each other.
+const BASE_TARIFF: f32 = 0.1;
+/// Tariff added at an opinion of -100, and waived at +100.
+const TARIFF_SPREAD: f32 = 0.1;
+/// Economy earned per unit of trade profit.
+const TRADE_INCOME: f32 = 0.05;
+/// Ticks a route may carry nothing before it lapses.
+const IDLE_LIMIT: u32 = 60;
+/// Ticks of lost income each partner pays at once when war cuts a route.
+const SEVERANCE_TICKS: f32 = 15.0;
+
+/// A good worth shipping along a route this tick.
+struct Shipment {
+    name: String,
+    /// Whether it goes from the route's `a` to its `b`.
+    outbound: bool,
+    /// Profit per unit once duty and carriage are paid.
+    margin: f32,
+    /// Units the exporter can spare.
+    offer: f32,
+    /// Exporter's price.
+    price: f32,
+    /// Importer's duty rate.
+    duty: f32,
+}
+
+/// Duty `importer` levies on goods from `exporter`: lower for nations it
+/// likes, halved again for allies.
+fn tariff(diplo: &DiplomaticRelations, importer: Nation, exporter: Nation) -> f32 {
+    let rate = (BASE_TARIFF - diplo.opinion(importer, exporter) / 100.0 * TARIFF_SPREAD)
+        .clamp(0.0, BASE_TARIFF + TARIFF_SPREAD);
+    if diplo.allied(importer, exporter) {
+        rate * 0.5
+    } else {
+        rate
+    }
+}
+
+/// Whether `issuer` embargoes `target`, alone or through a sanctions regime.
+fn embargoes(
+    diplo: &DiplomaticRelations,
+    blocs: &WorldBlocs,
+    issuer: Nation,
+    target: Nation,
+) -> bool {
+    diplo.sanctioning(issuer, target)
+        || blocs
+            .bloc_of(issuer, BlocKind::Sanction)
+            .is_some_and(|bloc| bloc.target == Some(target))
+}
+
+/// The relations and calendar that decide which routes may run.
+#[derive(SystemParam)]
+pub struct TradeInputs<'w> {
+    diplo: Res<'w, DiplomaticRelations>,
+    blocs: Res<'w, WorldBlocs>,
+    wars: Res<'w, Wars>,
+    registry: Res<'w, NationRegistry>,
+    time: Res<'w, WorldTime>,
+    world_meta: Res<'w, WorldMetadata>,
+}
+
+/// Runs one tick of trade on every route: closes routes that war or
+/// sanctions cut, moves goods between the partners' markets, credits each
+/// partner's economy with its share of the profit and its tariffs, and
+/// opens a route wherever a first cargo is worth sending.
+pub fn trade_route_system(
+    mut routes: ResMut<TradeRoutes>,
+    mut markets: ResMut<Markets>,
+    mut all_metrics: ResMut<AllNationMetrics>,
+    mut event_log: ResMut<WorldEventLog>,
+    inputs: TradeInputs,
+) {
+    let TradeInputs {
+        diplo,
+        blocs,
+        wars,
+        registry,
+        time,
+        world_meta,
+    } = inputs;
+    let (epoch, season) = world_meta.epoch_for_tick(time.tick);
+    let alive: Vec<Nation> = registry
+        .ids()
+        .filter(|nation| all_metrics.0.get(nation).is_some_and(|m| !m.is_destroyed))
+        .collect();
+
+    // 1. Close routes to fallen nations, routes war has cut and routes under
+    // embargo. A severed route costs each side a stretch of its income.
+    let mut closed: Vec<(TradeRoute, RouteChange)> = Vec::new();
+    routes.routes.retain(|route| {
+        let change = if !alive.contains(&route.a) || !alive.contains(&route.b) {
+            RouteChange::Lapsed
+        } else if wars.at_war(route.a, route.b) {
+            RouteChange::Severed
+        } else if embargoes(&diplo, &blocs, route.a, route.b)
+            || embargoes(&diplo, &blocs, route.b, route.a)
+        {
+            RouteChange::Embargoed
+        } else if route.idle >= IDLE_LIMIT {
+            RouteChange::Lapsed
+        } else {
+            return true;
+        };
+        closed.push((route.clone(), change));
+        false
+    });
+    for (route, change) in closed {
+        if change == RouteChange::Severed {
+            for nation in [route.a, route.b] {
+                if let Some(m) = all_metrics.0.get_mut(&nation) {
+                    m.economy = (m.economy - route.income(nation) * SEVERANCE_TICKS).max(0.0);
+                }
+            }
+        }
+        event_log.push(WorldEvent::trade_route(
+            time.tick,
+            epoch,
+            season,
+            route.a,
+            route.b,
+            change,
+            route.volume,
+        ));
+    }
+
+    // 2. Trade along every open pair, opening routes that carry a first cargo.
+    for (index, &a) in alive.iter().enumerate() {
+        for &b in &alive[index + 1..] {
+            if wars.at_war(a, b)
+                || embargoes(&diplo, &blocs, a, b)
+                || embargoes(&diplo, &blocs, b, a)
+            {
+                continue;
+            }
+            let (Some(info_a), Some(info_b)) = (registry.get(a), registry.get(b)) else {
+                continue;
+            };
+            let (Some(market_a), Some(market_b)) = (markets.0.get(&a), markets.0.get(&b)) else {
+                continue;
+            };
+            let distance = info_a.capital.distance(info_b.capital).max(1) as u32;
+            let tariff_a = tariff(&diplo, a, b);
+            let tariff_b = tariff(&diplo, b, a);
+
+            // Every good worth shipping, best margin first.
+            let mut cargo: Vec<Shipment> = market_a
+                .goods
+                .iter()
+                .filter_map(|(name, good_a)| {
+                    let good_b = market_b.goods.get(name)?;
+                    let carriage = good_a.item.base_price() * CARRIAGE * distance as f32;
+                    let (outbound, from, to, duty) = if good_a.price < good_b.price {
+                        (true, good_a, good_b, tariff_b)
+                    } else {
+                        (false, good_b, good_a, tariff_a)
+                    };
+                    let margin = to.price - from.price * (1.0 + duty) - carriage;
+                    let offer = from.stock * EXPORT_SHARE * (margin / to.price).min(1.0);
+                    (margin > 0.0 && offer > 0.0).then(|| Shipment {
+                        name: name.clone(),
+                        outbound,
+                        margin,
+                        offer,
+                        price: from.price,
+                        duty,
+                    })
+                })
+                .collect();
+            cargo.sort_by(|x, y| y.margin.total_cmp(&x.margin));
+
+            let mut capacity = ROUTE_CAPACITY * route_reach(distance);
+            let mut goods = BTreeMap::new();
+            let (mut volume, mut income_a, mut income_b) = (0.0, 0.0, 0.0);
+            for Shipment {
+                name,
+                outbound,
+                margin,
+                offer,
+                price,
+                duty,
+            } in cargo
+            {
+                let units = offer.min(capacity);
+                if units <= 0.0 {
+                    break;
+                }
+                capacity -= units;
+                let (from, to) = if outbound { (a, b) } else { (b, a) };
+                if let Some(good) = markets
+                    .0
+                    .get_mut(&from)
+                    .and_then(|m| m.goods.get_mut(&name))
+                {
+                    good.stock -= units;
+                }
+                if let Some(good) = markets.0.get_mut(&to).and_then(|m| m.goods.get_mut(&name)) {
+                    good.stock += units;
+                }
+                // The profit is split; the importer also keeps its duty.
+                let profit = margin * units * 0.5 * TRADE_INCOME;
+                let duties = price * duty * units * TRADE_INCOME;
+                if outbound {
+                    income_a += profit;
+                    income_b += profit + duties;
+                } else {
+                    income_a += profit + duties;
+                    income_b += profit;
+                }
+                volume += price * units;
+                goods.insert(name, if outbound { units } else { -units });
+            }
+            for (nation, income) in [(a, income_a), (b, income_b)] {
+                if let Some(m) = all_metrics.0.get_mut(&nation) {
+                    m.economy += income;
+                }
+            }
+
+            let existing = routes.routes.iter().position(|r| r.a == a && r.b == b);
+            let route = match existing {
+                Some(position) => &mut routes.routes[position],
+                None if volume > 0.0 => {
+                    routes
+                        .routes
+                        .push(TradeRoute::new(a, b, distance, time.tick));
+                    event_log.push(WorldEvent::trade_route(
+                        time.tick,
+                        epoch,
+                        season,
+                        a,
+                        b,
+                        RouteChange::Opened,
+                        volume,
+                    ));
+                    routes.routes.last_mut().expect("route just opened")
+                }
+                None => continue,
+            };
+            route.distance = distance;
+            route.volume = volume;
+            route.goods = goods;
+            route.tariff_a = tariff_a;
+            route.tariff_b = tariff_b;
+            route.income_a = income_a;
+            route.income_b = income_b;
+            route.idle = if volume > 0.0 { 0 } else { route.idle + 1 };
+        }
+    }
+}
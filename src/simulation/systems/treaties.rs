@@ -0,0 +1,230 @@
+//! Peace talks: every war ends in a treaty once one side is beaten or both
+//! are worn out, and the signatories then keep a truce.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::simulation::{
+    AllNationMetrics, AxialCoord, Hex, Nation, RngStream, SimulationRng, Truce, War, WarGoal, Wars,
+    WorldEvent, WorldEventLog, WorldMetadata, WorldTime,
+};
+use bevy_ecs::prelude::*;
+use rand::Rng;
+
+/// How long signatories are barred from declaring war on each other.
+const TRUCE_TICKS: u64 = 80;
+/// War score a side needs to dictate terms rather than settle for white peace.
+const VICTORY_SCORE: f32 = 25.0;
+/// War score at which the losing side capitulates outright.
+const CAPITULATION_SCORE: f32 = 75.0;
+/// Exhaustion above which either side may open talks.
+pub(crate) const TALKS_EXHAUSTION: f32 = 40.0;
+/// Every tick of fighting wears both sides down by this much.
+const EXHAUSTION_PER_TICK: f32 = 0.4;
+
+/// Ages every war, and concludes those that are decided or exhausted.
+///
+/// A side ahead by `VICTORY_SCORE` imposes its goal: annexation keeps the
+/// land its members occupied, reparations hand it back but charge each loser
+/// a share of its economy. Defenders who win always take reparations. Any
+/// other ending is a white peace that restores the pre-war borders. A war
+/// whose leader has sued for peace goes to talks as soon as it is worn down
+/// past `TALKS_EXHAUSTION`.
+pub fn peace_talks_system(
+    mut wars: ResMut<Wars>,
+    mut metrics: ResMut<AllNationMetrics>,
+    mut event_log: ResMut<WorldEventLog>,
+    time: Res<WorldTime>,
+    world_meta: Res<WorldMetadata>,
+    rng_source: Res<SimulationRng>,
+    mut hexes: Query<(&AxialCoord, &mut Hex)>,
+) {
+    // Keyed apart from the other warfare systems sharing this stream.
+    let mut rng = rng_source.keyed_stream(RngStream::Warfare, time.tick, 2);
+    let (epoch, season) = world_meta.epoch_for_tick(time.tick);
+
+    let mut ended = Vec::new();
+    for mut war in std::mem::take(&mut wars.active) {
+        let alive = |nation: &Nation| metrics.0.get(nation).is_some_and(|m| !m.is_destroyed);
+        war.attackers.retain(alive);
+        war.defenders.retain(alive);
+        war.exhaustion = (war.exhaustion + EXHAUSTION_PER_TICK).min(100.0);
+
+        let decided = war.attackers.is_empty()
+            || war.defenders.is_empty()
+            || war.score.abs() >= CAPITULATION_SCORE
+            || war.exhaustion >= 100.0;
+        // An offer of peace is taken up once the other side is tired too;
+        // otherwise it lapses and the fighting goes on.
+        let offered = std::mem::take(&mut war.peace_offered);
+        let talks = war.exhaustion > TALKS_EXHAUSTION
+            && (offered || rng.gen_bool(((war.exhaustion - TALKS_EXHAUSTION) / 300.0) as f64));
+        if decided || talks {
+            ended.push(war);
+        } else {
+            wars.active.push(war);
+        }
+    }
+    if ended.is_empty() {
+        return;
+    }
+
+    let mut owners: HashMap<AxialCoord, Option<Nation>> = hexes
+        .iter()
+        .map(|(coord, hex)| (*coord, hex.owner))
+        .collect();
+    for war in ended {
+        let attackers_won = if war.defenders.is_empty() || war.score >= VICTORY_SCORE {
+            Some(true)
+        } else if war.attackers.is_empty() || war.score <= -VICTORY_SCORE {
+            Some(false)
+        } else {
+            None
+        };
+        let (winners, losers) = match attackers_won {
+            Some(false) => (&war.defenders, &war.attackers),
+            _ => (&war.attackers, &war.defenders),
+        };
+        let goal = match attackers_won {
+            Some(true) => Some(war.goal),
+            Some(false) => Some(WarGoal::Reparations),
+            None => None,
+        };
+
+        // Occupied hexes still held by a combatant either pass to it for
+        // good or go back to their pre-war owner.
+        let mut ceded: BTreeMap<(Nation, Nation), usize> = BTreeMap::new();
+        for &(coord, original) in &war.occupied {
+            let Some(Some(holder)) = owners.get(&coord).copied() else {
+                continue;
+            };
+            if holder == original || war.side(holder).is_none() {
+                continue;
+            }
+            if goal == Some(WarGoal::Annexation) && winners.contains(&holder) {
+                *ceded.entry((original, holder)).or_default() += 1;
+            } else if metrics.0.get(&original).is_some_and(|m| !m.is_destroyed) {
+                owners.insert(coord, Some(original));
+            }
+        }
+
+        match goal {
+            Some(_) if !ceded.is_empty() => {
+                for ((from, to), count) in ceded {
+                    event_log.push(WorldEvent::hexes_ceded(
+                        time.tick, epoch, season, from, to, count,
+                    ));
+                }
+            }
+            // A side wiped out to the last nation leaves no one to pay or
+            // sign, and the war simply ends.
+            Some(_) => {
+                if let Some(&payee) = winners.first() {
+                    for (payer, amount) in pay_reparations(&war, payee, losers, &mut metrics) {
+                        event_log.push(WorldEvent::reparations_paid(
+                            time.tick, epoch, season, payer, payee, amount,
+                        ));
+                    }
+                }
+            }
+            None => {
+                if let (Some(&a), Some(&b)) = (war.attackers.first(), war.defenders.first()) {
+                    event_log.push(WorldEvent::truce_signed(
+                        time.tick,
+                        epoch,
+                        season,
+                        a,
+                        b,
+                        time.tick + TRUCE_TICKS,
+                    ));
+                }
+            }
+        }
+
+        for &a in &war.attackers {
+            for &b in &war.defenders {
+                wars.truces.push(Truce {
+                    a,
+                    b,
+                    until: time.tick + TRUCE_TICKS,
+                });
+            }
+        }
+    }
+
+    for (coord, mut hex) in hexes.iter_mut() {
+        if let Some(owner) = owners.get(coord) {
+            hex.owner = *owner;
+        }
+    }
+}
+
+/// Each loser pays the victors' leader a share of its economy that grows
+/// with how badly the war went. Returns what each payer handed over.
+fn pay_reparations(
+    war: &War,
+    payee: Nation,
+    losers: &[Nation],
+    metrics: &mut AllNationMetrics,
+) -> Vec<(Nation, f32)> {
+    let share = (war.score.abs() / 100.0 * 0.3).clamp(0.05, 0.3);
+    let mut paid = Vec::new();
+    for &payer in losers {
+        let Some(m) = metrics.0.get_mut(&payer) else {
+            continue;
+        };
+        let amount = m.economy * share;
+        m.economy -= amount;
+        if let Some(m) = metrics.0.get_mut(&payee) {
+            m.economy += amount;
+        }
+        paid.push((payer, amount));
+    }
+    paid
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::simulation::{CasusBelli, NationMetrics};
+
+    #[test]
+    fn war_between_destroyed_nations_ends_quietly() {
+        let (a, b) = (Nation(0), Nation(1));
+        let destroyed = NationMetrics {
+            is_destroyed: true,
+            ..NationMetrics::default()
+        };
+        let mut world = World::new();
+        world.insert_resource(AllNationMetrics(BTreeMap::from([
+            (a, destroyed.clone()),
+            (b, destroyed),
+        ])));
+        world.insert_resource(Wars {
+            active: vec![War {
+                id: 1,
+                attackers: vec![a],
+                defenders: vec![b],
+                casus_belli: CasusBelli::Conquest,
+                goal: WarGoal::Reparations,
+                started: 0,
+                score: 0.0,
+                exhaustion: 0.0,
+                occupied: Vec::new(),
+                nuclear: true,
+                peace_offered: false,
+            }],
+            ..Wars::default()
+        });
+        world.insert_resource(WorldEventLog::default());
+        world.insert_resource(WorldTime::default());
+        world.insert_resource(WorldMetadata::default());
+        world.insert_resource(SimulationRng::new(7));
+
+        world.run_system_once(peace_talks_system);
+
+        assert!(world.resource::<Wars>().active.is_empty());
+        assert!(world.resource::<WorldEventLog>().snapshot().is_empty());
+    }
+}
@@ -49,27 +49,9 @@ pub fn science_victory_system(
 
                 // Bloc bonus/penalty
                 let bloc_bonus = blocs
-                    .blocs
-                    .get(&crate::simulation::BlocKind::ResearchPact)
-                    .and_then(|bloc| {
-                        if bloc.members.contains(nation) {
-                            Some(bloc.strength * 0.3)
-                        } else {
-                            None
-                        }
-                    })
-                    .unwrap_or(0.0);
-                let embargo_drag = blocs
-                    .blocs
-                    .get(&crate::simulation::BlocKind::Sanction)
-                    .and_then(|bloc| {
-                        if bloc.members.contains(nation) {
-                            Some(bloc.strength * 0.2)
-                        } else {
-                            None
-                        }
-                    })
-                    .unwrap_or(0.0);
+                    .bloc_of(*nation, crate::simulation::BlocKind::ResearchPact)
+                    .map_or(0.0, |bloc| bloc.strength * 0.3);
+                let embargo_drag = blocs.embargo_on(*nation) * 0.2;
 
                 let momentum = (metrics.science * 0.042
                     + metrics.economy * 0.012
@@ -128,7 +110,7 @@ pub fn science_victory_system(
         }
     } else if matches!(tracker.space_stage, crate::simulation::SpaceStage::Mars) {
         let (epoch, season) = world_meta.epoch_for_tick(time.tick);
-        let leader = tracker.winner.unwrap_or(Nation::Tera);
+        let leader = tracker.winner.unwrap_or(Nation(0));
         let base = tracker.mars_progress;
         let growth = 0.4 + (base / tracker.mars_goal) * 0.9;
         tracker.mars_progress = (base + growth).min(tracker.mars_goal);
@@ -147,7 +129,7 @@ pub fn science_victory_system(
         }
     } else if matches!(tracker.space_stage, crate::simulation::SpaceStage::Jovian) {
         let (epoch, season) = world_meta.epoch_for_tick(time.tick);
-        let leader = tracker.winner.unwrap_or(Nation::Tera);
+        let leader = tracker.winner.unwrap_or(Nation(0));
         let base = tracker.jovian_progress;
         let growth = 0.35 + (base / tracker.jovian_goal) * 0.8;
         tracker.jovian_progress = (base + growth).min(tracker.jovian_goal);
@@ -168,7 +150,7 @@ pub fn science_victory_system(
     } else if tracker.interstellar_mode {
         // Phase 3: Interstellar expansion
         let (epoch, season) = world_meta.epoch_for_tick(time.tick);
-        let leader = tracker.winner.unwrap_or(Nation::Tera);
+        let leader = tracker.winner.unwrap_or(Nation(0));
         let base = tracker.interstellar_progress;
         let growth = 0.6 + (base / tracker.interstellar_goal) * 0.8;
         tracker.interstellar_progress = (base + growth).min(tracker.interstellar_goal);
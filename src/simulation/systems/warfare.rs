@@ -1,18 +1,15 @@
+use crate::simulation::systems::{blocs, border_lengths, nuclear};
 use crate::simulation::{
-    AllNationCivState, AllNationMetrics, DiplomaticRelations, Hex, Nation, WeaponTier, WorldTime,
+    AllNationCivState, AllNationMetrics, Army, CasusBelli, DISBAND_STRENGTH, DiplomaticRelations,
+    Hex, MemoryKind, Nation, NuclearArsenals, RngStream, SimulationRng, War, Wars, WorldBlocs,
+    WorldEvent, WorldTime,
     components::{Combatants, InCombat},
     grid::AxialCoord,
 };
 use bevy_ecs::prelude::*;
-use rand::prelude::SliceRandom;
-use rand::rngs::SmallRng;
-use rand::{Rng, SeedableRng};
-use std::collections::{HashMap, HashSet};
-
-struct BattleRequest {
-    nation_a: Nation,
-    nation_b: Nation,
-}
+use bevy_ecs::system::SystemParam;
+use rand::Rng;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 fn apply_war_science_penalty(metrics: &mut crate::simulation::NationMetrics, casualties: u64) {
     if metrics.population == 0 {
@@ -30,14 +27,6 @@ fn apply_war_science_penalty(metrics: &mut crate::simulation::NationMetrics, cas
     metrics.culture = (metrics.culture - casualty_ratio * 6.0).max(0.0);
 }
 
-fn ordered_pair(a: Nation, b: Nation) -> (Nation, Nation) {
-    if (a as u32) < (b as u32) {
-        (a, b)
-    } else {
-        (b, a)
-    }
-}
-
 // System to clean up finished combat encounters
 pub fn combat_cleanup_system(mut commands: Commands, mut query: Query<(Entity, &mut InCombat)>) {
     for (entity, mut in_combat) in query.iter_mut() {
@@ -49,270 +38,415 @@ pub fn combat_cleanup_system(mut commands: Commands, mut query: Query<(Entity, &
     }
 }
 
+/// What the warfare system reads, but never changes, as it opens wars
+/// and sends armies out.
+#[derive(SystemParam)]
+pub struct WarfareInputs<'w, 's> {
+    all_metrics: Res<'w, AllNationMetrics>,
+    time: Res<'w, WorldTime>,
+    world_meta: Res<'w, crate::simulation::WorldMetadata>,
+    science_victory: Res<'w, crate::simulation::ScienceVictory>,
+    hex_query: Query<'w, 's, (&'static AxialCoord, &'static Hex)>,
+}
+
+/// Carries out the declarations nations' strategies queued this tick,
+/// calling the defender's treaty partners to its side, and sends every idle
+/// army of a warring nation on campaign against the enemy its nation
+/// resents most. A partner that stays out, or an attacker that turns on its
+/// own partner, breaks the treaty.
 pub fn warfare_system(
-    mut commands: Commands,
-    mut all_metrics: ResMut<AllNationMetrics>,
-    mut civ_state: ResMut<AllNationCivState>,
-    mut blasts: ResMut<crate::simulation::NuclearBlasts>,
-    time: Res<WorldTime>,
     mut event_log: ResMut<crate::simulation::WorldEventLog>,
-    world_meta: Res<crate::simulation::WorldMetadata>,
-    science_victory: Res<crate::simulation::ScienceVictory>,
-    diplo: Res<DiplomaticRelations>,
-    hex_query: Query<(Entity, &Hex, &AxialCoord)>,
+    mut diplo: ResMut<DiplomaticRelations>,
+    mut wars: ResMut<Wars>,
+    mut world_blocs: ResMut<WorldBlocs>,
+    mut armies: Query<&mut Army>,
+    inputs: WarfareInputs,
 ) {
+    let WarfareInputs {
+        all_metrics,
+        time,
+        world_meta,
+        science_victory,
+        hex_query,
+    } = inputs;
+    let declarations = std::mem::take(&mut wars.declarations);
     if science_victory.finished {
         return;
     }
-    let mut rng = SmallRng::seed_from_u64(time.tick.wrapping_mul(257));
-    let mut battle_requests = Vec::new();
-    let mut seen_pairs = HashSet::new();
-
-    // 1. Identify potential battles
-    let nations: Vec<Nation> = all_metrics.0.keys().cloned().collect();
-    for i in 0..nations.len() {
-        for j in (i + 1)..nations.len() {
-            let nation_a_key = nations[i];
-            let nation_b_key = nations[j];
+    let (epoch, season) = world_meta.epoch_for_tick(time.tick);
+    wars.truces.retain(|truce| truce.until > time.tick);
+    let alive = |nation: &Nation| all_metrics.0.get(nation).is_some_and(|m| !m.is_destroyed);
 
-            let (metrics_a, metrics_b) = (
-                all_metrics.0.get(&nation_a_key).unwrap(),
-                all_metrics.0.get(&nation_b_key).unwrap(),
-            );
+    // 1. Declarations, unless the pair came to blows or terms meanwhile.
+    let borders = if declarations.is_empty() {
+        BTreeMap::new()
+    } else {
+        border_lengths(hex_query.iter())
+    };
+    for (attacker, defender) in declarations {
+        if !alive(&attacker)
+            || !alive(&defender)
+            || wars.at_war(attacker, defender)
+            || wars.in_truce(attacker, defender)
+        {
+            continue;
+        }
+        let avenging = diplo.memories.iter().any(|memory| {
+            memory.holder == attacker
+                && memory.subject == defender
+                && matches!(memory.kind, MemoryKind::Invaded | MemoryKind::Betrayed)
+        });
+        let casus_belli = if avenging {
+            CasusBelli::Revenge
+        } else if diplo.sanctioning(defender, attacker) {
+            CasusBelli::Sanctions
+        } else if borders.contains_key(&DiplomaticRelations::pair(attacker, defender)) {
+            CasusBelli::BorderDispute
+        } else {
+            CasusBelli::Conquest
+        };
 
-            if metrics_a.is_destroyed
-                || metrics_b.is_destroyed
-                || metrics_a.military <= 1.0
-                || metrics_b.military <= 1.0
-            {
+        let mut breaches = Vec::new();
+        let mut defenders = vec![defender];
+        for (partner, treaty) in world_blocs.treaty_partners(defender) {
+            if partner == attacker {
+                breaches.push((attacker, treaty));
+            } else if !alive(&partner) {
                 continue;
+            } else if wars.in_truce(partner, attacker) {
+                breaches.push((partner, treaty));
+            } else {
+                defenders.push(partner);
             }
-
-            // --- War Prevention Logic ---
-            // Higher diplomacy, culture, and religion reduce the chance of war.
-            let peace_factor = (metrics_a.diplomacy + metrics_b.diplomacy)
-                + (metrics_a.culture + metrics_b.culture) * 0.5
-                + (metrics_a.religion + metrics_b.religion) * 0.5;
-
-            // Base probability of war is 20%, reduced by the peace factor.
-            let war_prob = (0.2 - peace_factor * 0.001).max(0.01);
-
-            if rng.gen_bool(war_prob as f64) {
-                let pair = ordered_pair(nation_a_key, nation_b_key);
-                if seen_pairs.insert(pair) {
-                    battle_requests.push(BattleRequest {
-                        nation_a: nation_a_key,
-                        nation_b: nation_b_key,
-                    });
-                }
+        }
+        let war = War {
+            id: wars.next_id,
+            attackers: vec![attacker],
+            defenders,
+            casus_belli,
+            goal: casus_belli.goal(),
+            started: time.tick,
+            score: 0.0,
+            exhaustion: 0.0,
+            occupied: Vec::new(),
+            nuclear: false,
+            peace_offered: false,
+        };
+        event_log.push(WorldEvent::war_declared(time.tick, epoch, season, &war));
+        for member in &war.defenders {
+            diplo.remember(*member, attacker, MemoryKind::Invaded, time.tick);
+            for comrade in &war.defenders {
+                diplo.remember(*member, *comrade, MemoryKind::AlliedInWar, time.tick);
+            }
+        }
+        wars.next_id += 1;
+        wars.active.push(war);
+        for (breaker, treaty) in breaches {
+            if let Some(name) =
+                blocs::break_treaty(&mut world_blocs, &mut diplo, breaker, treaty, time.tick)
+            {
+                event_log.push(WorldEvent::treaty_broken(
+                    time.tick, epoch, season, breaker, defender, name,
+                ));
             }
         }
     }
 
-    // Alliance auto-join: allied nations can be pulled into existing battles.
-    let alliances = &diplo.alliances;
-    let mut extra_requests = Vec::new();
-    for req in &battle_requests {
-        for (ally_a, ally_b) in alliances {
-            if req.nation_a == *ally_a && req.nation_b != *ally_b {
-                let pair = ordered_pair(*ally_b, req.nation_b);
-                if seen_pairs.insert(pair) {
-                    extra_requests.push(BattleRequest {
-                        nation_a: *ally_b,
-                        nation_b: req.nation_b,
-                    });
-                }
-            } else if req.nation_a == *ally_b && req.nation_b != *ally_a {
-                let pair = ordered_pair(*ally_a, req.nation_b);
-                if seen_pairs.insert(pair) {
-                    extra_requests.push(BattleRequest {
-                        nation_a: *ally_a,
-                        nation_b: req.nation_b,
-                    });
-                }
-            }
-            if req.nation_b == *ally_a && req.nation_a != *ally_b {
-                let pair = ordered_pair(req.nation_a, *ally_b);
-                if seen_pairs.insert(pair) {
-                    extra_requests.push(BattleRequest {
-                        nation_a: req.nation_a,
-                        nation_b: *ally_b,
-                    });
-                }
-            } else if req.nation_b == *ally_b && req.nation_a != *ally_a {
-                let pair = ordered_pair(req.nation_a, *ally_a);
-                if seen_pairs.insert(pair) {
-                    extra_requests.push(BattleRequest {
-                        nation_a: req.nation_a,
-                        nation_b: *ally_a,
-                    });
-                }
-            }
+    // 2. Send every idle army that is fit to march against the enemy still
+    // standing that its nation thinks least of.
+    for mut army in armies.iter_mut() {
+        if army.enemy.is_some() || army.supply < 0.5 {
+            continue;
         }
+        army.enemy = wars
+            .active
+            .iter()
+            .flat_map(|war| war.enemies_of(army.nation).iter().copied())
+            .filter(|enemy| alive(enemy))
+            .min_by(|a, b| {
+                diplo
+                    .opinion(army.nation, *a)
+                    .total_cmp(&diplo.opinion(army.nation, *b))
+            });
     }
-    battle_requests.extend(extra_requests);
+}
 
+/// Resolves clashes between hostile armies that stand in or next to the same
+/// hex, then lets unopposed armies occupy the enemy hex they stand on. Both
+/// mark the hex as a front and move its ownership to the victor.
+pub fn battle_system(
+    mut commands: Commands,
+    mut all_metrics: ResMut<AllNationMetrics>,
+    mut civ_state: ResMut<AllNationCivState>,
+    mut blasts: ResMut<crate::simulation::NuclearBlasts>,
+    time: Res<WorldTime>,
+    mut event_log: ResMut<crate::simulation::WorldEventLog>,
+    world_meta: Res<crate::simulation::WorldMetadata>,
+    science_victory: Res<crate::simulation::ScienceVictory>,
+    rng_source: Res<SimulationRng>,
+    mut wars: ResMut<Wars>,
+    mut arsenals: ResMut<NuclearArsenals>,
+    mut hex_query: Query<(Entity, &mut Hex, &AxialCoord), Without<Army>>,
+    mut army_query: Query<(Entity, &mut Army, &AxialCoord), Without<Hex>>,
+) {
+    if science_victory.finished {
+        return;
+    }
+    // Keyed apart from `warfare_system`, which draws from the same stream.
+    let mut rng = rng_source.keyed_stream(RngStream::Warfare, time.tick, 1);
     let (epoch, season) = world_meta.epoch_for_tick(time.tick);
-    let nation_hexes: HashMap<Nation, HashSet<AxialCoord>> = {
-        let mut map: HashMap<Nation, HashSet<AxialCoord>> = HashMap::new();
-        for (_, hex, coord) in hex_query.iter() {
-            map.entry(hex.owner).or_default().insert(*coord);
+
+    let mut entities: HashMap<AxialCoord, Entity> = HashMap::new();
+    let mut owners: HashMap<AxialCoord, Nation> = HashMap::new();
+    for (entity, hex, coord) in hex_query.iter() {
+        entities.insert(*coord, entity);
+        if let Some(owner) = hex.owner {
+            owners.insert(*coord, owner);
+        }
+    }
+    let mut captured: HashMap<AxialCoord, Nation> = HashMap::new();
+    let mut mark_front = |coord: AxialCoord, winner: Nation, loser: Nation| {
+        if let Some(&entity) = entities.get(&coord) {
+            commands.entity(entity).insert((
+                InCombat { ticks_remaining: 5 },
+                Combatants {
+                    nation_a: winner,
+                    nation_b: loser,
+                },
+            ));
         }
-        map
     };
 
-    // 2. Process battles
-    for request in battle_requests {
-        let (winner, loser) = {
-            let metrics_a = all_metrics.0.get(&request.nation_a).unwrap();
-            let metrics_b = all_metrics.0.get(&request.nation_b).unwrap();
-
-            // --- Battle Outcome Logic ---
-            // Science acts as a multiplier for military strength.
-            let military_a_effective = metrics_a.military
-                * (1.0 + metrics_a.science / 100.0)
-                * metrics_a.weapon_tier.combat_multiplier();
-            let military_b_effective = metrics_b.military
-                * (1.0 + metrics_b.science / 100.0)
-                * metrics_b.weapon_tier.combat_multiplier();
-
-            let roll_a = rng.gen_range(0.0..1.0) * military_a_effective;
-            let roll_b = rng.gen_range(0.0..1.0) * military_b_effective;
+    let mut order: Vec<(u64, Entity)> = army_query
+        .iter()
+        .map(|(entity, army, _)| (army.id, entity))
+        .collect();
+    order.sort();
 
-            if roll_a > roll_b {
-                (request.nation_a, request.nation_b)
-            } else {
-                (request.nation_b, request.nation_a)
+    // 1. Battles: each army fights at most once per tick.
+    let mut engaged: HashSet<Entity> = HashSet::new();
+    for (i, &(_, first)) in order.iter().enumerate() {
+        for &(_, second) in &order[i + 1..] {
+            if engaged.contains(&first) || engaged.contains(&second) {
+                continue;
             }
-        };
+            let Ok([(_, army_a, coord_a), (_, army_b, coord_b)]) =
+                army_query.get_many_mut([first, second])
+            else {
+                continue;
+            };
+            if !wars.at_war(army_a.nation, army_b.nation) || coord_a.distance(*coord_b) > 1 {
+                continue;
+            }
+            engaged.insert(first);
+            engaged.insert(second);
 
-        // Update metrics
-        let territory_change = 0.5;
-        let military_loss = 2.0;
-        let base_force = {
-            let metrics_a = all_metrics.0.get(&request.nation_a).unwrap();
-            let metrics_b = all_metrics.0.get(&request.nation_b).unwrap();
-            (metrics_a.military + metrics_b.military).max(1.0)
-        };
-        let mut raw_casualties = (base_force * rng.gen_range(6000.0..12000.0)) as u64;
-        let (metrics_a, metrics_b) = (
-            all_metrics.0.get(&request.nation_a).unwrap(),
-            all_metrics.0.get(&request.nation_b).unwrap(),
-        );
-        let nuclear_a = matches!(metrics_a.weapon_tier, WeaponTier::NuclearArsenal);
-        let nuclear_b = matches!(metrics_b.weapon_tier, WeaponTier::NuclearArsenal);
-        let late_game = time.tick > 800 || (nuclear_a && nuclear_b);
-        let nuke_probability = if late_game { 0.9 } else { 0.6 };
-        let nuclear = if nuclear_a || nuclear_b {
-            // Escalate to nuclear more often late game; if both have nukes, high mutual retaliation chance.
-            let escalate = rng.gen_bool(nuke_probability);
-            if escalate {
-                let nuke_count = if late_game {
-                    rng.gen_range(200..1001)
-                } else {
-                    rng.gen_range(3..13)
-                };
-                let mutual = nuclear_a && nuclear_b && rng.gen_bool(0.8);
-                let multiplier = if mutual {
-                    5.0 * nuke_count as f32
+            // --- Battle Outcome Logic ---
+            // Science, weapons and combat techs multiply an army's strength,
+            // hunger and low spirits sap it, and defenders hold their ground.
+            let effective = |army: &Army, coord: &AxialCoord| {
+                let m = &all_metrics.0[&army.nation];
+                let defending = if owners.get(coord) == Some(&army.nation) {
+                    1.2
                 } else {
-                    5.0 * (nuke_count as f32 * 0.5)
+                    1.0
                 };
-                raw_casualties = (raw_casualties as f32 * multiplier) as u64;
+                army.strength
+                    * (1.0 + m.science / 100.0)
+                    * m.weapon_tier.combat_multiplier()
+                    * m.combat_modifier
+                    * (0.5 + 0.5 * army.supply)
+                    * army.morale.max(0.1)
+                    * defending
+            };
+            let roll_a = rng.gen_range(0.0..1.0) * effective(&army_a, coord_a);
+            let roll_b = rng.gen_range(0.0..1.0) * effective(&army_b, coord_b);
+            let (mut victor, victor_coord, mut beaten, beaten_coord) = if roll_a > roll_b {
+                (army_a, *coord_a, army_b, *coord_b)
+            } else {
+                (army_b, *coord_b, army_a, *coord_a)
+            };
+            let (winner, loser) = (victor.nation, beaten.nation);
+
+            // The battle is fought where the losing army stood; if that was
+            // its own land, the victor takes it.
+            let site = beaten_coord;
+            let hexes_captured = if owners.get(&site) == Some(&loser) {
+                owners.insert(site, winner);
+                captured.insert(site, winner);
+                1
+            } else {
+                0
+            };
+            mark_front(site, winner, loser);
+            if victor_coord != site {
+                mark_front(victor_coord, winner, loser);
             }
-            escalate
-        } else {
-            false
-        };
-        let total_casualties = raw_casualties.clamp(15_000, 2_000_000);
-        let winner_casualties = (total_casualties as f32 * 0.35) as u64;
-        let loser_casualties = total_casualties.saturating_sub(winner_casualties);
 
-        if let Some(winner_metrics) = all_metrics.0.get_mut(&winner) {
-            winner_metrics.territory += territory_change;
-            winner_metrics.military -= military_loss;
-            winner_metrics.territory = winner_metrics.territory.max(0.0);
-            winner_metrics.military = winner_metrics.military.max(0.0);
-            apply_war_science_penalty(winner_metrics, winner_casualties);
-            winner_metrics.population = winner_metrics.population.saturating_sub(winner_casualties);
-            // Post-war rebuilding boosts diplomacy/culture for victors that avoid annihilation
-            winner_metrics.diplomacy =
-                (winner_metrics.diplomacy + (territory_change * 0.8)).min(100.0);
-            winner_metrics.culture = (winner_metrics.culture + 0.6).min(100.0);
-        }
+            let military_loss = 2.0;
+            let raw_casualties = ((victor.strength + beaten.strength).max(1.0)
+                * rng.gen_range(6000.0..12000.0)) as u64;
+            victor.strength *= 0.85;
+            victor.morale = (victor.morale + 0.1).min(1.0);
+            beaten.strength *= 0.55;
+            beaten.morale = (beaten.morale - 0.25).max(0.0);
+            // The beaten army falls back home to regroup.
+            beaten.enemy = None;
+            beaten.objective = None;
+            beaten.path.clear();
 
-        if let Some(loser_metrics) = all_metrics.0.get_mut(&loser) {
-            loser_metrics.territory -= territory_change;
-            loser_metrics.military -= military_loss;
-            loser_metrics.territory = loser_metrics.territory.max(0.0);
-            loser_metrics.military = loser_metrics.military.max(0.0);
-            apply_war_science_penalty(loser_metrics, loser_casualties);
-            loser_metrics.population = loser_metrics.population.saturating_sub(loser_casualties);
+            let total_casualties = raw_casualties.clamp(15_000, 2_000_000);
+            let winner_casualties = (total_casualties as f32 * 0.35) as u64;
+            let loser_casualties = total_casualties.saturating_sub(winner_casualties);
 
-            if loser_metrics.territory <= 0.0 {
-                loser_metrics.is_destroyed = true;
-                loser_metrics.population = 0;
-                if let Some(civ) = civ_state.0.get_mut(&loser) {
-                    civ.cities = 0;
-                    civ.production = 0.0;
-                    civ.happiness = 0.0;
-                    civ.stability = 0.0;
-                }
+            if let Some(winner_metrics) = all_metrics.0.get_mut(&winner) {
+                winner_metrics.military -= military_loss;
+                winner_metrics.military = winner_metrics.military.max(0.0);
+                apply_war_science_penalty(winner_metrics, winner_casualties);
+                winner_metrics.population =
+                    winner_metrics.population.saturating_sub(winner_casualties);
+                // Post-war rebuilding boosts diplomacy/culture for victors that avoid annihilation
+                winner_metrics.diplomacy = (winner_metrics.diplomacy + 0.4).min(100.0);
+                winner_metrics.culture = (winner_metrics.culture + 0.6).min(100.0);
+            }
+            if let Some(loser_metrics) = all_metrics.0.get_mut(&loser) {
+                loser_metrics.military -= military_loss;
+                loser_metrics.military = loser_metrics.military.max(0.0);
+                apply_war_science_penalty(loser_metrics, loser_casualties);
+                loser_metrics.population =
+                    loser_metrics.population.saturating_sub(loser_casualties);
             }
-        }
 
-        // Log the event
-        event_log.push(crate::simulation::WorldEvent::warfare(
-            time.tick,
-            epoch,
-            season,
-            winner,
-            loser,
-            territory_change,
-            total_casualties,
-            nuclear,
-        ));
+            let Some(war) = wars.between_mut(winner, loser) else {
+                continue;
+            };
+            war.credit(winner, 3.0 + 4.0 * hexes_captured as f32);
+            war.exhaustion =
+                (war.exhaustion + 1.5 + total_casualties as f32 / 200_000.0).min(100.0);
+            if hexes_captured > 0 {
+                war.record_capture(site, loser, winner);
+            }
 
-        if nuclear {
-            if let Some((_, _, center)) = hex_query.iter().find(|(_, h, _)| h.owner == loser) {
-                let radius = 2;
-                let duration = 8;
-                let loser_hexes = nation_hexes.get(&loser).cloned().unwrap_or_default();
-                let mut targets: Vec<AxialCoord> = loser_hexes.into_iter().collect();
-                targets.shuffle(&mut rng);
-                for blast_center in targets.into_iter().take(20).chain(std::iter::once(*center)) {
-                    for dq in -radius..=radius {
-                        for dr in (-radius).max(-dq - radius)..=radius.min(-dq + radius) {
-                            let target = AxialCoord::new(blast_center.q + dq, blast_center.r + dr);
-                            blasts.0.insert(target, duration);
-                        }
+            // The beaten side may reach for its arsenal, and a struck nation
+            // answers with a second strike if it has warheads left.
+            let mut strikes = Vec::new();
+            if nuclear::launches(&arsenals, loser, winner, war.nuclear, &mut rng) {
+                for (attacker, target, retaliation) in
+                    [(loser, winner, false), (winner, loser, true)]
+                {
+                    let salvo = nuclear::launch_salvo(
+                        &mut arsenals,
+                        &mut blasts,
+                        &mut all_metrics,
+                        &owners,
+                        attacker,
+                        target,
+                        &mut rng,
+                    );
+                    if salvo.warheads == 0 {
+                        break;
                     }
+                    strikes.push((attacker, target, salvo, retaliation));
                 }
             }
+            let nuclear = !strikes.is_empty();
+            if nuclear {
+                war.nuclear = true;
+                war.exhaustion = (war.exhaustion + 10.0 * strikes.len() as f32).min(100.0);
+            }
+            event_log.push(crate::simulation::WorldEvent::warfare(
+                time.tick,
+                epoch,
+                season,
+                war.id,
+                winner,
+                loser,
+                hexes_captured,
+                total_casualties,
+                nuclear,
+            ));
+            for (attacker, target, salvo, retaliation) in strikes {
+                event_log.push(crate::simulation::WorldEvent::nuclear_strike(
+                    time.tick,
+                    epoch,
+                    season,
+                    attacker,
+                    target,
+                    salvo,
+                    retaliation,
+                ));
+            }
         }
+    }
 
-        // 3. Find border hexes and mark them as in combat
-        let mut border_hex_entities = HashSet::new();
-        let loser_hexes = nation_hexes.get(&loser).cloned().unwrap_or_default();
+    // 2. Occupation: an army on campaign takes the enemy hex it stands on
+    // unless a hostile army is there to contest it.
+    for &(_, entity) in &order {
+        if engaged.contains(&entity) {
+            continue;
+        }
+        let Ok((_, army, coord)) = army_query.get(entity) else {
+            continue;
+        };
+        let Some(enemy) = army.enemy else {
+            continue;
+        };
+        if owners.get(coord) != Some(&enemy) {
+            continue;
+        }
+        let contested = army_query.iter().any(|(_, other, other_coord)| {
+            wars.at_war(army.nation, other.nation) && other_coord.distance(*coord) <= 1
+        });
+        if contested {
+            continue;
+        }
+        let Some(war) = wars.between_mut(army.nation, enemy) else {
+            continue;
+        };
+        war.credit(army.nation, 1.0);
+        war.exhaustion = (war.exhaustion + 0.2).min(100.0);
+        war.record_capture(*coord, enemy, army.nation);
+        owners.insert(*coord, army.nation);
+        captured.insert(*coord, army.nation);
+        mark_front(*coord, army.nation, enemy);
+    }
 
-        for (entity, hex, coord) in hex_query.iter() {
-            if hex.owner == winner {
-                for neighbor_coord in coord.neighbors() {
-                    if loser_hexes.contains(&neighbor_coord) {
-                        border_hex_entities.insert(entity);
-                    }
-                }
-            }
+    // 3. Worn-out armies disband, and nations left without land fall.
+    for (entity, army, _) in army_query.iter() {
+        if army.strength < DISBAND_STRENGTH {
+            commands.entity(entity).despawn();
+        }
+    }
+    let mut losers: Vec<Nation> = captured
+        .keys()
+        .filter_map(|coord| {
+            hex_query
+                .get(entities[coord])
+                .ok()
+                .and_then(|(_, hex, _)| hex.owner)
+        })
+        .collect();
+    losers.sort();
+    losers.dedup();
+    for loser in losers {
+        if owners.values().any(|owner| *owner == loser) {
+            continue;
         }
+        if let Some(loser_metrics) = all_metrics.0.get_mut(&loser) {
+            loser_metrics.is_destroyed = true;
+            loser_metrics.population = 0;
+        }
+        if let Some(civ) = civ_state.0.get_mut(&loser) {
+            civ.cities = 0;
+            civ.production = 0.0;
+            civ.happiness = 0.0;
+            civ.stability = 0.0;
+        }
+    }
 
-        for entity in border_hex_entities {
-            commands.entity(entity).insert((
-                InCombat { ticks_remaining: 5 },
-                Combatants {
-                    nation_a: winner,
-                    nation_b: loser,
-                },
-            ));
+    for (coord, winner) in captured {
+        if let Ok((_, mut hex, _)) = hex_query.get_mut(entities[&coord]) {
+            hex.owner = Some(winner);
         }
     }
 }
@@ -1,6 +1,9 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Eras are ordered; a nation's era is the latest one among its unlocked techs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Era {
     Dawn,
     Ancient,
@@ -23,21 +26,10 @@ impl Era {
             Era::Nuclear => "Nuclear/Future",
         }
     }
-
-    pub fn next(&self) -> Option<Era> {
-        match self {
-            Era::Dawn => Some(Era::Ancient),
-            Era::Ancient => Some(Era::Classical),
-            Era::Classical => Some(Era::Medieval),
-            Era::Medieval => Some(Era::Industrial),
-            Era::Industrial => Some(Era::Modern),
-            Era::Modern => Some(Era::Nuclear),
-            Era::Nuclear => None,
-        }
-    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Ordered from weakest to strongest; a nation fields the best tier its techs unlock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum WeaponTier {
     KnappedStone,
     PolishedStone,
@@ -81,11 +73,21 @@ impl WeaponTier {
 pub enum Tech {
     Knapping,
     PolishedTools,
+    Agriculture,
+    Writing,
     Archery,
+    BronzeWorking,
+    Currency,
+    Philosophy,
     Siegecraft,
     Metallurgy,
+    Banking,
+    ScientificMethod,
     GunpowderChemistry,
+    Industrialization,
+    SteelForging,
     Ballistics,
+    Electronics,
     NuclearPhysics,
 }
 
@@ -94,101 +96,345 @@ impl Tech {
         match self {
             Tech::Knapping => "Knapping",
             Tech::PolishedTools => "Polished Tools",
+            Tech::Agriculture => "Agriculture",
+            Tech::Writing => "Writing",
             Tech::Archery => "Archery",
+            Tech::BronzeWorking => "Bronze Working",
+            Tech::Currency => "Currency",
+            Tech::Philosophy => "Philosophy",
             Tech::Siegecraft => "Siegecraft",
             Tech::Metallurgy => "Metallurgy",
+            Tech::Banking => "Banking",
+            Tech::ScientificMethod => "Scientific Method",
             Tech::GunpowderChemistry => "Gunpowder Chemistry",
+            Tech::Industrialization => "Industrialization",
+            Tech::SteelForging => "Steel Forging",
             Tech::Ballistics => "Ballistics",
+            Tech::Electronics => "Electronics",
             Tech::NuclearPhysics => "Nuclear Physics",
         }
     }
 }
 
+/// What a tech grants once unlocked. Bonuses are fractions (0.1 = +10%) and
+/// stack additively across every tech a nation holds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TechEffects {
+    pub combat_bonus: f32,
+    pub economy_bonus: f32,
+    pub research_bonus: f32,
+    pub weapon_tier: Option<WeaponTier>,
+}
+
+impl TechEffects {
+    /// Each bonus with its field name, for validation.
+    fn bonuses(&self) -> [(&'static str, f32); 3] {
+        [
+            ("combat_bonus", self.combat_bonus),
+            ("economy_bonus", self.economy_bonus),
+            ("research_bonus", self.research_bonus),
+        ]
+    }
+
+    /// How much the tech helps on the battlefield, for research planning.
+    pub fn military_weight(&self) -> f32 {
+        self.combat_bonus * 5.0 + if self.weapon_tier.is_some() { 1.0 } else { 0.0 }
+    }
+
+    /// How much the tech helps the economy and further research.
+    pub fn civic_weight(&self) -> f32 {
+        (self.economy_bonus + self.research_bonus) * 5.0
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EraTechTier {
+pub struct TechNode {
+    pub tech: Tech,
     pub era: Era,
-    pub science_gate: f32,
-    pub culture_gate: f32,
-    pub military_gate: f32,
-    pub weapon_tier: WeaponTier,
-    pub unlocks: Vec<Tech>,
+    /// Research stock spent to unlock it. Zero-cost roots are known from the start.
+    pub cost: f32,
+    #[serde(default)]
+    pub prerequisites: Vec<Tech>,
+    #[serde(default)]
+    pub effects: TechEffects,
 }
 
+/// Tech graph: each node becomes researchable once all its prerequisites are unlocked.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TechTree {
-    pub tiers: Vec<EraTechTier>,
+    pub techs: Vec<TechNode>,
 }
 
 impl TechTree {
-    pub fn tier(&self, era: Era) -> Option<&EraTechTier> {
-        self.tiers.iter().find(|tier| tier.era == era)
+    pub fn node(&self, tech: Tech) -> Option<&TechNode> {
+        self.techs.iter().find(|node| node.tech == tech)
+    }
+
+    /// Techs not yet unlocked whose prerequisites all are.
+    pub fn available<'a>(&'a self, unlocked: &'a [Tech]) -> impl Iterator<Item = &'a TechNode> {
+        self.techs.iter().filter(move |node| {
+            !unlocked.contains(&node.tech)
+                && node
+                    .prerequisites
+                    .iter()
+                    .all(|prerequisite| unlocked.contains(prerequisite))
+        })
+    }
+
+    pub fn era_for(&self, unlocked: &[Tech]) -> Era {
+        self.unlocked_nodes(unlocked)
+            .map(|node| node.era)
+            .max()
+            .unwrap_or(Era::Dawn)
     }
 
-    pub fn next_tier(&self, era: Era) -> Option<&EraTechTier> {
-        era.next().and_then(|next| self.tier(next))
+    /// Combined effects of every unlocked tech; `weapon_tier` is the best granted.
+    pub fn effects_for(&self, unlocked: &[Tech]) -> TechEffects {
+        self.unlocked_nodes(unlocked)
+            .fold(TechEffects::default(), |mut total, node| {
+                total.combat_bonus += node.effects.combat_bonus;
+                total.economy_bonus += node.effects.economy_bonus;
+                total.research_bonus += node.effects.research_bonus;
+                total.weapon_tier = total.weapon_tier.max(node.effects.weapon_tier);
+                total
+            })
     }
+
+    fn unlocked_nodes<'a>(&'a self, unlocked: &'a [Tech]) -> impl Iterator<Item = &'a TechNode> {
+        self.techs
+            .iter()
+            .filter(move |node| unlocked.contains(&node.tech))
+    }
+
+    /// Structural problems with the graph, as `techs[i].field: problem` messages.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.techs.is_empty() {
+            problems.push("techs: must list at least one tech".to_string());
+        }
+        if !self.techs.iter().any(|node| node.prerequisites.is_empty()) {
+            problems.push("techs: at least one tech must have no prerequisites".to_string());
+        }
+
+        let mut index_of = HashMap::new();
+        for (index, node) in self.techs.iter().enumerate() {
+            let field = format!("techs[{index}]");
+            if index_of.insert(node.tech, index).is_some() {
+                problems.push(format!(
+                    "{field}.tech: {:?} appears more than once",
+                    node.tech
+                ));
+            }
+            if !node.cost.is_finite() || node.cost < 0.0 {
+                problems.push(format!(
+                    "{field}.cost: must be a non-negative number (got {})",
+                    node.cost
+                ));
+            }
+            for (name, bonus) in node.effects.bonuses() {
+                if !bonus.is_finite() || bonus <= -1.0 {
+                    problems.push(format!(
+                        "{field}.effects.{name}: must be a number greater than -1 (got {bonus})"
+                    ));
+                }
+            }
+        }
+        // Penalties stack, so together they must still leave each modifier
+        // positive once every tech is unlocked.
+        for (slot, (name, _)) in TechEffects::default().bonuses().into_iter().enumerate() {
+            let penalty: f32 = self
+                .techs
+                .iter()
+                .map(|node| node.effects.bonuses()[slot].1.min(0.0))
+                .sum();
+            if penalty <= -1.0 {
+                problems.push(format!(
+                    "techs: negative {name} effects must sum to more than -1 (got {penalty})"
+                ));
+            }
+        }
+        for (index, node) in self.techs.iter().enumerate() {
+            for (slot, prerequisite) in node.prerequisites.iter().enumerate() {
+                if !index_of.contains_key(prerequisite) {
+                    problems.push(format!(
+                        "techs[{index}].prerequisites[{slot}]: {prerequisite:?} is not in the tree"
+                    ));
+                }
+            }
+        }
+
+        // Depth-first search for cycles; a tech on one could never be researched.
+        let mut state = vec![Visit::New; self.techs.len()];
+        for start in 0..self.techs.len() {
+            if let Some(index) = self.find_cycle(start, &index_of, &mut state) {
+                problems.push(format!(
+                    "techs[{index}].prerequisites: {:?} is part of a prerequisite cycle",
+                    self.techs[index].tech
+                ));
+            }
+        }
+        problems
+    }
+
+    fn find_cycle(
+        &self,
+        index: usize,
+        index_of: &HashMap<Tech, usize>,
+        state: &mut [Visit],
+    ) -> Option<usize> {
+        match state[index] {
+            Visit::Done => return None,
+            Visit::Active => return Some(index),
+            Visit::New => {}
+        }
+        state[index] = Visit::Active;
+        for prerequisite in &self.techs[index].prerequisites {
+            if let Some(&next) = index_of.get(prerequisite)
+                && let Some(cycle) = self.find_cycle(next, index_of, state)
+            {
+                state[index] = Visit::Done;
+                return Some(cycle);
+            }
+        }
+        state[index] = Visit::Done;
+        None
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Visit {
+    New,
+    Active,
+    Done,
 }
 
 impl Default for TechTree {
     fn default() -> Self {
+        use Era::*;
+        use Tech::*;
+
+        let combat = |combat_bonus| TechEffects {
+            combat_bonus,
+            ..TechEffects::default()
+        };
+        let weapon = |tier, combat_bonus| TechEffects {
+            combat_bonus,
+            weapon_tier: Some(tier),
+            ..TechEffects::default()
+        };
+        let economy = |economy_bonus| TechEffects {
+            economy_bonus,
+            ..TechEffects::default()
+        };
+        let research = |research_bonus| TechEffects {
+            research_bonus,
+            ..TechEffects::default()
+        };
+        let node = |tech, era, cost, prerequisites: &[Tech], effects| TechNode {
+            tech,
+            era,
+            cost,
+            prerequisites: prerequisites.to_vec(),
+            effects,
+        };
+
         Self {
-            tiers: vec![
-                EraTechTier {
-                    era: Era::Dawn,
-                    science_gate: 0.0,
-                    culture_gate: 0.0,
-                    military_gate: 0.0,
-                    weapon_tier: WeaponTier::KnappedStone,
-                    unlocks: vec![Tech::Knapping],
-                },
-                EraTechTier {
-                    era: Era::Ancient,
-                    science_gate: 35.0,
-                    culture_gate: 20.0,
-                    military_gate: 10.0,
-                    weapon_tier: WeaponTier::PolishedStone,
-                    unlocks: vec![Tech::PolishedTools],
-                },
-                EraTechTier {
-                    era: Era::Classical,
-                    science_gate: 50.0,
-                    culture_gate: 30.0,
-                    military_gate: 18.0,
-                    weapon_tier: WeaponTier::Bow,
-                    unlocks: vec![Tech::Archery],
-                },
-                EraTechTier {
-                    era: Era::Medieval,
-                    science_gate: 65.0,
-                    culture_gate: 40.0,
-                    military_gate: 25.0,
-                    weapon_tier: WeaponTier::Crossbow,
-                    unlocks: vec![Tech::Siegecraft],
-                },
-                EraTechTier {
-                    era: Era::Industrial,
-                    science_gate: 75.0,
-                    culture_gate: 55.0,
-                    military_gate: 35.0,
-                    weapon_tier: WeaponTier::Gunpowder,
-                    unlocks: vec![Tech::Metallurgy, Tech::GunpowderChemistry],
-                },
-                EraTechTier {
-                    era: Era::Modern,
-                    science_gate: 85.0,
-                    culture_gate: 65.0,
-                    military_gate: 50.0,
-                    weapon_tier: WeaponTier::ModernArmor,
-                    unlocks: vec![Tech::Ballistics],
-                },
-                EraTechTier {
-                    era: Era::Nuclear,
-                    science_gate: 95.0,
-                    culture_gate: 75.0,
-                    military_gate: 70.0,
-                    weapon_tier: WeaponTier::NuclearArsenal,
-                    unlocks: vec![Tech::NuclearPhysics],
-                },
+            techs: vec![
+                node(
+                    Knapping,
+                    Dawn,
+                    0.0,
+                    &[],
+                    weapon(WeaponTier::KnappedStone, 0.0),
+                ),
+                node(
+                    PolishedTools,
+                    Ancient,
+                    40.0,
+                    &[Knapping],
+                    weapon(WeaponTier::PolishedStone, 0.0),
+                ),
+                node(Agriculture, Ancient, 45.0, &[Knapping], economy(0.1)),
+                node(Writing, Ancient, 70.0, &[Agriculture], research(0.1)),
+                node(
+                    Archery,
+                    Classical,
+                    90.0,
+                    &[PolishedTools],
+                    weapon(WeaponTier::Bow, 0.0),
+                ),
+                node(
+                    BronzeWorking,
+                    Classical,
+                    100.0,
+                    &[PolishedTools, Agriculture],
+                    combat(0.1),
+                ),
+                node(Currency, Classical, 110.0, &[Writing], economy(0.15)),
+                node(Philosophy, Classical, 130.0, &[Writing], research(0.15)),
+                node(
+                    Siegecraft,
+                    Medieval,
+                    200.0,
+                    &[Archery, BronzeWorking],
+                    weapon(WeaponTier::Crossbow, 0.0),
+                ),
+                node(Metallurgy, Medieval, 220.0, &[BronzeWorking], combat(0.1)),
+                node(Banking, Medieval, 240.0, &[Currency], economy(0.15)),
+                node(
+                    ScientificMethod,
+                    Medieval,
+                    260.0,
+                    &[Philosophy],
+                    research(0.2),
+                ),
+                node(
+                    GunpowderChemistry,
+                    Industrial,
+                    380.0,
+                    &[Siegecraft, Metallurgy],
+                    weapon(WeaponTier::Gunpowder, 0.0),
+                ),
+                node(
+                    Industrialization,
+                    Industrial,
+                    420.0,
+                    &[Banking, Metallurgy],
+                    economy(0.2),
+                ),
+                node(
+                    SteelForging,
+                    Industrial,
+                    450.0,
+                    &[Metallurgy, Industrialization],
+                    weapon(WeaponTier::SteelArmor, 0.05),
+                ),
+                node(
+                    Ballistics,
+                    Modern,
+                    650.0,
+                    &[GunpowderChemistry, SteelForging],
+                    weapon(WeaponTier::ModernArmor, 0.1),
+                ),
+                node(
+                    Electronics,
+                    Modern,
+                    700.0,
+                    &[Industrialization, ScientificMethod],
+                    TechEffects {
+                        economy_bonus: 0.1,
+                        research_bonus: 0.25,
+                        ..TechEffects::default()
+                    },
+                ),
+                node(
+                    NuclearPhysics,
+                    Nuclear,
+                    1200.0,
+                    &[Ballistics, Electronics],
+                    weapon(WeaponTier::NuclearArsenal, 0.0),
+                ),
             ],
         }
     }
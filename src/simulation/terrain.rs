@@ -0,0 +1,332 @@
+//! Seeded procedural terrain for the hex grid.
+//!
+//! Elevation blends a few continent cores with fractal value noise, and ridged
+//! noise raises mountain ranges out of the land. Heights are then rescaled so
+//! a fixed share of the map lies below [`SEA_LEVEL`]. Temperature falls with
+//! latitude and altitude; moisture mixes noise with distance to open water.
+//! [`classify`] turns the three fields into a biome under the current climate,
+//! so rising seas and retreating ice reclassify hexes in place.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use crate::simulation::rng::splitmix64;
+use crate::simulation::{
+    AxialCoord, Biome, ClimateState, Nation, RngStream, SimulationRng, hex_coords,
+};
+
+/// Shoreline elevation at a sea level of zero.
+pub const SEA_LEVEL: f32 = 0.3;
+/// Elevation drowned per unit of `ClimateState::sea_level`.
+const SEA_RISE: f32 = 0.15;
+/// Water within this depth of the waterline is coast rather than ocean.
+const SHELF_DEPTH: f32 = 0.04;
+/// Land above this elevation is mountains.
+const MOUNTAIN_LINE: f32 = 0.78;
+/// Temperature below which land freezes, per unit of `ClimateState::ice_line`.
+const FROST_SCALE: f32 = 0.5;
+/// Share of generated hexes that start below sea level.
+const OCEAN_SHARE: f32 = 0.45;
+/// Claim cost of crossing a water hex, relative to a land hex.
+const WATER_CROSSING_COST: u32 = 4;
+
+/// The three fields generated for each hex.
+#[derive(Debug, Clone, Copy)]
+pub struct TerrainSample {
+    pub elevation: f32,
+    pub moisture: f32,
+    pub temperature: f32,
+}
+
+/// Generates terrain for every hex of a grid of `radius`. The same seed
+/// always yields the same map.
+pub fn generate(rng: &SimulationRng, radius: i32) -> HashMap<AxialCoord, TerrainSample> {
+    let mut draw = rng.stream(RngStream::Terrain, 0);
+    let shape = ValueNoise { seed: draw.r#gen() };
+    let ridges = ValueNoise { seed: draw.r#gen() };
+    let weather = ValueNoise { seed: draw.r#gen() };
+    let span = radius.max(1) as f32;
+
+    // Continent cores scattered inside the rim; bigger maps get more of them.
+    let cores: Vec<((f32, f32), f32)> = (0..3 + radius.max(0) / 5)
+        .map(|_| {
+            let angle = draw.gen_range(0.0..TAU);
+            let distance = draw.gen_range(0.0..0.7) * span;
+            let size = draw.gen_range(0.25..0.45) * span;
+            ((angle.cos() * distance, angle.sin() * distance), size)
+        })
+        .collect();
+
+    let coords: Vec<AxialCoord> = hex_coords(radius).collect();
+    let heights: Vec<f32> = coords
+        .iter()
+        .map(|&coord| {
+            let (x, y) = plane(coord);
+            let (nx, ny) = (x / span, y / span);
+            let continent = cores
+                .iter()
+                .map(|&((cx, cy), size)| 1.0 - (x - cx).hypot(y - cy) / size)
+                .fold(-1.0, f32::max);
+            let detail = shape.fractal(nx * 3.5, ny * 3.5, 5) - 0.5;
+            // Ranges follow the ridge lines but only rise where there is land.
+            let land = (continent * 3.0).clamp(0.0, 1.0);
+            let ridge = ridges.ridged(nx * 3.0, ny * 3.0, 2) * land;
+            // The rim of the map always falls away into open ocean.
+            let rim = (x.hypot(y) / span - 0.8).max(0.0) * 2.5;
+            continent * 0.5 + detail * 1.2 + ridge * 0.5 - rim
+        })
+        .collect();
+
+    let mut sorted = heights.clone();
+    sorted.sort_by(f32::total_cmp);
+    let (Some(&low), Some(&high)) = (sorted.first(), sorted.last()) else {
+        return HashMap::new();
+    };
+    let shore = sorted[((sorted.len() - 1) as f32 * OCEAN_SHARE) as usize];
+    let elevations: HashMap<AxialCoord, f32> = coords
+        .iter()
+        .zip(&heights)
+        .map(|(&coord, &height)| {
+            let elevation = if height < shore {
+                SEA_LEVEL * (height - low) / (shore - low).max(f32::EPSILON)
+            } else {
+                SEA_LEVEL + (1.0 - SEA_LEVEL) * (height - shore) / (high - shore).max(f32::EPSILON)
+            };
+            (coord, elevation.clamp(0.0, 1.0))
+        })
+        .collect();
+
+    let shoreline_distance = distance_to_water(&elevations);
+    let max_y = span * 3.0_f32.sqrt() / 2.0;
+    coords
+        .iter()
+        .map(|&coord| {
+            let elevation = elevations[&coord];
+            let (x, y) = plane(coord);
+            let (nx, ny) = (x / span, y / span);
+            let damp = 0.5_f32.powf(shoreline_distance[&coord] as f32 / 2.0);
+            let rain = stretch(weather.fractal(nx * 4.0 + 31.7, ny * 4.0 - 12.9, 3));
+            let moisture = (rain * 0.55 + damp * 0.45).clamp(0.0, 1.0);
+            let latitude = (y / max_y).abs().min(1.0);
+            let altitude = (elevation - SEA_LEVEL).max(0.0);
+            let drift = weather.fractal(nx * 2.0 - 7.3, ny * 2.0 + 5.1, 2) - 0.5;
+            let temperature =
+                (1.0 - latitude * 0.85 - altitude * 0.6 + drift * 0.2).clamp(0.0, 1.0);
+            (
+                coord,
+                TerrainSample {
+                    elevation,
+                    moisture,
+                    temperature,
+                },
+            )
+        })
+        .collect()
+}
+
+/// The neighbour that fallout and weather drift onto from `coord` on a grid
+/// of `radius`. Trade winds blow west near the equator, westerlies east
+/// through the mid-latitudes and polar easterlies west again near the rim.
+pub fn downwind(coord: AxialCoord, radius: i32) -> AxialCoord {
+    let latitude = coord.r.abs() as f32 / radius.max(1) as f32;
+    let step = if (1.0 / 3.0..2.0 / 3.0).contains(&latitude) {
+        1
+    } else {
+        -1
+    };
+    AxialCoord::new(coord.q + step, coord.r)
+}
+
+/// Biome of a hex under the current climate. Rising seas drown low land and a
+/// higher ice line freezes more of the cold latitudes.
+pub fn classify(sample: TerrainSample, climate: &ClimateState) -> Biome {
+    let waterline = SEA_LEVEL + climate.sea_level * SEA_RISE;
+    let frost = climate.ice_line * FROST_SCALE;
+    let TerrainSample {
+        elevation,
+        moisture,
+        temperature,
+    } = sample;
+
+    if elevation < waterline - SHELF_DEPTH {
+        Biome::Ocean
+    } else if elevation < waterline {
+        Biome::Coast
+    } else if temperature < frost {
+        Biome::Glacier
+    } else if elevation > MOUNTAIN_LINE {
+        Biome::Mountain
+    } else if temperature < frost + 0.15 {
+        Biome::Tundra
+    } else if temperature > 0.68 {
+        if moisture < 0.3 {
+            Biome::Desert
+        } else if moisture < 0.55 {
+            Biome::Savanna
+        } else {
+            Biome::Jungle
+        }
+    } else if moisture < 0.25 {
+        Biome::Desert
+    } else if moisture < 0.5 {
+        Biome::Plains
+    } else {
+        Biome::Forest
+    }
+}
+
+/// For each target, the nearest land hex fit for a capital that no earlier
+/// target took. Targets with no such hex left are kept as they are.
+pub fn land_capitals(
+    biomes: &HashMap<AxialCoord, Biome>,
+    targets: &[AxialCoord],
+) -> Vec<AxialCoord> {
+    let mut taken = HashSet::new();
+    targets
+        .iter()
+        .map(|&target| {
+            let capital = biomes
+                .iter()
+                .filter(|(coord, biome)| {
+                    !biome.is_water()
+                        && !matches!(biome, Biome::Glacier | Biome::Mountain)
+                        && !taken.contains(*coord)
+                })
+                .map(|(coord, _)| *coord)
+                .min_by_key(|coord| (coord.distance(target), coord.q, coord.r))
+                .unwrap_or(target);
+            taken.insert(capital);
+            capital
+        })
+        .collect()
+}
+
+/// Grows every claim outward from its capital, cheapest hex first, and
+/// returns the owner of each land hex reached. Each claim is
+/// `(nation, capital)`. Claims may cross water at a higher cost, but water
+/// hexes themselves stay unowned.
+pub fn allocate_land(
+    biomes: &HashMap<AxialCoord, Biome>,
+    claims: &[(Nation, AxialCoord)],
+) -> HashMap<AxialCoord, Nation> {
+    let mut owners = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut frontier = BinaryHeap::new();
+    for (index, &(_, capital)) in claims.iter().enumerate() {
+        frontier.push(Reverse((0_u32, index, capital.q, capital.r)));
+    }
+
+    while let Some(Reverse((cost, index, q, r))) = frontier.pop() {
+        let coord = AxialCoord::new(q, r);
+        let Some(biome) = biomes.get(&coord) else {
+            continue;
+        };
+        if !settled.insert(coord) {
+            continue;
+        }
+        let (nation, _) = claims[index];
+        if !biome.is_water() {
+            owners.insert(coord, nation);
+        }
+        for next in coord.neighbors() {
+            let Some(next_biome) = biomes.get(&next) else {
+                continue;
+            };
+            if settled.contains(&next) {
+                continue;
+            }
+            let step = if next_biome.is_water() {
+                WATER_CROSSING_COST
+            } else {
+                1
+            };
+            frontier.push(Reverse((cost + step, index, next.q, next.r)));
+        }
+    }
+    owners
+}
+
+/// Hex steps from each coordinate to the nearest hex below sea level.
+fn distance_to_water(elevations: &HashMap<AxialCoord, f32>) -> HashMap<AxialCoord, u32> {
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for (&coord, &elevation) in elevations {
+        if elevation < SEA_LEVEL {
+            distances.insert(coord, 0);
+            queue.push_back(coord);
+        }
+    }
+    while let Some(coord) = queue.pop_front() {
+        let next_distance = distances[&coord] + 1;
+        for next in coord.neighbors() {
+            if elevations.contains_key(&next) && !distances.contains_key(&next) {
+                distances.insert(next, next_distance);
+                queue.push_back(next);
+            }
+        }
+    }
+    // A map with no water at all: treat everything as far inland.
+    for &coord in elevations.keys() {
+        distances.entry(coord).or_insert(u32::MAX);
+    }
+    distances
+}
+
+/// Center of a hex on the plane, one unit between neighbours.
+fn plane(coord: AxialCoord) -> (f32, f32) {
+    let (q, r) = (coord.q as f32, coord.r as f32);
+    (q + r / 2.0, r * 3.0_f32.sqrt() / 2.0)
+}
+
+/// Widens fractal noise, which bunches around 0.5, back towards 0..1.
+fn stretch(value: f32) -> f32 {
+    ((value - 0.5) * 2.0 + 0.5).clamp(0.0, 1.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Smoothly interpolated random values on an integer lattice.
+struct ValueNoise {
+    seed: u64,
+}
+
+impl ValueNoise {
+    fn lattice(&self, x: i32, y: i32) -> f32 {
+        let key = ((x as u32 as u64) << 32) | y as u32 as u64;
+        (splitmix64(self.seed ^ splitmix64(key)) >> 40) as f32 / (1_u64 << 24) as f32
+    }
+
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let (x0, y0) = (x.floor(), y.floor());
+        let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+        let (tx, ty) = (smooth(x - x0), smooth(y - y0));
+        let (ix, iy) = (x0 as i32, y0 as i32);
+        let top = lerp(self.lattice(ix, iy), self.lattice(ix + 1, iy), tx);
+        let bottom = lerp(self.lattice(ix, iy + 1), self.lattice(ix + 1, iy + 1), tx);
+        lerp(top, bottom, ty)
+    }
+
+    /// Octaves at doubling frequency and halving weight, normalised to 0..1.
+    fn fractal(&self, x: f32, y: f32, octaves: u32) -> f32 {
+        let (mut total, mut weight, mut frequency, mut norm) = (0.0, 1.0, 1.0, 0.0);
+        for octave in 0..octaves {
+            // Offset each octave so they do not share a lattice point at the origin.
+            let offset = octave as f32 * 17.31;
+            total += self.sample(x * frequency + offset, y * frequency - offset) * weight;
+            norm += weight;
+            weight *= 0.5;
+            frequency *= 2.0;
+        }
+        total / norm
+    }
+
+    /// Sharp crests where the noise crosses its midpoint, for mountain chains.
+    fn ridged(&self, x: f32, y: f32, octaves: u32) -> f32 {
+        (1.0 - (self.fractal(x, y, octaves) * 2.0 - 1.0).abs()).powi(3)
+    }
+}
@@ -0,0 +1,136 @@
+//! Trade routes: standing links between two nations' capital markets that
+//! carry goods from where they are cheap to where they are dear.
+
+use std::collections::BTreeMap;
+
+use bevy_ecs::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::Nation;
+
+/// Capital distance, in hexes, at which a route carries half as much.
+const DISTANCE_SCALE: f32 = 10.0;
+/// Ticks of a route's income weighed against a nation's economy when
+/// judging how much it depends on the route.
+const DEPENDENCE_TICKS: f32 = 100.0;
+
+/// Share of its full load a route over `distance` hexes can carry.
+pub fn route_reach(distance: u32) -> f32 {
+    1.0 / (1.0 + distance as f32 / DISTANCE_SCALE)
+}
+
+/// What happened to a trade route.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteChange {
+    Opened,
+    /// Cut by a war between the partners.
+    Severed,
+    /// Closed by sanctions one partner imposed on the other.
+    Embargoed,
+    /// Closed after carrying nothing for too long.
+    Lapsed,
+}
+
+impl RouteChange {
+    pub fn label(self) -> &'static str {
+        match self {
+            RouteChange::Opened => "opens",
+            RouteChange::Severed => "is severed by war",
+            RouteChange::Embargoed => "is closed by sanctions",
+            RouteChange::Lapsed => "lapses",
+        }
+    }
+}
+
+/// A route between the capitals of `a` and `b`, with `a < b`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeRoute {
+    pub a: Nation,
+    pub b: Nation,
+    /// Hexes between the two capitals.
+    pub distance: u32,
+    pub opened: u64,
+    /// Value, at the exporters' prices, of the goods carried last tick.
+    pub volume: f32,
+    /// Units of each good carried last tick: positive from `a` to `b`,
+    /// negative from `b` to `a`.
+    pub goods: BTreeMap<String, f32>,
+    /// Duty `a` levies on goods from `b`, as a share of their value.
+    pub tariff_a: f32,
+    /// Duty `b` levies on goods from `a`.
+    pub tariff_b: f32,
+    /// Economy each side earned from the route last tick.
+    pub income_a: f32,
+    pub income_b: f32,
+    /// Ticks in a row the route has carried nothing.
+    pub idle: u32,
+}
+
+impl TradeRoute {
+    pub fn new(a: Nation, b: Nation, distance: u32, opened: u64) -> Self {
+        Self {
+            a,
+            b,
+            distance,
+            opened,
+            volume: 0.0,
+            goods: BTreeMap::new(),
+            tariff_a: 0.0,
+            tariff_b: 0.0,
+            income_a: 0.0,
+            income_b: 0.0,
+            idle: 0,
+        }
+    }
+
+    pub fn links(&self, nation: Nation) -> bool {
+        self.a == nation || self.b == nation
+    }
+
+    /// The other end of the route from `nation`.
+    pub fn partner(&self, nation: Nation) -> Nation {
+        if self.a == nation { self.b } else { self.a }
+    }
+
+    /// What `nation` earned from the route last tick.
+    pub fn income(&self, nation: Nation) -> f32 {
+        if self.a == nation {
+            self.income_a
+        } else if self.b == nation {
+            self.income_b
+        } else {
+            0.0
+        }
+    }
+
+    /// The good carried in the largest quantity, either way.
+    pub fn main_good(&self) -> Option<&str> {
+        self.goods
+            .iter()
+            .max_by(|x, y| x.1.abs().total_cmp(&y.1.abs()))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct TradeRoutes {
+    pub routes: Vec<TradeRoute>,
+}
+
+impl TradeRoutes {
+    pub fn route(&self, a: Nation, b: Nation) -> Option<&TradeRoute> {
+        self.routes
+            .iter()
+            .find(|route| route.links(a) && route.links(b) && a != b)
+    }
+
+    /// How much `nation`, with `economy`, leans on its route to `partner`
+    /// (0..1): the route's income over a stretch of ticks against the
+    /// economy it would have to do without.
+    pub fn dependence(&self, nation: Nation, partner: Nation, economy: f32) -> f32 {
+        self.route(nation, partner).map_or(0.0, |route| {
+            (route.income(nation) * DEPENDENCE_TICKS / economy.max(1.0)).clamp(0.0, 1.0)
+        })
+    }
+}
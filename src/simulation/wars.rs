@@ -0,0 +1,169 @@
+//! Wars between coalitions of nations, and the truces that follow them.
+
+use bevy_ecs::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::{AxialCoord, Nation};
+
+/// Why a war was declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CasusBelli {
+    /// Neighbours fighting over the land between them.
+    BorderDispute,
+    /// Retaliation against a nation that sanctioned the aggressor.
+    Sanctions,
+    /// Naked aggression against a rival.
+    Conquest,
+    /// Settling a score with a nation that once invaded or betrayed the
+    /// aggressor.
+    Revenge,
+}
+
+impl CasusBelli {
+    pub fn label(self) -> &'static str {
+        match self {
+            CasusBelli::BorderDispute => "Border Dispute",
+            CasusBelli::Sanctions => "Sanctions",
+            CasusBelli::Conquest => "Conquest",
+            CasusBelli::Revenge => "Revenge",
+        }
+    }
+
+    /// What the aggressor demands if it wins.
+    pub fn goal(self) -> WarGoal {
+        match self {
+            CasusBelli::BorderDispute | CasusBelli::Conquest => WarGoal::Annexation,
+            CasusBelli::Sanctions | CasusBelli::Revenge => WarGoal::Reparations,
+        }
+    }
+}
+
+/// The terms a winning side imposes at the peace table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarGoal {
+    /// Keep the land occupied during the war.
+    Annexation,
+    /// Hand occupied land back, but make the losers pay.
+    Reparations,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct War {
+    pub id: u64,
+    /// The attacking coalition; the first member declared the war.
+    pub attackers: Vec<Nation>,
+    /// The defending coalition; the first member was the declared target.
+    pub defenders: Vec<Nation>,
+    pub casus_belli: CasusBelli,
+    pub goal: WarGoal,
+    pub started: u64,
+    /// -100..100; positive favours the attackers.
+    pub score: f32,
+    /// 0..100; battles, occupation and time wear both sides down until
+    /// they come to terms.
+    pub exhaustion: f32,
+    /// Hexes taken during the war, with the owner each had before.
+    pub occupied: Vec<(AxialCoord, Nation)>,
+    /// Someone has fired nuclear weapons; every arsenal is now in play.
+    pub nuclear: bool,
+    /// A leader has sued for peace; talks open at the next review.
+    #[serde(default)]
+    pub peace_offered: bool,
+}
+
+impl War {
+    /// `Some(true)` for attackers, `Some(false)` for defenders.
+    pub fn side(&self, nation: Nation) -> Option<bool> {
+        if self.attackers.contains(&nation) {
+            Some(true)
+        } else if self.defenders.contains(&nation) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `nation` leads its side, and so may sue for peace.
+    pub fn leads(&self, nation: Nation) -> bool {
+        self.attackers.first() == Some(&nation) || self.defenders.first() == Some(&nation)
+    }
+
+    pub fn opposes(&self, a: Nation, b: Nation) -> bool {
+        matches!(
+            (self.side(a), self.side(b)),
+            (Some(side_a), Some(side_b)) if side_a != side_b
+        )
+    }
+
+    /// The members fighting against `nation`, war leader first.
+    pub fn enemies_of(&self, nation: Nation) -> &[Nation] {
+        match self.side(nation) {
+            Some(true) => &self.defenders,
+            Some(false) => &self.attackers,
+            None => &[],
+        }
+    }
+
+    /// Moves the score toward `nation`'s side by `points`.
+    pub fn credit(&mut self, nation: Nation, points: f32) {
+        let signed = if self.side(nation) == Some(true) {
+            points
+        } else {
+            -points
+        };
+        self.score = (self.score + signed).clamp(-100.0, 100.0);
+    }
+
+    /// Records `coord` changing hands from `from`; a hex won back by its
+    /// pre-war owner is no longer occupied.
+    pub fn record_capture(&mut self, coord: AxialCoord, from: Nation, to: Nation) {
+        if let Some(index) = self.occupied.iter().position(|(c, _)| *c == coord) {
+            if self.occupied[index].1 == to {
+                self.occupied.remove(index);
+            }
+        } else {
+            self.occupied.push((coord, from));
+        }
+    }
+}
+
+/// A pair of nations barred from declaring war on each other until `until`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Truce {
+    pub a: Nation,
+    pub b: Nation,
+    pub until: u64,
+}
+
+#[derive(Debug, Clone, Resource, Serialize, Deserialize, Default)]
+pub struct Wars {
+    pub active: Vec<War>,
+    pub truces: Vec<Truce>,
+    pub next_id: u64,
+    /// Declarations made this tick, as (attacker, target), waiting for the
+    /// warfare system to carry them out.
+    #[serde(default)]
+    pub declarations: Vec<(Nation, Nation)>,
+}
+
+impl Wars {
+    pub fn between(&self, a: Nation, b: Nation) -> Option<&War> {
+        self.active.iter().find(|war| war.opposes(a, b))
+    }
+
+    pub fn between_mut(&mut self, a: Nation, b: Nation) -> Option<&mut War> {
+        self.active.iter_mut().find(|war| war.opposes(a, b))
+    }
+
+    pub fn at_war(&self, a: Nation, b: Nation) -> bool {
+        self.between(a, b).is_some()
+    }
+
+    pub fn in_truce(&self, a: Nation, b: Nation) -> bool {
+        self.truces
+            .iter()
+            .any(|truce| (truce.a == a && truce.b == b) || (truce.a == b && truce.b == a))
+    }
+}
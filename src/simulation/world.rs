@@ -1,41 +1,59 @@
 //! Structured metadata describing TERA's worldbuilding fabric.
+//!
+//! The built-in world lives in `WorldMetadata::default()`; designers can
+//! override any part of it from a TOML, RON or JSON file.
 
 use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
 
+use anyhow::{Context, anyhow, bail};
 use bevy_ecs::prelude::Resource;
+use serde::de::{self, DeserializeOwned, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::simulation::{BehaviorState, Biome, Faction, Position, TechTree};
+use crate::simulation::{
+    BehaviorState, Biome, Faction, MAX_NATIONS, MIN_NATIONS, NationProfile, Position, TechTree,
+    Yields,
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BiomeMetadata {
-    pub label: &'static str,
-    pub epithet: &'static str,
-    pub description: &'static str,
+    pub label: String,
+    pub epithet: String,
+    pub description: String,
     pub anchor: (f32, f32),
-    pub resource_profile: Vec<&'static str>,
-    pub tensions: Vec<&'static str>,
+    pub resource_profile: Vec<String>,
+    pub tensions: Vec<String>,
+    #[serde(deserialize_with = "enum_keyed")]
     pub behavior_bias: HashMap<BehaviorState, f32>,
     pub economic_shift: EconomicShift,
+    /// Base food, energy and rare output of one hex of this biome.
+    #[serde(default)]
+    pub yields: Yields,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FactionMetadata {
-    pub motto: &'static str,
-    pub doctrine: &'static str,
-    pub influence_vectors: Vec<&'static str>,
+    pub motto: String,
+    pub doctrine: String,
+    pub influence_vectors: Vec<String>,
     pub strongholds: Vec<Biome>,
+    #[serde(deserialize_with = "enum_keyed")]
     pub behavior_modifiers: HashMap<BehaviorState, f32>,
     pub economy_profile: EconomyProfile,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EconomyMetadata {
-    pub circulation_cycle: Vec<&'static str>,
-    pub stressors: Vec<&'static str>,
-    pub catalysts: Vec<&'static str>,
+    pub circulation_cycle: Vec<String>,
+    pub stressors: Vec<String>,
+    pub catalysts: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EconomicShift {
     pub trade_opportunity: f32,
     pub resource_abundance: f32,
@@ -56,7 +74,7 @@ impl EconomicShift {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EconomyProfile {
     pub trade_yield: f32,
     pub volatility_resistance: f32,
@@ -77,22 +95,196 @@ impl EconomyProfile {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EpochCadence {
-    pub day_segments: Vec<&'static str>,
-    pub seasons: Vec<&'static str>,
+    pub day_segments: Vec<String>,
+    pub seasons: Vec<String>,
 }
 
-#[derive(Debug, Clone, Resource)]
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WorldMetadata {
+    #[serde(deserialize_with = "enum_keyed")]
     pub biomes: HashMap<Biome, BiomeMetadata>,
+    #[serde(deserialize_with = "enum_keyed")]
     pub factions: HashMap<Faction, FactionMetadata>,
     pub economy: EconomyMetadata,
     pub epochs: EpochCadence,
+    /// Nations the run starts with, in id order; the grid gets one sector each.
+    pub nations: Vec<NationProfile>,
     pub tech_tree: TechTree,
 }
 
 impl WorldMetadata {
+    /// Loads metadata from a `.toml`, `.ron` or `.json` file. Top-level
+    /// sections, biomes and factions left out of the file keep their
+    /// built-in values.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read world file {}", path.display()))?;
+        let parsed = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => parse_with_path(toml::Deserializer::new(&text)),
+            Some("ron") => {
+                let mut deserializer = ron::Deserializer::from_str(&text)
+                    .with_context(|| format!("invalid RON in {}", path.display()))?;
+                parse_with_path(&mut deserializer)
+            }
+            Some("json") => parse_with_path(&mut serde_json::Deserializer::from_str(&text)),
+            _ => bail!(
+                "world file {} must end in .toml, .ron or .json",
+                path.display()
+            ),
+        };
+        let mut metadata =
+            parsed.map_err(|err| anyhow!("invalid world file {}: {err}", path.display()))?;
+
+        let defaults = Self::default();
+        for (biome, meta) in defaults.biomes {
+            metadata.biomes.entry(biome).or_insert(meta);
+        }
+        for (faction, meta) in defaults.factions {
+            metadata.factions.entry(faction).or_insert(meta);
+        }
+
+        let problems = metadata.validate();
+        if !problems.is_empty() {
+            bail!(
+                "invalid world file {}:\n  {}",
+                path.display(),
+                problems.join("\n  ")
+            );
+        }
+        Ok(metadata)
+    }
+
+    /// Writes this metadata in the format implied by the file extension,
+    /// giving designers a complete template to edit.
+    pub fn export(&self, path: &Path) -> anyhow::Result<()> {
+        let text = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::to_string_pretty(self)?,
+            Some("ron") => ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?,
+            Some("json") => serde_json::to_string_pretty(self)?,
+            _ => bail!(
+                "world file {} must end in .toml, .ron or .json",
+                path.display()
+            ),
+        };
+        fs::write(path, text).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Checks the invariants the systems rely on, returning one message per
+    /// problem prefixed with the offending field.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let lists = [
+            ("epochs.day_segments", &self.epochs.day_segments),
+            ("epochs.seasons", &self.epochs.seasons),
+            ("economy.circulation_cycle", &self.economy.circulation_cycle),
+            ("economy.stressors", &self.economy.stressors),
+            ("economy.catalysts", &self.economy.catalysts),
+        ];
+        for (field, list) in lists {
+            if list.is_empty() {
+                problems.push(format!("{field}: must list at least one entry"));
+            }
+        }
+
+        for (biome, meta) in &self.biomes {
+            let field = format!("biomes.{biome:?}");
+            if !(meta.anchor.0.is_finite() && meta.anchor.1.is_finite()) {
+                problems.push(format!("{field}.anchor: coordinates must be finite"));
+            }
+            let shift = &meta.economic_shift;
+            check_positive(
+                &mut problems,
+                &format!("{field}.economic_shift.trade_opportunity"),
+                shift.trade_opportunity,
+            );
+            check_positive(
+                &mut problems,
+                &format!("{field}.economic_shift.resource_abundance"),
+                shift.resource_abundance,
+            );
+            check_positive(
+                &mut problems,
+                &format!("{field}.economic_shift.risk_factor"),
+                shift.risk_factor,
+            );
+            for (channel, value) in [
+                ("food", meta.yields.food),
+                ("energy", meta.yields.energy),
+                ("rare", meta.yields.rare),
+            ] {
+                check_non_negative(&mut problems, &format!("{field}.yields.{channel}"), value);
+            }
+            for (state, bias) in &meta.behavior_bias {
+                check_non_negative(
+                    &mut problems,
+                    &format!("{field}.behavior_bias.{state:?}"),
+                    *bias,
+                );
+            }
+        }
+
+        for (faction, meta) in &self.factions {
+            let field = format!("factions.{faction:?}");
+            let profile = &meta.economy_profile;
+            check_positive(
+                &mut problems,
+                &format!("{field}.economy_profile.trade_yield"),
+                profile.trade_yield,
+            );
+            check_positive(
+                &mut problems,
+                &format!("{field}.economy_profile.volatility_resistance"),
+                profile.volatility_resistance,
+            );
+            check_positive(
+                &mut problems,
+                &format!("{field}.economy_profile.upkeep_burden"),
+                profile.upkeep_burden,
+            );
+            for (state, modifier) in &meta.behavior_modifiers {
+                check_non_negative(
+                    &mut problems,
+                    &format!("{field}.behavior_modifiers.{state:?}"),
+                    *modifier,
+                );
+            }
+        }
+
+        if !(MIN_NATIONS..=MAX_NATIONS).contains(&self.nations.len()) {
+            problems.push(format!(
+                "nations: must list between {MIN_NATIONS} and {MAX_NATIONS} nations (got {})",
+                self.nations.len()
+            ));
+        }
+        for (index, profile) in self.nations.iter().enumerate() {
+            if profile.name.trim().is_empty() {
+                problems.push(format!("nations[{index}].name: must not be empty"));
+            } else if self.nations[..index]
+                .iter()
+                .any(|earlier| earlier.name.eq_ignore_ascii_case(&profile.name))
+            {
+                // Nations are looked up by name regardless of case.
+                problems.push(format!(
+                    "nations[{index}].name: {} appears more than once, ignoring case",
+                    profile.name
+                ));
+            }
+        }
+
+        problems.extend(
+            self.tech_tree
+                .validate()
+                .into_iter()
+                .map(|problem| format!("tech_tree.{problem}")),
+        );
+
+        problems
+    }
+
     pub fn anchor_position(&self, biome: Biome) -> Position {
         if let Some(metadata) = self.biomes.get(&biome) {
             Position {
@@ -143,6 +335,13 @@ impl WorldMetadata {
             .unwrap_or(1.0)
     }
 
+    pub fn biome_yields(&self, biome: Biome) -> Yields {
+        self.biomes
+            .get(&biome)
+            .map(|meta| meta.yields)
+            .unwrap_or_default()
+    }
+
     pub fn biome_risk_factor(&self, biome: Biome) -> f32 {
         self.biomes
             .get(&biome)
@@ -171,12 +370,12 @@ impl WorldMetadata {
             .unwrap_or(1.0)
     }
 
-    pub fn epoch_for_tick(&self, tick: u64) -> (&'static str, &'static str) {
+    pub fn epoch_for_tick(&self, tick: u64) -> (&str, &str) {
         let day_segments = &self.epochs.day_segments;
         let seasons = &self.epochs.seasons;
 
-        let day_segment = day_segments[(tick as usize) % day_segments.len()];
-        let season = seasons[((tick / day_segments.len() as u64) as usize) % seasons.len()];
+        let day_segment = &day_segments[(tick as usize) % day_segments.len()];
+        let season = &seasons[((tick / day_segments.len() as u64) as usize) % seasons.len()];
 
         (day_segment, season)
     }
@@ -190,13 +389,13 @@ impl Default for WorldMetadata {
             (
                 Biome::Forest,
                 BiomeMetadata {
-                    label: "Silken Veil Forest",
-                    epithet: "Land of Whispering Canopies",
+                    label: "Silken Veil Forest".into(),
+                    epithet: "Land of Whispering Canopies".into(),
                     description:
-                        "Ancient forest where herbs, hidden shrines, and fierce spirits coexist.",
+                        "Ancient forest where herbs, hidden shrines, and fierce spirits coexist.".into(),
                     anchor: (6.0, 4.5),
-                    resource_profile: vec!["Herbs", "Lumber", "Rare Animals"],
-                    tensions: vec!["Bandit Ambush", "Expedition Venture", "Shrine Guardian"],
+                    resource_profile: strings(&["Herbs", "Lumber", "Rare Animals"]),
+                    tensions: strings(&["Bandit Ambush", "Expedition Venture", "Shrine Guardian"]),
                     behavior_bias: HashMap::from([
                         (Explore, 1.25),
                         (Gather, 1.2),
@@ -208,18 +407,19 @@ impl Default for WorldMetadata {
                         resource_abundance: 1.2,
                         risk_factor: 1.1,
                     },
+                    yields: Yields::new(0.9, 1.0, 0.3),
                 },
             ),
             (
                 Biome::Plains,
                 BiomeMetadata {
-                    label: "Silverwind Plains",
-                    epithet: "Caravan Procession Under Vast Skies",
+                    label: "Silverwind Plains".into(),
+                    epithet: "Caravan Procession Under Vast Skies".into(),
                     description:
-                        "Vast grasslands with ceaseless caravans, crop rotation, and mounted patrols.",
+                        "Vast grasslands with ceaseless caravans, crop rotation, and mounted patrols.".into(),
                     anchor: (1.0, 2.0),
-                    resource_profile: vec!["Grain", "Livestock", "Fiber"],
-                    tensions: vec!["Harvest Dispute", "Beast Migration", "Caravan Toll"],
+                    resource_profile: strings(&["Grain", "Livestock", "Fiber"]),
+                    tensions: strings(&["Harvest Dispute", "Beast Migration", "Caravan Toll"]),
                     behavior_bias: HashMap::from([
                         (Trade, 1.2),
                         (Gather, 1.1),
@@ -231,18 +431,19 @@ impl Default for WorldMetadata {
                         resource_abundance: 1.05,
                         risk_factor: 0.9,
                     },
+                    yields: Yields::new(1.6, 0.4, 0.1),
                 },
             ),
             (
                 Biome::Desert,
                 BiomeMetadata {
-                    label: "Ashen Mirage",
-                    epithet: "Ruins Sleeping Under Dunes",
+                    label: "Ashen Mirage".into(),
+                    epithet: "Ruins Sleeping Under Dunes".into(),
                     description:
-                        "Desert intertwined with ancient ruins and dangerous mirages, testing all expeditions.",
+                        "Desert intertwined with ancient ruins and dangerous mirages, testing all expeditions.".into(),
                     anchor: (-4.0, -1.5),
-                    resource_profile: vec!["Relics", "Minerals", "Glassroots"],
-                    tensions: vec!["Water Scarcity", "Sandstorm", "Relic Scramble"],
+                    resource_profile: strings(&["Relics", "Minerals", "Glassroots"]),
+                    tensions: strings(&["Water Scarcity", "Sandstorm", "Relic Scramble"]),
                     behavior_bias: HashMap::from([
                         (Explore, 1.1),
                         (Hunt, 1.25),
@@ -254,18 +455,187 @@ impl Default for WorldMetadata {
                         resource_abundance: 0.8,
                         risk_factor: 1.35,
                     },
+                    yields: Yields::new(0.2, 0.7, 0.6),
+                },
+            ),
+            (
+                Biome::Savanna,
+                BiomeMetadata {
+                    label: "Amber Reach".into(),
+                    epithet: "Herds Beneath the Acacia".into(),
+                    description:
+                        "Warm grassland dotted with lone trees, where herds follow the rains and hunters follow the herds.".into(),
+                    anchor: (-2.0, 3.5),
+                    resource_profile: strings(&["Livestock", "Hides", "Ivory"]),
+                    tensions: strings(&["Drought", "Grazing Feud", "Wildfire"]),
+                    behavior_bias: HashMap::from([
+                        (Hunt, 1.2),
+                        (Explore, 1.05),
+                        (Gather, 1.0),
+                        (Rest, 0.95),
+                    ]),
+                    economic_shift: EconomicShift {
+                        trade_opportunity: 1.0,
+                        resource_abundance: 1.0,
+                        risk_factor: 1.0,
+                    },
+                    yields: Yields::new(1.1, 0.4, 0.2),
+                },
+            ),
+            (
+                Biome::Jungle,
+                BiomeMetadata {
+                    label: "Verdant Deep".into(),
+                    epithet: "Where the Canopy Never Dries".into(),
+                    description:
+                        "Steaming rainforest rich in spice and timber, choked with vines, fever, and forgotten temples.".into(),
+                    anchor: (-5.5, 4.0),
+                    resource_profile: strings(&["Spices", "Hardwood", "Rubber"]),
+                    tensions: strings(&["Fever Outbreak", "Lost Expedition", "Temple Curse"]),
+                    behavior_bias: HashMap::from([
+                        (Explore, 1.2),
+                        (Gather, 1.25),
+                        (Hunt, 1.05),
+                        (Rest, 0.85),
+                    ]),
+                    economic_shift: EconomicShift {
+                        trade_opportunity: 0.8,
+                        resource_abundance: 1.35,
+                        risk_factor: 1.3,
+                    },
+                    yields: Yields::new(1.0, 0.6, 0.5),
+                },
+            ),
+            (
+                Biome::Mountain,
+                BiomeMetadata {
+                    label: "Ironspine Range".into(),
+                    epithet: "Crown of Stone and Thunder".into(),
+                    description:
+                        "Jagged peaks veined with ore, guarded passes, and monasteries clinging to the cliffs.".into(),
+                    anchor: (5.0, -5.5),
+                    resource_profile: strings(&["Ore", "Stone", "Gems"]),
+                    tensions: strings(&["Rockslide", "Pass Blockade", "Mine Collapse"]),
+                    behavior_bias: HashMap::from([
+                        (Gather, 1.15),
+                        (Explore, 1.1),
+                        (Trade, 0.8),
+                        (Rest, 0.9),
+                    ]),
+                    economic_shift: EconomicShift {
+                        trade_opportunity: 0.7,
+                        resource_abundance: 1.25,
+                        risk_factor: 1.25,
+                    },
+                    yields: Yields::new(0.2, 0.8, 0.9),
+                },
+            ),
+            (
+                Biome::Tundra,
+                BiomeMetadata {
+                    label: "Pale Steppe".into(),
+                    epithet: "Lichen, Wind, and Long Nights".into(),
+                    description:
+                        "Frozen plain where reindeer herders and trappers endure the dark half of the year.".into(),
+                    anchor: (2.0, -6.5),
+                    resource_profile: strings(&["Furs", "Peat", "Whale Oil"]),
+                    tensions: strings(&["Whiteout", "Famine Winter", "Herd Collapse"]),
+                    behavior_bias: HashMap::from([
+                        (Hunt, 1.2),
+                        (Rest, 1.1),
+                        (Trade, 0.85),
+                        (Explore, 0.9),
+                    ]),
+                    economic_shift: EconomicShift {
+                        trade_opportunity: 0.75,
+                        resource_abundance: 0.7,
+                        risk_factor: 1.2,
+                    },
+                    yields: Yields::new(0.3, 0.4, 0.2),
+                },
+            ),
+            (
+                Biome::Glacier,
+                BiomeMetadata {
+                    label: "Silent Ice".into(),
+                    epithet: "The World's Frozen Roof".into(),
+                    description:
+                        "Endless ice sheet that swallows expeditions and gives back only meltwater and legend.".into(),
+                    anchor: (0.0, -8.0),
+                    resource_profile: strings(&["Freshwater", "Ancient Ice"]),
+                    tensions: strings(&["Crevasse Fall", "Blizzard", "Calving Flood"]),
+                    behavior_bias: HashMap::from([
+                        (Rest, 1.2),
+                        (Explore, 0.8),
+                        (Gather, 0.6),
+                        (Trade, 0.6),
+                    ]),
+                    economic_shift: EconomicShift {
+                        trade_opportunity: 0.5,
+                        resource_abundance: 0.4,
+                        risk_factor: 1.5,
+                    },
+                    yields: Yields::new(0.0, 0.1, 0.2),
+                },
+            ),
+            (
+                Biome::Coast,
+                BiomeMetadata {
+                    label: "Saltmere Shallows".into(),
+                    epithet: "Nets, Reefs, and Harbor Lights".into(),
+                    description:
+                        "Warm shallows and tidal flats where fishing fleets and smugglers share the same coves.".into(),
+                    anchor: (7.5, 1.0),
+                    resource_profile: strings(&["Fish", "Salt", "Pearls"]),
+                    tensions: strings(&["Storm Surge", "Smuggler Cove", "Reef Wreck"]),
+                    behavior_bias: HashMap::from([
+                        (Trade, 1.15),
+                        (Gather, 1.1),
+                        (Explore, 1.0),
+                        (Rest, 1.0),
+                    ]),
+                    economic_shift: EconomicShift {
+                        trade_opportunity: 1.2,
+                        resource_abundance: 1.1,
+                        risk_factor: 1.0,
+                    },
+                    yields: Yields::new(1.2, 0.3, 0.3),
+                },
+            ),
+            (
+                Biome::Ocean,
+                BiomeMetadata {
+                    label: "Sunless Deep".into(),
+                    epithet: "Roads of Salt Between the Lands".into(),
+                    description:
+                        "Open ocean crossed only by the bold, with whale roads, sea lanes, and storms without end.".into(),
+                    anchor: (9.0, -2.0),
+                    resource_profile: strings(&["Fish", "Whales", "Sea Lanes"]),
+                    tensions: strings(&["Shipwreck", "Piracy", "Rogue Wave"]),
+                    behavior_bias: HashMap::from([
+                        (Explore, 1.2),
+                        (Trade, 1.05),
+                        (Gather, 0.8),
+                        (Rest, 0.8),
+                    ]),
+                    economic_shift: EconomicShift {
+                        trade_opportunity: 1.1,
+                        resource_abundance: 0.9,
+                        risk_factor: 1.4,
+                    },
+                    yields: Yields::new(0.6, 0.2, 0.1),
                 },
             ),
             (
                 Biome::Village,
                 BiomeMetadata {
-                    label: "Hearthfire Corridor",
-                    epithet: "Heart of the Community",
+                    label: "Hearthfire Corridor".into(),
+                    epithet: "Heart of the Community".into(),
                     description:
-                        "A ring of villages where workshops, granaries, and temples are tightly connected.",
+                        "A ring of villages where workshops, granaries, and temples are tightly connected.".into(),
                     anchor: (3.5, -3.0),
-                    resource_profile: vec!["Goods", "Craftsmanship", "Rituals"],
-                    tensions: vec!["Civil Conflict", "Disease Spread", "Supply Shortage"],
+                    resource_profile: strings(&["Goods", "Craftsmanship", "Rituals"]),
+                    tensions: strings(&["Civil Conflict", "Disease Spread", "Supply Shortage"]),
                     behavior_bias: HashMap::from([
                         (Trade, 1.1),
                         (Rest, 1.2),
@@ -277,18 +647,19 @@ impl Default for WorldMetadata {
                         resource_abundance: 1.1,
                         risk_factor: 0.85,
                     },
+                    yields: Yields::new(1.0, 0.5, 0.4),
                 },
             ),
             (
                 Biome::Market,
                 BiomeMetadata {
-                    label: "Golden Confluence",
-                    epithet: "Pulse of Commerce",
+                    label: "Golden Confluence".into(),
+                    epithet: "Pulse of Commerce".into(),
                     description:
-                        "Tiered market city where the Guild Council coordinates trade, tariffs, and truces.",
+                        "Tiered market city where the Guild Council coordinates trade, tariffs, and truces.".into(),
                     anchor: (0.0, 0.0),
-                    resource_profile: vec!["Currency", "Contracts", "Intel"],
-                    tensions: vec!["Tariff War", "Speculative Crash", "Guild Infighting"],
+                    resource_profile: strings(&["Currency", "Contracts", "Intel"]),
+                    tensions: strings(&["Tariff War", "Speculative Crash", "Guild Infighting"]),
                     behavior_bias: HashMap::from([
                         (Trade, 1.35),
                         (Idle, 0.9),
@@ -300,6 +671,7 @@ impl Default for WorldMetadata {
                         resource_abundance: 0.9,
                         risk_factor: 1.05,
                     },
+                    yields: Yields::new(0.6, 0.4, 0.8),
                 },
             ),
         ]
@@ -310,9 +682,9 @@ impl Default for WorldMetadata {
             (
                 Faction::MerchantGuild,
                 FactionMetadata {
-                    motto: "Balance the ledger, stabilize the world.",
-                    doctrine: "Focuses on trade diplomacy, caravan escort, and price adjustment.",
-                    influence_vectors: vec!["Tariff Adjustment", "Supply Contract", "Credit Issuance"],
+                    motto: "Balance the ledger, stabilize the world.".into(),
+                    doctrine: "Focuses on trade diplomacy, caravan escort, and price adjustment.".into(),
+                    influence_vectors: strings(&["Tariff Adjustment", "Supply Contract", "Credit Issuance"]),
                     strongholds: vec![Biome::Market, Biome::Plains],
                     behavior_modifiers: HashMap::from([
                         (BehaviorState::Trade, 1.4),
@@ -329,9 +701,9 @@ impl Default for WorldMetadata {
             (
                 Faction::BanditClans,
                 FactionMetadata {
-                    motto: "Seize what the world hides.",
-                    doctrine: "Expands influence via asymmetric raids, terror tactics, and relic monopolies.",
-                    influence_vectors: vec!["Ambush Threat", "Black Market", "Smuggling Network"],
+                    motto: "Seize what the world hides.".into(),
+                    doctrine: "Expands influence via asymmetric raids, terror tactics, and relic monopolies.".into(),
+                    influence_vectors: strings(&["Ambush Threat", "Black Market", "Smuggling Network"]),
                     strongholds: vec![Biome::Forest, Biome::Desert],
                     behavior_modifiers: HashMap::from([
                         (BehaviorState::Hunt, 1.45),
@@ -349,9 +721,9 @@ impl Default for WorldMetadata {
             (
                 Faction::ExplorersLeague,
                 FactionMetadata {
-                    motto: "Map the unknown, grasp the invisible.",
-                    doctrine: "Conducts reconnaissance, anomaly recording, and relic appraisal.",
-                    influence_vectors: vec!["Discovery Rights", "Map Intel", "Relic Appraisal"],
+                    motto: "Map the unknown, grasp the invisible.".into(),
+                    doctrine: "Conducts reconnaissance, anomaly recording, and relic appraisal.".into(),
+                    influence_vectors: strings(&["Discovery Rights", "Map Intel", "Relic Appraisal"]),
                     strongholds: vec![Biome::Forest, Biome::Desert],
                     behavior_modifiers: HashMap::from([
                         (BehaviorState::Explore, 1.5),
@@ -369,9 +741,9 @@ impl Default for WorldMetadata {
             (
                 Faction::SettlersUnion,
                 FactionMetadata {
-                    motto: "Rooted in labor, growing through craft.",
-                    doctrine: "Leads cooperative labor, agricultural planning, and urban reconstruction.",
-                    influence_vectors: vec!["Infrastructure", "Harvest Mgmt", "Community Festivals"],
+                    motto: "Rooted in labor, growing through craft.".into(),
+                    doctrine: "Leads cooperative labor, agricultural planning, and urban reconstruction.".into(),
+                    influence_vectors: strings(&["Infrastructure", "Harvest Mgmt", "Community Festivals"]),
                     strongholds: vec![Biome::Plains, Biome::Village],
                     behavior_modifiers: HashMap::from([
                         (BehaviorState::Gather, 1.35),
@@ -389,9 +761,9 @@ impl Default for WorldMetadata {
             (
                 Faction::TempleOfSuns,
                 FactionMetadata {
-                    motto: "Three suns, one harmonious light.",
-                    doctrine: "Handles peace mediation, relic purification, and public welfare.",
-                    influence_vectors: vec!["Healing Rituals", "Pilgrimage Net", "Moral Authority"],
+                    motto: "Three suns, one harmonious light.".into(),
+                    doctrine: "Handles peace mediation, relic purification, and public welfare.".into(),
+                    influence_vectors: strings(&["Healing Rituals", "Pilgrimage Net", "Moral Authority"]),
                     strongholds: vec![Biome::Village, Biome::Market],
                     behavior_modifiers: HashMap::from([
                         (BehaviorState::Rest, 1.4),
@@ -411,31 +783,31 @@ impl Default for WorldMetadata {
         .collect();
 
         let economy = EconomyMetadata {
-            circulation_cycle: vec![
+            circulation_cycle: strings(&[
                 "Market Auction",
                 "Guild Bidding",
                 "Village Services",
                 "Desert Expedition",
                 "Market Reflux",
-            ],
-            stressors: vec![
+            ]),
+            stressors: strings(&[
                 "Drought Pressure",
                 "Bandit Raid",
                 "Currency Devaluation",
                 "Relic Shortage",
                 "Plague Spread",
-            ],
-            catalysts: vec![
+            ]),
+            catalysts: strings(&[
                 "Temple Festival",
                 "Explorer Breakthrough",
                 "Guild Tariff Cut",
                 "Union Harvest",
-            ],
+            ]),
         };
 
         let epochs = EpochCadence {
-            day_segments: vec!["Dawn", "Midday", "Dusk"],
-            seasons: vec!["Flower Bloom", "Sunburst Peak", "Ashfall"],
+            day_segments: strings(&["Dawn", "Midday", "Dusk"]),
+            seasons: strings(&["Flower Bloom", "Sunburst Peak", "Ashfall"]),
         };
 
         Self {
@@ -443,7 +815,81 @@ impl Default for WorldMetadata {
             factions,
             economy,
             epochs,
+            nations: NationProfile::roster(5),
             tech_tree: TechTree::default(),
         }
     }
 }
+
+/// Deserializes while tracking the path to the field that failed, so errors
+/// read `biomes.Forest.anchor: invalid type ...` instead of just the message.
+fn parse_with_path<'de, D>(deserializer: D) -> Result<WorldMetadata, String>
+where
+    D: Deserializer<'de>,
+    D::Error: Display,
+{
+    serde_path_to_error::deserialize(deserializer)
+        .map_err(|err| format!("{}: {}", err.path(), err.inner()))
+}
+
+/// Map key read as a bare identifier, which covers TOML keys, JSON strings and
+/// RON enum names alike.
+#[derive(PartialEq, Eq, Hash)]
+struct KeyName(String);
+
+impl<'de> Deserialize<'de> for KeyName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl Visitor<'_> for KeyVisitor {
+            type Value = KeyName;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map key")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<KeyName, E> {
+                Ok(KeyName(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_identifier(KeyVisitor)
+    }
+}
+
+/// Reads an enum-keyed map through its key names. `serde_path_to_error` only
+/// records keys it sees as strings, so this keeps paths like
+/// `biomes.Forest.label` intact.
+fn enum_keyed<'de, D, K, V>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+where
+    D: Deserializer<'de>,
+    K: DeserializeOwned + Eq + Hash,
+    V: Deserialize<'de>,
+{
+    HashMap::<KeyName, V>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(KeyName(key), value)| {
+            K::deserialize(de::value::StrDeserializer::<D::Error>::new(&key))
+                .map(|key| (key, value))
+                .map_err(|err| de::Error::custom(format_args!("key `{key}`: {err}")))
+        })
+        .collect()
+}
+
+fn check_positive(problems: &mut Vec<String>, field: &str, value: f32) {
+    if !value.is_finite() || value <= 0.0 {
+        problems.push(format!("{field}: must be a positive number (got {value})"));
+    }
+}
+
+fn check_non_negative(problems: &mut Vec<String>, field: &str, value: f32) {
+    if !value.is_finite() || value < 0.0 {
+        problems.push(format!(
+            "{field}: must be a non-negative number (got {value})"
+        ));
+    }
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
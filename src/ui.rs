@@ -1,12 +1,18 @@
 mod charts;
+mod command;
 mod control;
+mod intervention;
 mod map;
 mod panels;
 
 use crate::simulation::events::WorldEventKind;
-use crate::simulation::{AxialCoord, Nation, ObserverSnapshot, format_number_commas};
+use crate::simulation::{AxialCoord, Metric, Nation, ObserverSnapshot, format_number_commas};
 use charts::render_indicator_grid;
+pub use command::CommandRow;
+use command::render_command_panel;
 use control::render_control_deck;
+use intervention::render_intervention_panel;
+pub use intervention::{InterventionRow, next_metric, scale_metric};
 use map::MapWidget;
 use panels::{render_event_leaderboard, render_world_state_panel};
 use ratatui::{
@@ -57,6 +63,18 @@ pub struct ControlState {
     pub pinned_nation: Option<Nation>,
     pub log_pin_selected: bool,
     pub focus_mode: bool,
+    /// The command panel for the played nation is open.
+    pub command_open: bool,
+    /// Row of the command panel under the cursor.
+    pub command_row: usize,
+    /// The observer's intervention panel is open.
+    pub intervene_open: bool,
+    /// Row of the intervention panel under the cursor.
+    pub intervene_row: usize,
+    /// Metric the intervention panel's metric row adjusts.
+    pub intervene_metric: Metric,
+    /// Outcome of the latest quicksave/quickload, if any.
+    pub persistence_status: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -74,6 +92,7 @@ pub enum MapOverlay {
     Ownership,
     Climate,
     Conflict,
+    Trade,
 }
 
 impl MapOverlay {
@@ -82,6 +101,7 @@ impl MapOverlay {
             MapOverlay::Ownership => "Territory/Leader",
             MapOverlay::Climate => "Climate/Sea",
             MapOverlay::Conflict => "Conflict/Fatigue",
+            MapOverlay::Trade => "Trade Routes",
         }
     }
 
@@ -89,15 +109,17 @@ impl MapOverlay {
         match self {
             MapOverlay::Ownership => MapOverlay::Climate,
             MapOverlay::Climate => MapOverlay::Conflict,
-            MapOverlay::Conflict => MapOverlay::Ownership,
+            MapOverlay::Conflict => MapOverlay::Trade,
+            MapOverlay::Trade => MapOverlay::Ownership,
         }
     }
 
     pub fn prev(self) -> Self {
         match self {
-            MapOverlay::Ownership => MapOverlay::Conflict,
+            MapOverlay::Ownership => MapOverlay::Trade,
             MapOverlay::Climate => MapOverlay::Ownership,
             MapOverlay::Conflict => MapOverlay::Climate,
+            MapOverlay::Trade => MapOverlay::Conflict,
         }
     }
 }
@@ -262,10 +284,21 @@ pub fn render(frame: &mut Frame, snapshot: &ObserverSnapshot, control: &ControlS
         .border_style(Style::default().fg(MODERN_THEME.border));
     let graph_area = graph_block.inner(left_column[1]);
     frame.render_widget(graph_block, left_column[1]);
-    render_indicator_grid(frame, graph_area, snapshot);
+    render_indicator_grid(
+        frame,
+        graph_area,
+        snapshot,
+        control.pinned_nation.or(control.selected_owner),
+    );
 
     // --- World State Panel ---
     render_world_state_panel(frame, right_column[0], snapshot, control);
+    if control.command_open {
+        render_command_panel(frame, right_column[0], snapshot, control);
+    }
+    if control.intervene_open {
+        render_intervention_panel(frame, right_column[0], snapshot, control);
+    }
 
     // --- Event Log Panel ---
     // Event Log - Using a Table for alignment
@@ -299,40 +332,115 @@ pub fn render(frame: &mut Frame, snapshot: &ObserverSnapshot, control: &ControlS
         .map(|event| {
             let (nation_cell, base_color) = match &event.kind {
                 WorldEventKind::Trade { actor, .. } => (
-                    Cell::from(actor.nation.name())
-                        .style(Style::default().fg(actor.nation.color())),
+                    Cell::from(snapshot.nations.name(actor.nation))
+                        .style(Style::default().fg(snapshot.nations.color(actor.nation))),
                     MODERN_THEME.success,
                 ),
                 WorldEventKind::Social { convener, .. } => (
-                    Cell::from(convener.nation.name())
-                        .style(Style::default().fg(convener.nation.color())),
+                    Cell::from(snapshot.nations.name(convener.nation))
+                        .style(Style::default().fg(snapshot.nations.color(convener.nation))),
                     MODERN_THEME.accent_a,
                 ),
                 WorldEventKind::MacroShock { .. } => (Cell::from("System"), MODERN_THEME.warning),
                 WorldEventKind::Warfare { winner, .. } => (
-                    Cell::from(winner.name()).style(Style::default().fg(winner.color())),
+                    Cell::from(snapshot.nations.name(*winner))
+                        .style(Style::default().fg(snapshot.nations.color(*winner))),
+                    MODERN_THEME.danger,
+                ),
+                WorldEventKind::NuclearStrike { attacker, .. } => (
+                    Cell::from(snapshot.nations.name(*attacker))
+                        .style(Style::default().fg(snapshot.nations.color(*attacker))),
+                    MODERN_THEME.danger,
+                ),
+                WorldEventKind::WarDeclared { attacker, .. } => (
+                    Cell::from(snapshot.nations.name(*attacker))
+                        .style(Style::default().fg(snapshot.nations.color(*attacker))),
                     MODERN_THEME.danger,
                 ),
+                WorldEventKind::HexesCeded { to, .. } => (
+                    Cell::from(snapshot.nations.name(*to))
+                        .style(Style::default().fg(snapshot.nations.color(*to))),
+                    MODERN_THEME.warning,
+                ),
+                WorldEventKind::ReparationsPaid { payee, .. } => (
+                    Cell::from(snapshot.nations.name(*payee))
+                        .style(Style::default().fg(snapshot.nations.color(*payee))),
+                    MODERN_THEME.warning,
+                ),
+                WorldEventKind::TruceSigned { a, .. } => (
+                    Cell::from(snapshot.nations.name(*a))
+                        .style(Style::default().fg(snapshot.nations.color(*a))),
+                    MODERN_THEME.success,
+                ),
+                WorldEventKind::BlocChanged { nation, .. } => (
+                    Cell::from(snapshot.nations.name(*nation))
+                        .style(Style::default().fg(snapshot.nations.color(*nation))),
+                    MODERN_THEME.accent_a,
+                ),
+                WorldEventKind::TreatyBroken { breaker, .. } => (
+                    Cell::from(snapshot.nations.name(*breaker))
+                        .style(Style::default().fg(snapshot.nations.color(*breaker))),
+                    MODERN_THEME.warning,
+                ),
+                WorldEventKind::AllianceDissolved { a, .. } => (
+                    Cell::from(snapshot.nations.name(*a))
+                        .style(Style::default().fg(snapshot.nations.color(*a))),
+                    MODERN_THEME.warning,
+                ),
+                WorldEventKind::SanctionsLifted { issuer, .. } => (
+                    Cell::from(snapshot.nations.name(*issuer))
+                        .style(Style::default().fg(snapshot.nations.color(*issuer))),
+                    MODERN_THEME.success,
+                ),
                 WorldEventKind::EraShift { nation, .. } => (
-                    Cell::from(nation.name()).style(Style::default().fg(nation.color())),
+                    Cell::from(snapshot.nations.name(*nation))
+                        .style(Style::default().fg(snapshot.nations.color(*nation))),
                     MODERN_THEME.accent_b,
                 ),
+                WorldEventKind::TechDiscovered { nation, .. } => (
+                    Cell::from(snapshot.nations.name(*nation))
+                        .style(Style::default().fg(snapshot.nations.color(*nation))),
+                    MODERN_THEME.accent_a,
+                ),
                 WorldEventKind::ScienceProgress { nation, .. } => (
-                    Cell::from(nation.name()).style(Style::default().fg(nation.color())),
+                    Cell::from(snapshot.nations.name(*nation))
+                        .style(Style::default().fg(snapshot.nations.color(*nation))),
                     MODERN_THEME.accent_a,
                 ),
                 WorldEventKind::ScienceVictory { winner, .. } => (
-                    Cell::from(winner.name()).style(Style::default().fg(winner.color())),
+                    Cell::from(snapshot.nations.name(*winner))
+                        .style(Style::default().fg(snapshot.nations.color(*winner))),
                     MODERN_THEME.success,
                 ),
                 WorldEventKind::InterstellarProgress { leader, .. } => (
-                    Cell::from(leader.name()).style(Style::default().fg(leader.color())),
+                    Cell::from(snapshot.nations.name(*leader))
+                        .style(Style::default().fg(snapshot.nations.color(*leader))),
                     MODERN_THEME.accent_a,
                 ),
                 WorldEventKind::InterstellarVictory { winner, .. } => (
-                    Cell::from(winner.name()).style(Style::default().fg(winner.color())),
+                    Cell::from(snapshot.nations.name(*winner))
+                        .style(Style::default().fg(snapshot.nations.color(*winner))),
+                    MODERN_THEME.success,
+                ),
+                WorldEventKind::TradeRoute { a, .. } => (
+                    Cell::from(snapshot.nations.name(*a))
+                        .style(Style::default().fg(snapshot.nations.color(*a))),
                     MODERN_THEME.success,
                 ),
+                WorldEventKind::SovereignDefault { nation, .. }
+                | WorldEventKind::EconomicCrisis { nation, .. } => (
+                    Cell::from(snapshot.nations.name(*nation))
+                        .style(Style::default().fg(snapshot.nations.color(*nation))),
+                    MODERN_THEME.danger,
+                ),
+                WorldEventKind::EconomicRecovery { nation, .. } => (
+                    Cell::from(snapshot.nations.name(*nation))
+                        .style(Style::default().fg(snapshot.nations.color(*nation))),
+                    MODERN_THEME.success,
+                ),
+                WorldEventKind::Intervention { .. } => {
+                    (Cell::from("Observer"), MODERN_THEME.accent_b)
+                }
             };
 
             let pinned_hit = control
@@ -377,48 +485,191 @@ pub fn render(frame: &mut Frame, snapshot: &ObserverSnapshot, control: &ControlS
                 WorldEventKind::Warfare {
                     winner,
                     loser,
-                    territory_change,
+                    hexes_captured,
                     casualties,
                     nuclear,
+                    ..
                 } => (
-                    winner.name().to_string(),
-                    format!("vs {}", loser.name()),
+                    snapshot.nations.name(*winner).to_string(),
+                    format!("vs {}", snapshot.nations.name(*loser)),
                     format!(
-                        "+{:.1}km² | Kill {}{}",
-                        territory_change,
+                        "+{} hex | Kill {}{}",
+                        hexes_captured,
                         format_number_commas(*casualties),
                         if *nuclear { " [NUKE]" } else { "" }
                     ),
                 ),
+                WorldEventKind::NuclearStrike {
+                    attacker,
+                    target,
+                    warheads,
+                    casualties,
+                    retaliation,
+                } => (
+                    snapshot.nations.name(*attacker).to_string(),
+                    format!(
+                        "{} {}",
+                        if *retaliation {
+                            "strikes back at"
+                        } else {
+                            "nukes"
+                        },
+                        snapshot.nations.name(*target)
+                    ),
+                    format!(
+                        "{} warheads | Kill {}",
+                        warheads,
+                        format_number_commas(*casualties)
+                    ),
+                ),
+                WorldEventKind::WarDeclared {
+                    attacker,
+                    defender,
+                    casus_belli,
+                    allies,
+                    ..
+                } => (
+                    snapshot.nations.name(*attacker).to_string(),
+                    format!("declares on {}", snapshot.nations.name(*defender)),
+                    format!("{} | +{} allies", casus_belli.label(), allies),
+                ),
+                WorldEventKind::HexesCeded { from, to, hexes } => (
+                    snapshot.nations.name(*to).to_string(),
+                    format!("annexes from {}", snapshot.nations.name(*from)),
+                    format!("+{hexes} hex"),
+                ),
+                WorldEventKind::ReparationsPaid {
+                    payer,
+                    payee,
+                    amount,
+                } => (
+                    snapshot.nations.name(*payee).to_string(),
+                    format!("paid by {}", snapshot.nations.name(*payer)),
+                    format!("+{amount:.1} economy"),
+                ),
+                WorldEventKind::TruceSigned { a, b, until } => (
+                    snapshot.nations.name(*a).to_string(),
+                    format!("truce with {}", snapshot.nations.name(*b)),
+                    format!("until tick {until}"),
+                ),
+                WorldEventKind::BlocChanged {
+                    bloc,
+                    bloc_kind,
+                    nation,
+                    change,
+                } => (
+                    snapshot.nations.name(*nation).to_string(),
+                    format!("{} {}", change.label(), bloc),
+                    bloc_kind.label().to_string(),
+                ),
+                WorldEventKind::TreatyBroken {
+                    breaker,
+                    ally,
+                    treaty,
+                } => (
+                    snapshot.nations.name(*breaker).to_string(),
+                    format!("abandons {}", snapshot.nations.name(*ally)),
+                    format!("breaks {treaty}"),
+                ),
+                WorldEventKind::AllianceDissolved { a, b, reason } => (
+                    snapshot.nations.name(*a).to_string(),
+                    format!("alliance with {} ends", snapshot.nations.name(*b)),
+                    reason.label().to_string(),
+                ),
+                WorldEventKind::SanctionsLifted {
+                    issuer,
+                    target,
+                    concession,
+                } => (
+                    snapshot.nations.name(*issuer).to_string(),
+                    format!("lifts sanctions on {}", snapshot.nations.name(*target)),
+                    format!("concession {concession:.1}"),
+                ),
                 WorldEventKind::EraShift {
                     nation,
                     era,
                     weapon,
                 } => (
-                    nation.name().to_string(),
+                    snapshot.nations.name(*nation).to_string(),
                     era.label().to_string(),
                     weapon.label().to_string(),
                 ),
+                WorldEventKind::TechDiscovered { nation, tech } => (
+                    snapshot.nations.name(*nation).to_string(),
+                    "Discovery".to_string(),
+                    tech.label().to_string(),
+                ),
                 WorldEventKind::ScienceProgress { nation, progress } => (
-                    nation.name().to_string(),
+                    snapshot.nations.name(*nation).to_string(),
                     "Moon Project".to_string(),
                     format!("{progress:.1}%"),
                 ),
                 WorldEventKind::ScienceVictory { winner, progress } => (
-                    winner.name().to_string(),
+                    snapshot.nations.name(*winner).to_string(),
                     "Science Win".to_string(),
                     format!("{progress:.1}%"),
                 ),
                 WorldEventKind::InterstellarProgress { leader, progress } => (
-                    leader.name().to_string(),
+                    snapshot.nations.name(*leader).to_string(),
                     "Interstellar".to_string(),
                     format!("{progress:.1}%"),
                 ),
                 WorldEventKind::InterstellarVictory { winner, progress } => (
-                    winner.name().to_string(),
+                    snapshot.nations.name(*winner).to_string(),
                     "Galactic Win".to_string(),
                     format!("{progress:.1}%"),
                 ),
+                WorldEventKind::TradeRoute {
+                    a,
+                    b,
+                    change,
+                    volume,
+                } => (
+                    snapshot.nations.name(*a).to_string(),
+                    format!("route to {} {}", snapshot.nations.name(*b), change.label()),
+                    format!("volume {volume:.1}"),
+                ),
+                WorldEventKind::SovereignDefault {
+                    nation,
+                    debt,
+                    haircut,
+                } => (
+                    snapshot.nations.name(*nation).to_string(),
+                    "Default".to_string(),
+                    format!("debt {debt:.1}, -{haircut:.1}"),
+                ),
+                WorldEventKind::EconomicCrisis {
+                    nation,
+                    crisis,
+                    origin,
+                    output_gap,
+                } => (
+                    snapshot.nations.name(*nation).to_string(),
+                    match origin {
+                        Some(origin) => {
+                            format!("{} from {}", crisis.label(), snapshot.nations.name(*origin))
+                        }
+                        None => crisis.label().to_string(),
+                    },
+                    format!("gap {output_gap:+.1}%"),
+                ),
+                WorldEventKind::EconomicRecovery {
+                    nation,
+                    crisis,
+                    ticks,
+                } => (
+                    snapshot.nations.name(*nation).to_string(),
+                    format!("{} over", crisis.label()),
+                    format!("{ticks} ticks"),
+                ),
+                WorldEventKind::Intervention {
+                    intervention,
+                    summary,
+                } => (
+                    "Observer".to_string(),
+                    intervention.label().to_string(),
+                    summary.clone(),
+                ),
             };
 
             let cells = vec![
@@ -482,14 +733,25 @@ fn filter_event(
 ) -> bool {
     let passes = match filter {
         LogFilter::All => true,
-        LogFilter::War => matches!(event.kind, WorldEventKind::Warfare { .. }),
+        LogFilter::War => matches!(
+            event.kind,
+            WorldEventKind::Warfare { .. }
+                | WorldEventKind::NuclearStrike { .. }
+                | WorldEventKind::WarDeclared { .. }
+                | WorldEventKind::HexesCeded { .. }
+                | WorldEventKind::ReparationsPaid { .. }
+                | WorldEventKind::TruceSigned { .. }
+        ),
         LogFilter::TradeSocial => matches!(
             event.kind,
-            WorldEventKind::Trade { .. } | WorldEventKind::Social { .. }
+            WorldEventKind::Trade { .. }
+                | WorldEventKind::Social { .. }
+                | WorldEventKind::TradeRoute { .. }
         ),
         LogFilter::ScienceSpace => matches!(
             event.kind,
             WorldEventKind::ScienceProgress { .. }
+                | WorldEventKind::TechDiscovered { .. }
                 | WorldEventKind::ScienceVictory { .. }
                 | WorldEventKind::InterstellarProgress { .. }
                 | WorldEventKind::InterstellarVictory { .. }
@@ -498,7 +760,14 @@ fn filter_event(
             event.kind,
             WorldEventKind::EraShift { .. }
                 | WorldEventKind::MacroShock { .. }
+                | WorldEventKind::SovereignDefault { .. }
+                | WorldEventKind::EconomicCrisis { .. }
+                | WorldEventKind::EconomicRecovery { .. }
                 | WorldEventKind::Social { .. }
+                | WorldEventKind::BlocChanged { .. }
+                | WorldEventKind::TreatyBroken { .. }
+                | WorldEventKind::AllianceDissolved { .. }
+                | WorldEventKind::SanctionsLifted { .. }
         ),
     };
     if !passes {
@@ -519,11 +788,38 @@ fn event_involves(
         WorldEventKind::Social { convener, .. } => convener.nation == nation,
         WorldEventKind::MacroShock { .. } => false,
         WorldEventKind::Warfare { winner, loser, .. } => *winner == nation || *loser == nation,
+        WorldEventKind::NuclearStrike {
+            attacker, target, ..
+        } => *attacker == nation || *target == nation,
+        WorldEventKind::WarDeclared {
+            attacker, defender, ..
+        } => *attacker == nation || *defender == nation,
+        WorldEventKind::HexesCeded { from, to, .. } => *from == nation || *to == nation,
+        WorldEventKind::ReparationsPaid { payer, payee, .. } => {
+            *payer == nation || *payee == nation
+        }
+        WorldEventKind::TruceSigned { a, b, .. } => *a == nation || *b == nation,
+        WorldEventKind::BlocChanged { nation: n, .. } => *n == nation,
+        WorldEventKind::TreatyBroken { breaker, ally, .. } => *breaker == nation || *ally == nation,
+        WorldEventKind::AllianceDissolved { a, b, .. } => *a == nation || *b == nation,
+        WorldEventKind::SanctionsLifted { issuer, target, .. } => {
+            *issuer == nation || *target == nation
+        }
         WorldEventKind::EraShift { nation: n, .. } => *n == nation,
+        WorldEventKind::TechDiscovered { nation: n, .. } => *n == nation,
         WorldEventKind::ScienceProgress { nation: n, .. } => *n == nation,
         WorldEventKind::ScienceVictory { winner, .. } => *winner == nation,
         WorldEventKind::InterstellarProgress { leader, .. } => *leader == nation,
         WorldEventKind::InterstellarVictory { winner, .. } => *winner == nation,
+        WorldEventKind::TradeRoute { a, b, .. } => *a == nation || *b == nation,
+        WorldEventKind::SovereignDefault { nation: n, .. }
+        | WorldEventKind::EconomicRecovery { nation: n, .. } => *n == nation,
+        WorldEventKind::EconomicCrisis {
+            nation: n, origin, ..
+        } => *n == nation || *origin == Some(nation),
+        WorldEventKind::Intervention { intervention, .. } => {
+            intervention.nations().contains(&nation)
+        }
     }
 }
 
@@ -581,7 +877,7 @@ fn render_diagnostics_strip(
                 "PIN: {}",
                 control
                     .pinned_nation
-                    .map(|n| n.name().to_string())
+                    .map(|n| snapshot.nations.name(n).to_string())
                     .unwrap_or_else(|| "None".to_string())
             ),
             Style::default().fg(MODERN_THEME.accent_b),
@@ -621,14 +917,22 @@ fn narrative_ticker(snapshot: &ObserverSnapshot) -> String {
             WorldEventKind::Trade {
                 actor, trade_focus, ..
             } => {
-                format!("{} Trade {}", actor.nation.name(), trade_focus)
+                format!(
+                    "{} Trade {}",
+                    snapshot.nations.name(actor.nation),
+                    trade_focus
+                )
             }
             WorldEventKind::Social {
                 convener,
                 gathering_theme,
                 ..
             } => {
-                format!("{} {}", convener.nation.name(), gathering_theme)
+                format!(
+                    "{} {}",
+                    snapshot.nations.name(convener.nation),
+                    gathering_theme
+                )
             }
             WorldEventKind::MacroShock {
                 stressor,
@@ -645,25 +949,120 @@ fn narrative_ticker(snapshot: &ObserverSnapshot) -> String {
             } => {
                 format!(
                     "{} vs {} {}",
-                    winner.name(),
-                    loser.name(),
+                    snapshot.nations.name(*winner),
+                    snapshot.nations.name(*loser),
                     if *nuclear { "[NUKE]" } else { "" }
                 )
             }
+            WorldEventKind::NuclearStrike {
+                attacker, target, ..
+            } => {
+                format!(
+                    "{} nukes {} [NUKE]",
+                    snapshot.nations.name(*attacker),
+                    snapshot.nations.name(*target)
+                )
+            }
+            WorldEventKind::WarDeclared {
+                attacker, defender, ..
+            } => {
+                format!(
+                    "{} declares war on {}",
+                    snapshot.nations.name(*attacker),
+                    snapshot.nations.name(*defender)
+                )
+            }
+            WorldEventKind::HexesCeded { from, to, hexes } => {
+                format!(
+                    "{} cedes {} to {}",
+                    snapshot.nations.name(*from),
+                    hexes,
+                    snapshot.nations.name(*to)
+                )
+            }
+            WorldEventKind::ReparationsPaid { payer, payee, .. } => {
+                format!(
+                    "{} pays {}",
+                    snapshot.nations.name(*payer),
+                    snapshot.nations.name(*payee)
+                )
+            }
+            WorldEventKind::TruceSigned { a, b, .. } => {
+                format!(
+                    "{} / {} truce",
+                    snapshot.nations.name(*a),
+                    snapshot.nations.name(*b)
+                )
+            }
+            WorldEventKind::BlocChanged {
+                bloc,
+                nation,
+                change,
+                ..
+            } => {
+                format!(
+                    "{} {} {}",
+                    snapshot.nations.name(*nation),
+                    change.label(),
+                    bloc
+                )
+            }
+            WorldEventKind::TreatyBroken {
+                breaker, treaty, ..
+            } => {
+                format!("{} breaks {}", snapshot.nations.name(*breaker), treaty)
+            }
+            WorldEventKind::AllianceDissolved { a, b, .. } => {
+                format!(
+                    "{} / {} split",
+                    snapshot.nations.name(*a),
+                    snapshot.nations.name(*b)
+                )
+            }
+            WorldEventKind::SanctionsLifted { issuer, target, .. } => {
+                format!(
+                    "{} lifts {} sanctions",
+                    snapshot.nations.name(*issuer),
+                    snapshot.nations.name(*target)
+                )
+            }
             WorldEventKind::EraShift { nation, era, .. } => {
-                format!("{} Era {}", nation.name(), era.label())
+                format!("{} Era {}", snapshot.nations.name(*nation), era.label())
+            }
+            WorldEventKind::TechDiscovered { nation, tech } => {
+                format!("{} {}", snapshot.nations.name(*nation), tech.label())
             }
             WorldEventKind::ScienceProgress { nation, progress } => {
-                format!("{} Moon {:.0}%", nation.name(), progress)
+                format!("{} Moon {:.0}%", snapshot.nations.name(*nation), progress)
             }
             WorldEventKind::ScienceVictory { winner, .. } => {
-                format!("{} Science Win", winner.name())
+                format!("{} Science Win", snapshot.nations.name(*winner))
             }
             WorldEventKind::InterstellarProgress { leader, progress } => {
-                format!("{} Space {:.0}%", leader.name(), progress)
+                format!("{} Space {:.0}%", snapshot.nations.name(*leader), progress)
             }
             WorldEventKind::InterstellarVictory { winner, .. } => {
-                format!("{} Galactic Civ", winner.name())
+                format!("{} Galactic Civ", snapshot.nations.name(*winner))
+            }
+            WorldEventKind::TradeRoute { a, b, change, .. } => {
+                format!(
+                    "{} / {} route {}",
+                    snapshot.nations.name(*a),
+                    snapshot.nations.name(*b),
+                    change.label()
+                )
+            }
+            WorldEventKind::SovereignDefault { nation, .. } => {
+                format!("{} Default", snapshot.nations.name(*nation))
+            }
+            WorldEventKind::EconomicCrisis { nation, crisis, .. } => {
+                format!("{} {}", snapshot.nations.name(*nation), crisis.label())
+            }
+            WorldEventKind::EconomicRecovery { nation, .. } => {
+                format!("{} Recovery", snapshot.nations.name(*nation))
+            }
+            WorldEventKind::Intervention { intervention, .. } => {
+                format!("Observer {}", intervention.label())
             }
         };
         snippets.push(snippet);
@@ -5,8 +5,8 @@ use ratatui::{
     widgets::{BarChart, Block, BorderType, Paragraph, Sparkline},
 };
 
-use crate::simulation::ObserverSnapshot;
 use crate::simulation::events::WorldEventKind;
+use crate::simulation::{Nation, ObserverSnapshot, RouteChange};
 use crate::ui::MODERN_THEME;
 
 /// Evolutionary, climate, and sentiment charts.
@@ -207,8 +207,14 @@ pub fn render_evolutionary_charts(frame: &mut Frame, area: Rect, snapshot: &Obse
     frame.render_widget(pop, lanes[6]);
 }
 
-/// Additional graph lane for dramatic indicator swings.
-pub fn render_indicator_grid(frame: &mut Frame, area: Rect, snapshot: &ObserverSnapshot) {
+/// Additional graph lane for dramatic indicator swings. The supply chart
+/// follows `focus`, or else the nation longest in deficit.
+pub fn render_indicator_grid(
+    frame: &mut Frame,
+    area: Rect,
+    snapshot: &ObserverSnapshot,
+    focus: Option<Nation>,
+) {
     let block = Block::bordered()
         .style(Style::default().bg(MODERN_THEME.panel_bg))
         .title(" [ SENSOR GRID ] Pulseboard ")
@@ -227,9 +233,10 @@ pub fn render_indicator_grid(frame: &mut Frame, area: Rect, snapshot: &ObserverS
     let top = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Ratio(1, 3),
-            Constraint::Ratio(1, 3),
-            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(1, 4),
         ])
         .split(rows[0]);
 
@@ -286,6 +293,46 @@ pub fn render_indicator_grid(frame: &mut Frame, area: Rect, snapshot: &ObserverS
         .style(Style::default().fg(MODERN_THEME.success));
     frame.render_widget(population_line, top[2]);
 
+    let supply_nation = focus.or_else(|| {
+        snapshot
+            .supply
+            .iter()
+            .max_by_key(|(_, chain)| chain.deficit_ticks)
+            .map(|(nation, _)| *nation)
+    });
+    let chain = supply_nation.and_then(|nation| snapshot.supply.get(&nation));
+    let food: Vec<f32> = chain
+        .map(|chain| chain.history.iter().map(|output| output.food).collect())
+        .unwrap_or_default();
+    let food_series = series_from_history(&food, 1.0);
+    let (title, color) = match (supply_nation, chain) {
+        (Some(nation), Some(chain)) => (
+            format!(
+                " Food · {} (stock {:.0}) ",
+                snapshot.nations.name(nation),
+                chain.stockpile.food
+            ),
+            if chain.deficit_ticks > 0 {
+                MODERN_THEME.danger
+            } else {
+                MODERN_THEME.success
+            },
+        ),
+        _ => (" Food ".to_string(), MODERN_THEME.success),
+    };
+    let supply_line = Sparkline::default()
+        .block(
+            Block::bordered()
+                .style(Style::default().bg(MODERN_THEME.panel_bg))
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(MODERN_THEME.border))
+                .title(title),
+        )
+        .data(&food_series)
+        .max(food_series.iter().cloned().max().unwrap_or(1))
+        .style(Style::default().fg(color));
+    frame.render_widget(supply_line, top[3]);
+
     let bottom = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -368,7 +415,7 @@ pub fn render_science_progress_panel(frame: &mut Frame, area: Rect, snapshot: &O
     let leader_name = snapshot
         .science_victory
         .leader
-        .map(|n| n.name().to_string())
+        .map(|n| snapshot.nations.name(n).to_string())
         .unwrap_or_else(|| "TBD".to_string());
     let leader_progress = snapshot
         .science_victory
@@ -449,12 +496,30 @@ pub fn build_sentiment_series(
     for event in &snapshot.events {
         let index = (event.tick / bucket_size).min((buckets - 1) as u64) as usize;
         let delta = match event.kind {
-            WorldEventKind::MacroShock { .. } | WorldEventKind::Warfare { .. } => -2,
+            WorldEventKind::NuclearStrike { .. } => -3,
+            WorldEventKind::MacroShock { .. }
+            | WorldEventKind::SovereignDefault { .. }
+            | WorldEventKind::EconomicCrisis { .. }
+            | WorldEventKind::Warfare { .. }
+            | WorldEventKind::WarDeclared { .. } => -2,
+            WorldEventKind::TreatyBroken { .. } | WorldEventKind::AllianceDissolved { .. } => -1,
+            WorldEventKind::SanctionsLifted { .. } => 1,
+            WorldEventKind::HexesCeded { .. }
+            | WorldEventKind::ReparationsPaid { .. }
+            | WorldEventKind::BlocChanged { .. } => 0,
+            WorldEventKind::TruceSigned { .. } => 2,
             WorldEventKind::ScienceVictory { .. } | WorldEventKind::InterstellarVictory { .. } => 3,
             WorldEventKind::ScienceProgress { .. }
             | WorldEventKind::InterstellarProgress { .. }
             | WorldEventKind::EraShift { .. } => 2,
+            WorldEventKind::TechDiscovered { .. } => 1,
             WorldEventKind::Trade { .. } | WorldEventKind::Social { .. } => 1,
+            WorldEventKind::TradeRoute { change, .. } => match change {
+                RouteChange::Opened => 1,
+                RouteChange::Severed | RouteChange::Embargoed | RouteChange::Lapsed => -1,
+            },
+            WorldEventKind::EconomicRecovery { .. } => 1,
+            WorldEventKind::Intervention { .. } => 0,
         };
         series[index] += delta;
     }
@@ -508,7 +573,7 @@ where
         .iter()
         .map(|(nation, metrics)| {
             (
-                nation.name().to_string(),
+                snapshot.nations.name(*nation).to_string(),
                 selector(metrics).max(0.0).round() as u64,
             )
         })
@@ -0,0 +1,195 @@
+use ratatui::{
+    prelude::*,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Wrap},
+};
+
+use super::{ControlState, MODERN_THEME};
+use crate::simulation::{BudgetLine, MAX_TAX, Nation, ObserverSnapshot, PlayerCommand, Treasury};
+use crate::ui::charts::heat_bar;
+
+/// Budget points, and points of revenue spent, one Left/Right press moves.
+const BUDGET_STEP: i8 = 5;
+/// Points of tax rate one Left/Right press moves.
+const TAX_STEP: i8 = 1;
+
+/// One row of the command panel: a budget line, the tax rate or the level
+/// of spending to adjust, or an order to issue against the nation selected
+/// on the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandRow {
+    Budget(BudgetLine),
+    TaxRate,
+    Spending,
+    DeclareWar,
+    OfferPeace,
+    ProposeAlliance,
+    Sanction,
+}
+
+impl CommandRow {
+    pub const ALL: [CommandRow; 11] = [
+        CommandRow::Budget(BudgetLine::Military),
+        CommandRow::Budget(BudgetLine::Science),
+        CommandRow::Budget(BudgetLine::Culture),
+        CommandRow::Budget(BudgetLine::Diplomacy),
+        CommandRow::Budget(BudgetLine::Welfare),
+        CommandRow::TaxRate,
+        CommandRow::Spending,
+        CommandRow::DeclareWar,
+        CommandRow::OfferPeace,
+        CommandRow::ProposeAlliance,
+        CommandRow::Sanction,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CommandRow::Budget(line) => line.label(),
+            CommandRow::TaxRate => "Tax rate",
+            CommandRow::Spending => "Spending",
+            CommandRow::DeclareWar => "Declare war on",
+            CommandRow::OfferPeace => "Offer peace to",
+            CommandRow::ProposeAlliance => "Propose alliance to",
+            CommandRow::Sanction => "Sanction",
+        }
+    }
+
+    /// The order a Left (`raise` false) or Right press issues on this row;
+    /// order rows issue none.
+    pub fn shift(self, raise: bool) -> Option<PlayerCommand> {
+        let sign = if raise { 1 } else { -1 };
+        match self {
+            CommandRow::Budget(line) => Some(PlayerCommand::ShiftBudget {
+                line,
+                delta: sign * BUDGET_STEP,
+            }),
+            CommandRow::TaxRate => Some(PlayerCommand::ShiftTax {
+                delta: sign * TAX_STEP,
+            }),
+            CommandRow::Spending => Some(PlayerCommand::ShiftSpending {
+                delta: sign * BUDGET_STEP,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The order this row issues against `target`; budget rows issue none.
+    pub fn order(self, target: Nation) -> Option<PlayerCommand> {
+        match self {
+            CommandRow::Budget(_) | CommandRow::TaxRate | CommandRow::Spending => None,
+            CommandRow::DeclareWar => Some(PlayerCommand::DeclareWar { target }),
+            CommandRow::OfferPeace => Some(PlayerCommand::OfferPeace { enemy: target }),
+            CommandRow::ProposeAlliance => Some(PlayerCommand::ProposeAlliance { partner: target }),
+            CommandRow::Sanction => Some(PlayerCommand::Sanction { target }),
+        }
+    }
+}
+
+/// Draws the command panel for the played nation over `area`.
+pub fn render_command_panel(
+    frame: &mut Frame,
+    area: Rect,
+    snapshot: &ObserverSnapshot,
+    control: &ControlState,
+) {
+    let player = &snapshot.player;
+    let title = match player.nation {
+        Some(nation) => format!(" COMMAND · {} ", snapshot.nations.name(nation)),
+        None => " COMMAND ".to_string(),
+    };
+    let block = Block::bordered()
+        .style(Style::default().bg(MODERN_THEME.panel_bg))
+        .title(title)
+        .title_style(Style::default().fg(MODERN_THEME.accent_b).bold())
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(MODERN_THEME.accent_b));
+
+    let treasury = player
+        .nation
+        .and_then(|n| snapshot.treasuries.get(&n))
+        .map_or_else(Treasury::default, Treasury::clone);
+    let mut lines = Vec::new();
+    match player.nation.and_then(|n| snapshot.all_metrics.0.get(&n)) {
+        Some(metrics) => {
+            lines.push(Line::from(format!(
+                "Economy {:.1} | Military {:.1} | Science {:.1} | Culture {:.1} | Diplomacy {:.1}",
+                metrics.economy,
+                metrics.military,
+                metrics.science,
+                metrics.culture,
+                metrics.diplomacy
+            )));
+            lines.push(Line::from(format!(
+                "Revenue {:.2} | Spending {:.2} | Interest {:.2} | Debt {:.1}",
+                treasury.revenue, treasury.spending, treasury.interest, treasury.debt
+            )));
+        }
+        None => lines.push(Line::from(Span::styled(
+            "No nation in command — select one on the map and press T",
+            Style::default().fg(MODERN_THEME.warning),
+        ))),
+    }
+    let target = control
+        .selected_owner
+        .filter(|target| Some(*target) != player.nation);
+    let target_name = target.map_or("(select a nation on the map)", |t| snapshot.nations.name(t));
+    lines.push(Line::from(""));
+    for (index, row) in CommandRow::ALL.into_iter().enumerate() {
+        let selected = index == control.command_row;
+        let marker = if selected { "▶ " } else { "  " };
+        let text = match row {
+            CommandRow::Budget(line) => {
+                let share = treasury.budget.share(line);
+                format!(
+                    "{marker}{:<10} {} {:>3}%",
+                    row.label(),
+                    heat_bar(u64::from(share), 100, 20),
+                    share
+                )
+            }
+            CommandRow::TaxRate => {
+                let rate = (treasury.tax_rate * 100.0).round() as u64;
+                format!(
+                    "{marker}{:<10} {} {:>3}%",
+                    row.label(),
+                    heat_bar(rate, (MAX_TAX * 100.0) as u64, 20),
+                    rate
+                )
+            }
+            CommandRow::Spending => {
+                let ratio = (treasury.spending_ratio * 100.0).round() as u64;
+                format!(
+                    "{marker}{:<10} {} {:>3}% of revenue",
+                    row.label(),
+                    heat_bar(ratio, 150, 20),
+                    ratio
+                )
+            }
+            _ => format!("{marker}{} {}", row.label(), target_name),
+        };
+        let style = if selected {
+            Style::default().fg(MODERN_THEME.accent_a).bold()
+        } else {
+            Style::default().fg(MODERN_THEME.text_main)
+        };
+        lines.push(Line::from(Span::styled(text, style)));
+    }
+    lines.push(Line::from(""));
+    if let Some(report) = &player.report {
+        lines.push(Line::from(Span::styled(
+            report.clone(),
+            Style::default().fg(MODERN_THEME.success),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        "↑↓ choose · ←→ adjust budget, taxes, spending · Enter issue order · T release · Tab close",
+        Style::default().fg(MODERN_THEME.text_dim),
+    )));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+        area,
+    );
+}
@@ -102,7 +102,7 @@ pub fn render_control_deck(
             Span::styled(
                 control
                     .pinned_nation
-                    .map(|n| n.name().to_string())
+                    .map(|n| snapshot.nations.name(n).to_string())
                     .unwrap_or_else(|| "None".to_string()),
                 Style::default().fg(Color::Magenta),
             ),
@@ -110,17 +110,18 @@ pub fn render_control_deck(
             Span::styled(
                 control
                     .selected_owner
-                    .map(|n| n.name().to_string())
+                    .map(|n| snapshot.nations.name(n).to_string())
                     .unwrap_or_else(|| "None".to_string()),
                 Style::default().fg(Color::LightGreen),
             ),
         ]),
         Line::from(format!(
-            "Stage {} | Extinction {} | Hex {} | Entities {}",
+            "Stage {} | Extinction {} | Hex {} | Entities {} | Seed {}",
             snapshot.geologic_stage,
             snapshot.extinction_events,
             snapshot.grid.hexes.len(),
-            snapshot.entities.len()
+            snapshot.entities.len(),
+            snapshot.seed
         )),
         Line::from(vec![
             Span::styled("Hotkeys:", Style::default().fg(Color::Yellow)),
@@ -144,9 +145,18 @@ pub fn render_control_deck(
             Span::styled("C", Style::default().fg(Color::LightCyan)),
             Span::raw(" Pin selection  "),
             Span::styled("V", Style::default().fg(Color::LightGreen)),
-            Span::raw(" Focus toggle"),
+            Span::raw(" Focus toggle  "),
+            Span::styled("T/Tab", Style::default().fg(Color::LightMagenta)),
+            Span::raw(" Take command/Command panel  "),
+            Span::styled("I", Style::default().fg(Color::Yellow)),
+            Span::raw(" Intervene  "),
+            Span::styled("F5/F9", Style::default().fg(Color::LightBlue)),
+            Span::raw(" Quicksave/Quickload"),
         ]),
-        Line::from("Mouse: top-left [-][+][R] buttons usable"),
+        Line::from(match &control.persistence_status {
+            Some(status) => format!("Mouse: top-left [-][+][R] buttons usable | {status}"),
+            None => "Mouse: top-left [-][+][R] buttons usable".to_string(),
+        }),
     ];
     let status_paragraph = Paragraph::new(status_lines).wrap(Wrap { trim: true });
     frame.render_widget(status_paragraph, columns[0]);
@@ -211,7 +221,7 @@ pub fn render_control_deck(
     let legend_lines = vec![
         Line::from(vec![
             Span::styled("Map", Style::default().fg(Color::White).bold()),
-            Span::raw(" ◆ Leader | █ Territory | ✸ Front | ◎ Nuke "),
+            Span::raw(" ◆ Leader | █ Territory | ♜ Army | ✸ Front | ◎ Nuke "),
         ]),
         Line::from(vec![
             Span::raw("≈ Sea | ░ Ice | Mode "),
@@ -222,15 +232,16 @@ pub fn render_control_deck(
             Span::raw("  [ [ ] ] toggle"),
         ]),
         Line::from(format!(
-            "Sea {:.0}% | Ice {:.0}% | Fronts {}",
+            "Sea {:.0}% | Ice {:.0}% | Fronts {} | Armies {}",
             snapshot.overlay.sea_level * 100.0,
             snapshot.overlay.ice_line * 100.0,
-            snapshot.combat_hexes.len()
+            snapshot.combat_hexes.len(),
+            snapshot.armies.len()
         )),
         Line::from(match control.selected_owner {
             Some(nation) => format!(
                 "Selected hex: {} | Front {} | Nuke {}",
-                nation.name(),
+                snapshot.nations.name(nation),
                 if control
                     .selected_hex
                     .map(|c| snapshot.combat_hexes.contains(&c))
@@ -258,12 +269,7 @@ pub fn render_control_deck(
                 "Alliances {} · Sanctions {} · Trust {:.0}",
                 snapshot.diplomacy.alliances.len(),
                 snapshot.diplomacy.sanctions.len(),
-                snapshot
-                    .diplomacy
-                    .trust
-                    .iter()
-                    .map(|(_, v)| *v)
-                    .sum::<f32>()
+                snapshot.diplomacy.trust.values().sum::<f32>()
                     / snapshot.diplomacy.trust.len().max(1) as f32
             )),
             Span::raw(if control.focus_mode {
@@ -0,0 +1,183 @@
+use ratatui::{
+    prelude::*,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Wrap},
+};
+
+use super::{ControlState, MODERN_THEME};
+use crate::simulation::{Catastrophe, Faction, Intervention, Metric, Nation, ObserverSnapshot};
+
+/// Factor one Left/Right press scales the chosen metric by.
+pub const METRIC_SCALE: f64 = 1.25;
+/// Carbon one press of Enter on the carbon row adds, in ppm.
+pub const CARBON_STEP: f32 = 50.0;
+
+/// One row of the intervention panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterventionRow {
+    Catastrophe(Catastrophe),
+    ForceWar,
+    Metric,
+    SpawnNpc,
+    RemoveNpc,
+    RaiseCarbon,
+    GiftTech,
+}
+
+impl InterventionRow {
+    pub const ALL: [InterventionRow; 9] = [
+        InterventionRow::Catastrophe(Catastrophe::Meteor),
+        InterventionRow::Catastrophe(Catastrophe::GammaRayBurst),
+        InterventionRow::Catastrophe(Catastrophe::Supervolcano),
+        InterventionRow::ForceWar,
+        InterventionRow::Metric,
+        InterventionRow::SpawnNpc,
+        InterventionRow::RemoveNpc,
+        InterventionRow::RaiseCarbon,
+        InterventionRow::GiftTech,
+    ];
+
+    /// The intervention Enter issues on this row. Nation rows act on the
+    /// nation selected on the map; a forced war is declared by the pinned
+    /// nation. The metric row is adjusted with Left/Right instead.
+    pub fn order(
+        self,
+        snapshot: &ObserverSnapshot,
+        control: &ControlState,
+    ) -> Option<Intervention> {
+        let selected = control.selected_owner;
+        match self {
+            InterventionRow::Catastrophe(catastrophe) => {
+                Some(Intervention::Catastrophe { catastrophe })
+            }
+            InterventionRow::ForceWar => Some(Intervention::ForceWar {
+                attacker: control.pinned_nation?,
+                defender: selected?,
+            }),
+            InterventionRow::Metric => None,
+            InterventionRow::SpawnNpc => Some(Intervention::SpawnNpc {
+                nation: selected?,
+                faction: Faction::SettlersUnion,
+                name: None,
+            }),
+            InterventionRow::RemoveNpc => snapshot
+                .entities
+                .iter()
+                .map(|entity| entity.id)
+                .max()
+                .map(|id| Intervention::RemoveNpc { id }),
+            InterventionRow::RaiseCarbon => Some(Intervention::RaiseCarbon { ppm: CARBON_STEP }),
+            InterventionRow::GiftTech => Some(Intervention::GiftTech {
+                nation: selected?,
+                tech: None,
+            }),
+        }
+    }
+}
+
+/// Raises (or lowers) the selected nation's chosen metric by [`METRIC_SCALE`].
+pub fn scale_metric(
+    snapshot: &ObserverSnapshot,
+    control: &ControlState,
+    raise: bool,
+) -> Option<Intervention> {
+    let nation = control.selected_owner?;
+    let metric = control.intervene_metric;
+    let current = metric.value(snapshot.all_metrics.0.get(&nation)?);
+    let value = if raise {
+        (current * METRIC_SCALE).max(1.0)
+    } else {
+        current / METRIC_SCALE
+    };
+    Some(Intervention::SetMetric {
+        nation,
+        metric,
+        value,
+    })
+}
+
+/// The metric after `metric` in [`Metric::ALL`], wrapping around.
+pub fn next_metric(metric: Metric) -> Metric {
+    let index = Metric::ALL.iter().position(|m| *m == metric).unwrap_or(0);
+    Metric::ALL[(index + 1) % Metric::ALL.len()]
+}
+
+/// Draws the observer's intervention panel over `area`.
+pub fn render_intervention_panel(
+    frame: &mut Frame,
+    area: Rect,
+    snapshot: &ObserverSnapshot,
+    control: &ControlState,
+) {
+    let block = Block::bordered()
+        .style(Style::default().bg(MODERN_THEME.panel_bg))
+        .title(" INTERVENE ")
+        .title_style(Style::default().fg(MODERN_THEME.warning).bold())
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(MODERN_THEME.warning));
+
+    let name =
+        |nation: Option<Nation>| nation.map_or("(none)", |nation| snapshot.nations.name(nation));
+    let selected = name(control.selected_owner);
+    let mut lines = vec![
+        Line::from(format!(
+            "Selected {} | Pinned {}",
+            selected,
+            name(control.pinned_nation)
+        )),
+        Line::from(""),
+    ];
+    for (index, row) in InterventionRow::ALL.into_iter().enumerate() {
+        let marker = if index == control.intervene_row {
+            "▶ "
+        } else {
+            "  "
+        };
+        let text = match row {
+            InterventionRow::Catastrophe(catastrophe) => catastrophe.label().to_string(),
+            InterventionRow::ForceWar => {
+                format!("Force war: {} on {}", name(control.pinned_nation), selected)
+            }
+            InterventionRow::Metric => {
+                let metric = control.intervene_metric;
+                let value = control
+                    .selected_owner
+                    .and_then(|nation| snapshot.all_metrics.0.get(&nation))
+                    .map_or(0.0, |m| metric.value(m));
+                format!("{} of {}: {:.1}", metric.label(), selected, value)
+            }
+            InterventionRow::SpawnNpc => format!("Spawn an NPC in {selected}"),
+            InterventionRow::RemoveNpc => format!(
+                "Remove NPC {}",
+                snapshot
+                    .entities
+                    .iter()
+                    .max_by_key(|entity| entity.id)
+                    .map_or("(none)", |entity| entity.name.as_str())
+            ),
+            InterventionRow::RaiseCarbon => format!(
+                "Raise carbon +{CARBON_STEP:.0} ppm (now {:.0})",
+                snapshot.science_victory.carbon_ppm
+            ),
+            InterventionRow::GiftTech => format!("Gift a tech to {selected}"),
+        };
+        let style = if index == control.intervene_row {
+            Style::default().fg(MODERN_THEME.accent_a).bold()
+        } else {
+            Style::default().fg(MODERN_THEME.text_main)
+        };
+        lines.push(Line::from(Span::styled(format!("{marker}{text}"), style)));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑↓ choose · Enter apply (next metric) · ←→ lower/raise metric · I close",
+        Style::default().fg(MODERN_THEME.text_dim),
+    )));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+        area,
+    );
+}
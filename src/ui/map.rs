@@ -1,6 +1,6 @@
 use ratatui::{prelude::*, style::Stylize, widgets::Widget};
 
-use crate::simulation::{AxialCoord, Nation, ObserverSnapshot};
+use crate::simulation::{AxialCoord, Biome, Nation, ObserverSnapshot};
 use crate::ui::{MODERN_THEME, MapOverlay};
 
 const WORLD_ATLAS: &str = r#"
@@ -117,6 +117,17 @@ impl<'a> Widget for MapWidget<'a> {
                             color = Color::White;
                         }
                     }
+                    MapOverlay::Trade => {
+                        // Dim the land so the routes stand out.
+                        color = if is_land {
+                            Color::Rgb(70, 80, 70)
+                        } else {
+                            Color::Rgb(40, 60, 90)
+                        };
+                        if !is_land && norm_y > sea_level {
+                            base_char = "≈";
+                        }
+                    }
                     MapOverlay::Conflict => {
                         let fatigue_norm =
                             (self.snapshot.overlay.war_fatigue / 100.0).clamp(0.0, 1.2);
@@ -159,16 +170,40 @@ impl<'a> Widget for MapWidget<'a> {
             {
                 continue;
             }
-            let mut style = Style::default().fg(hex.owner.color()).bg(MODERN_THEME.bg);
-            if Some(hex.owner) == leader {
+            let Some(owner) = hex.owner else {
+                // Open water and unclaimed land show the terrain underneath.
+                let (glyph, color) = match hex.biome {
+                    Biome::Ocean => ("≈", Color::Rgb(40, 80, 150)),
+                    Biome::Coast => ("~", Color::Rgb(80, 140, 200)),
+                    Biome::Glacier | Biome::Tundra => ("░", Color::White),
+                    Biome::Mountain => ("▲", Color::Gray),
+                    _ => ("▒", Color::DarkGray),
+                };
+                let glyph = if self.selected_hex == Some(coord) {
+                    "◎"
+                } else {
+                    glyph
+                };
+                buf.set_string(
+                    screen_x as u16,
+                    screen_y as u16,
+                    glyph,
+                    Style::default().fg(color).bg(MODERN_THEME.bg),
+                );
+                continue;
+            };
+            let mut style = Style::default()
+                .fg(self.snapshot.nations.color(owner))
+                .bg(MODERN_THEME.bg);
+            if Some(owner) == leader {
                 style = style.bold();
             }
-            if Some(hex.owner) == self.focus {
+            if Some(owner) == self.focus {
                 style = style.fg(Color::White).bold();
             }
             let glyph = if self.selected_hex == Some(coord) {
                 "◎"
-            } else if Some(hex.owner) == leader {
+            } else if Some(owner) == leader {
                 "◆"
             } else {
                 "█"
@@ -212,5 +247,83 @@ impl<'a> Widget for MapWidget<'a> {
                 buf.set_string(screen_x as u16, screen_y as u16, glyph, style);
             }
         }
+
+        // Trade routes run capital to capital, brighter the more they carry;
+        // routes of the focused nation are drawn bold.
+        if self.overlay == MapOverlay::Trade {
+            let busiest = self
+                .snapshot
+                .trade_routes
+                .iter()
+                .map(|route| route.volume)
+                .fold(1.0_f32, f32::max);
+            for route in &self.snapshot.trade_routes {
+                let (Some(a), Some(b)) = (
+                    self.snapshot.nations.get(route.a),
+                    self.snapshot.nations.get(route.b),
+                ) else {
+                    continue;
+                };
+                let from = (
+                    center_x as i32 + a.capital.q * 2 + a.capital.r,
+                    center_y as i32 + a.capital.r,
+                );
+                let to = (
+                    center_x as i32 + b.capital.q * 2 + b.capital.r,
+                    center_y as i32 + b.capital.r,
+                );
+                let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+                let glyph = if dy == 0 {
+                    "─"
+                } else if dx.abs() < dy.abs() {
+                    "│"
+                } else if (dx > 0) == (dy > 0) {
+                    "╲"
+                } else {
+                    "╱"
+                };
+                let share = (route.volume / busiest).clamp(0.0, 1.0);
+                let level = (90.0 + share * 165.0) as u8;
+                let mut style = Style::default()
+                    .fg(Color::Rgb(level, level, 40))
+                    .bg(MODERN_THEME.bg);
+                if self.focus.is_some_and(|focus| route.links(focus)) {
+                    style = style.bold();
+                }
+                let steps = dx.abs().max(dy.abs());
+                for step in 1..steps {
+                    let x = from.0 + dx * step / steps;
+                    let y = from.1 + dy * step / steps;
+                    if x < area.x as i32
+                        || x >= (area.x + area.width) as i32
+                        || y < area.y as i32
+                        || y >= (area.y + area.height) as i32
+                    {
+                        continue;
+                    }
+                    buf.set_string(x as u16, y as u16, glyph, style);
+                }
+            }
+        }
+
+        // Armies sit on top of everything; those on campaign are drawn bold.
+        for army in &self.snapshot.armies {
+            let screen_x = center_x as i32 + army.coord.q * 2 + army.coord.r;
+            let screen_y = center_y as i32 + army.coord.r;
+            if screen_x < area.x as i32
+                || screen_x >= (area.x + area.width) as i32
+                || screen_y < area.y as i32
+                || screen_y >= (area.y + area.height) as i32
+            {
+                continue;
+            }
+            let mut style = Style::default()
+                .fg(self.snapshot.nations.color(army.nation))
+                .bg(MODERN_THEME.bg);
+            if army.enemy.is_some() {
+                style = style.bold();
+            }
+            buf.set_string(screen_x as u16, screen_y as u16, "♜", style);
+        }
     }
 }
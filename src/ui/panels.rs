@@ -9,9 +9,12 @@ use ratatui::{
 
 use super::{ControlState, MODERN_THEME};
 use crate::simulation::events::WorldEventKind;
-use crate::simulation::{Nation, ObserverSnapshot, format_number_commas};
+use crate::simulation::{Nation, ObserverSnapshot, StrategicAction, format_number_commas};
 use crate::ui::charts::{heat_bar, render_evolutionary_charts, render_science_progress_panel};
 
+/// Nation detail columns shown side by side before the panel gets unreadable.
+const MAX_NATION_COLUMNS: usize = 6;
+
 pub fn render_world_state_panel(
     frame: &mut Frame,
     area: Rect,
@@ -47,7 +50,7 @@ pub fn render_world_state_panel(
     let leader_name = snapshot
         .science_victory
         .leader
-        .map(|n| n.name().to_string())
+        .map(|n| snapshot.nations.name(n).to_string())
         .unwrap_or_else(|| "TBD".to_string());
     let leader_progress = snapshot
         .science_victory
@@ -154,7 +157,7 @@ pub fn render_world_state_panel(
             Some(hex) => {
                 let owner = control
                     .selected_owner
-                    .map(|n| n.name().to_string())
+                    .map(|n| snapshot.nations.name(n).to_string())
                     .unwrap_or_else(|| "Unclaimed".to_string());
                 let war = snapshot.combat_hexes.contains(&hex);
                 let nuke = snapshot.nuclear_hexes.contains(&hex);
@@ -182,9 +185,11 @@ pub fn render_world_state_panel(
     if let Some(focus) = control.pinned_nation.or(control.selected_owner) {
         nations.sort_by_key(|n| if *n == focus { 0 } else { 1 });
     } else {
-        nations.sort_by_key(|a| a.name());
+        nations.sort_by(|a, b| snapshot.nations.name(*a).cmp(snapshot.nations.name(*b)));
     }
 
+    // Large scenarios show the focused nation first and drop the rest.
+    nations.truncate(MAX_NATION_COLUMNS);
     let nations_len = nations.len().max(1) as u32;
     let constraints: Vec<Constraint> = (0..nations.len())
         .map(|_| Constraint::Ratio(1, nations_len))
@@ -200,7 +205,7 @@ pub fn render_world_state_panel(
         }
 
         if let Some(metrics) = snapshot.all_metrics.0.get(&nation) {
-            let nation_color = nation.color();
+            let nation_color = snapshot.nations.color(nation);
             let is_selected = control.selected_owner == Some(nation);
             let mut nation_lines = vec![];
             let pin_label = if control.pinned_nation == Some(nation) {
@@ -209,7 +214,7 @@ pub fn render_world_state_panel(
                 ""
             };
             nation_lines.push(Line::from(Span::styled(
-                nation.name(),
+                snapshot.nations.name(nation).to_string(),
                 Style::default()
                     .bold()
                     .underlined()
@@ -228,6 +233,43 @@ pub fn render_world_state_panel(
             }
 
             add_diplomacy_lines(snapshot, &mut nation_lines, nation);
+            if snapshot.player.nation == Some(nation) {
+                let budget = snapshot
+                    .treasuries
+                    .get(&nation)
+                    .map(|treasury| treasury.budget)
+                    .unwrap_or_default();
+                nation_lines.push(Line::from(Span::styled(
+                    format!(
+                        "  Player command | Budget M{} S{} C{} D{} W{}",
+                        budget.military,
+                        budget.science,
+                        budget.culture,
+                        budget.diplomacy,
+                        budget.welfare
+                    ),
+                    Style::default().fg(Color::LightMagenta),
+                )));
+            } else if let Some(strategy) = snapshot.strategies.0.get(&nation) {
+                let action = match strategy.last_action {
+                    StrategicAction::DeclareWar { target } => {
+                        format!("Declare war on {}", snapshot.nations.name(target))
+                    }
+                    StrategicAction::Sanction { target } => {
+                        format!("Sanction {}", snapshot.nations.name(target))
+                    }
+                    other => other.kind().label().to_string(),
+                };
+                nation_lines.push(Line::from(Span::styled(
+                    format!(
+                        "  AI: {} | Goal {} | {}",
+                        strategy.profile.label(),
+                        strategy.goal.label(),
+                        action
+                    ),
+                    Style::default().fg(Color::LightMagenta),
+                )));
+            }
             nation_lines.push(Line::from(Span::styled(
                 format!(
                     "  Era: {} | Weapon: {}",
@@ -240,6 +282,134 @@ pub fn render_world_state_panel(
                 format!("  Population: {}", format_number_commas(metrics.population)),
                 Style::default().fg(Color::White),
             )));
+            nation_lines.push(Line::from(Span::styled(
+                format!(
+                    "  Output: Food {:.0} | Energy {:.0} | Rare {:.0}",
+                    metrics.output.food, metrics.output.energy, metrics.output.rare
+                ),
+                Style::default().fg(Color::LightGreen),
+            )));
+            if let Some(chain) = snapshot.supply.get(&nation) {
+                let net = chain.imports - chain.exports;
+                let style = if chain.deficit_ticks > 0 {
+                    Style::default().fg(MODERN_THEME.danger)
+                } else {
+                    Style::default().fg(Color::LightGreen)
+                };
+                nation_lines.push(Line::from(Span::styled(
+                    format!(
+                        "  Stock: Food {:.0} | Energy {:.0} | Rare {:.0} | Net import {:+.1}/{:+.1}/{:+.1}{}",
+                        chain.stockpile.food,
+                        chain.stockpile.energy,
+                        chain.stockpile.rare,
+                        net.food,
+                        net.energy,
+                        net.rare,
+                        if chain.deficit_ticks > 0 {
+                            format!(" | Short {} ticks", chain.deficit_ticks)
+                        } else {
+                            String::new()
+                        }
+                    ),
+                    style,
+                )));
+            }
+            if let Some(market) = snapshot.markets.0.get(&nation) {
+                // The three goods whose prices sit furthest from their base.
+                let mut goods: Vec<_> = market.goods.values().collect();
+                goods.sort_by(|a, b| b.pressure().abs().total_cmp(&a.pressure().abs()));
+                let quotes = goods
+                    .iter()
+                    .take(3)
+                    .map(|good| {
+                        format!(
+                            "{} {:.1} ({:+.0}%)",
+                            good.item.name(),
+                            good.price,
+                            good.pressure() * 100.0
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                nation_lines.push(Line::from(Span::styled(
+                    format!("  Market: {quotes}"),
+                    Style::default().fg(Color::LightYellow),
+                )));
+            }
+            let routes: Vec<_> = snapshot
+                .trade_routes
+                .iter()
+                .filter(|route| route.links(nation))
+                .collect();
+            if !routes.is_empty() {
+                // The busiest partner and what mostly moves between them.
+                let busiest = routes
+                    .iter()
+                    .max_by(|a, b| a.volume.total_cmp(&b.volume))
+                    .map(|route| {
+                        format!(
+                            " | Top {} ({})",
+                            snapshot.nations.name(route.partner(nation)),
+                            route.main_good().unwrap_or("idle")
+                        )
+                    })
+                    .unwrap_or_default();
+                let income: f32 = routes.iter().map(|route| route.income(nation)).sum();
+                nation_lines.push(Line::from(Span::styled(
+                    format!(
+                        "  Trade: {} routes | +{:.2}/tick{}",
+                        routes.len(),
+                        income,
+                        busiest
+                    ),
+                    Style::default().fg(Color::LightYellow),
+                )));
+            }
+            if let Some(treasury) = snapshot.treasuries.get(&nation) {
+                let shut_out = if treasury.can_borrow(snapshot.tick) {
+                    String::new()
+                } else {
+                    format!(" | Shut out until {}", treasury.shut_out_until)
+                };
+                nation_lines.push(Line::from(Span::styled(
+                    format!(
+                        "  Treasury: Tax {:.0}% | {:+.2}/tick | Debt {:.1} ({:.0} ticks){}",
+                        treasury.tax_rate * 100.0,
+                        treasury.balance(),
+                        treasury.debt,
+                        treasury.debt_load(),
+                        shut_out
+                    ),
+                    Style::default().fg(if treasury.defaults > 0 {
+                        Color::LightRed
+                    } else {
+                        Color::LightYellow
+                    }),
+                )));
+            }
+            if let Some(economy) = snapshot.economies.get(&nation) {
+                let phase = match economy.crisis {
+                    Some(crisis) => format!(" | {}", crisis.kind.label()),
+                    None if economy.boom() > 0.0 => " | Credit boom".to_string(),
+                    None => String::new(),
+                };
+                nation_lines.push(Line::from(Span::styled(
+                    format!(
+                        "  Cycle: Gap {:+.1}% | Inflation {:.1}% | Jobless {:.1}% | Rate {:.1}% | Credit {:.0}%{}",
+                        economy.output_gap,
+                        economy.inflation,
+                        economy.unemployment,
+                        economy.policy_rate,
+                        economy.credit * 100.0,
+                        phase
+                    ),
+                    Style::default().fg(if economy.crisis.is_some() {
+                        Color::LightRed
+                    } else {
+                        Color::LightYellow
+                    }),
+                )));
+            }
             if let Some(civ_state) = snapshot.civ_state.0.get(&nation) {
                 nation_lines.push(Line::from(Span::styled(
                     format!(
@@ -263,6 +433,16 @@ pub fn render_world_state_panel(
                 format!("  Techs: {}", tech_list),
                 Style::default().fg(Color::White),
             )));
+            if let Some(target) = metrics.research_target {
+                nation_lines.push(Line::from(Span::styled(
+                    format!(
+                        "  Researching: {} ({:.0} stock)",
+                        target.label(),
+                        metrics.research_stock
+                    ),
+                    Style::default().fg(Color::LightBlue),
+                )));
+            }
 
             if metrics.is_destroyed {
                 nation_lines.push(Line::from(Span::styled(
@@ -326,20 +506,7 @@ fn add_diplomacy_lines(
     lines: &mut Vec<Line<'static>>,
     nation: Nation,
 ) {
-    let trust = snapshot
-        .diplomacy
-        .trust
-        .iter()
-        .find(|(n, _)| *n == nation)
-        .map(|(_, v)| *v)
-        .unwrap_or(40.0);
-    let fear = snapshot
-        .diplomacy
-        .fear
-        .iter()
-        .find(|(n, _)| *n == nation)
-        .map(|(_, v)| *v)
-        .unwrap_or(35.0);
+    let diplomacy = &snapshot.diplomacy;
     let leaning = snapshot
         .overlay
         .ideology_leaning
@@ -361,13 +528,65 @@ fn add_diplomacy_lines(
         .find(|(n, _)| *n == nation)
         .map(|(_, v)| *v)
         .unwrap_or(20.0);
+    // How far the others trust this nation, on average.
+    let trusted: Vec<f32> = diplomacy
+        .trust
+        .iter()
+        .filter(|((_, subject), _)| *subject == nation)
+        .map(|(_, v)| *v)
+        .collect();
+    let trusted = trusted.iter().sum::<f32>() / trusted.len().max(1) as f32;
+    let feared = diplomacy
+        .fear
+        .iter()
+        .filter(|((holder, _), _)| *holder == nation)
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|((_, subject), fear)| format!("{} {:.0}", snapshot.nations.name(*subject), fear))
+        .unwrap_or_else(|| "none".to_string());
     lines.push(Line::from(Span::styled(
         format!(
-            "  Trust {:.0} | Fear {:.0} | Ideology {:.0} / Cohesion {:.0} / Volatility {:.0}",
-            trust, fear, leaning, cohesion, volatility
+            "  Trusted {:.0} | Fears {} | Ideology {:.0} / Cohesion {:.0} / Volatility {:.0}",
+            trusted, feared, leaning, cohesion, volatility
         ),
         Style::default().fg(Color::Gray),
     )));
+
+    // This nation's row of the opinion matrix.
+    let mut row = vec![Span::styled("  Opinion ", Style::default().fg(Color::Gray))];
+    for ((_, subject), opinion) in diplomacy
+        .opinions
+        .iter()
+        .filter(|((holder, _), _)| *holder == nation)
+    {
+        let name: String = snapshot.nations.name(*subject).chars().take(3).collect();
+        row.push(Span::styled(
+            format!("{name} {opinion:+.0} "),
+            Style::default().fg(snapshot.nations.color(*subject)),
+        ));
+    }
+    lines.push(Line::from(row));
+
+    if let Some(memory) = diplomacy
+        .memories
+        .iter()
+        .filter(|memory| memory.holder == nation)
+        .max_by(|a, b| a.weight.abs().total_cmp(&b.weight.abs()))
+    {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  Remembers: {} {} at tick {} ({:+.0})",
+                snapshot.nations.name(memory.subject),
+                memory.kind.label(),
+                memory.tick,
+                memory.weight
+            ),
+            Style::default().fg(if memory.weight < 0.0 {
+                Color::LightRed
+            } else {
+                Color::LightGreen
+            }),
+        )));
+    }
 }
 
 fn push_metric_bar(lines: &mut Vec<Line<'static>>, label: &str, value: f32, color: Color) {
@@ -404,6 +623,7 @@ pub fn render_event_leaderboard(frame: &mut Frame, area: Rect, snapshot: &Observ
         "Trade",
         "Social",
         "MacroShock",
+        "Finance",
         "Science",
         "Space",
         "Era",
@@ -520,7 +740,9 @@ pub fn render_glory_tiles(frame: &mut Frame, area: Rect, snapshot: &ObserverSnap
     let mut war_wins: HashMap<String, u32> = HashMap::new();
     for event in snapshot.events.iter().rev().take(200) {
         if let WorldEventKind::Warfare { winner, .. } = &event.kind {
-            *war_wins.entry(winner.name().to_string()).or_default() += 1;
+            *war_wins
+                .entry(snapshot.nations.name(*winner).to_string())
+                .or_default() += 1;
         }
     }
     let war_champ = war_wins
@@ -532,21 +754,27 @@ pub fn render_glory_tiles(frame: &mut Frame, area: Rect, snapshot: &ObserverSnap
         (
             "Population Peak",
             top_pop
-                .map(|(n, pop)| format!("{} | {}", n.name(), format_number_commas(pop)))
+                .map(|(n, pop)| {
+                    format!(
+                        "{} | {}",
+                        snapshot.nations.name(*n),
+                        format_number_commas(pop)
+                    )
+                })
                 .unwrap_or_else(|| "Data None".to_string()),
             Color::LightCyan,
         ),
         (
             "Economic Hegemon",
             top_gdp
-                .map(|(n, m)| format!("{} | Economy {:.1}", n.name(), m.economy))
+                .map(|(n, m)| format!("{} | Economy {:.1}", snapshot.nations.name(*n), m.economy))
                 .unwrap_or_else(|| "Data None".to_string()),
             Color::LightGreen,
         ),
         (
             "Science Leader",
             science_leader
-                .map(|(n, p)| format!("{} | {:.1}% Moon", n.name(), p))
+                .map(|(n, p)| format!("{} | {:.1}% Moon", snapshot.nations.name(n), p))
                 .unwrap_or_else(|| "TBD".to_string()),
             Color::Yellow,
         ),
@@ -597,6 +825,32 @@ pub fn render_war_theater_panel(frame: &mut Frame, area: Rect, snapshot: &Observ
         ),
     ]));
 
+    for war in snapshot.wars.iter().take(3) {
+        let allies = war.attackers.len() + war.defenders.len() - 2;
+        lines.push(Line::from(vec![
+            Span::styled(
+                snapshot.nations.name(war.attackers[0]).to_string(),
+                Style::default().fg(snapshot.nations.color(war.attackers[0])),
+            ),
+            Span::raw(" vs "),
+            Span::styled(
+                snapshot.nations.name(war.defenders[0]).to_string(),
+                Style::default().fg(snapshot.nations.color(war.defenders[0])),
+            ),
+            Span::raw(format!(
+                "{} · {} · score {:+.0} · exhaustion {:.0}",
+                if allies > 0 {
+                    format!(" (+{allies} allies)")
+                } else {
+                    String::new()
+                },
+                war.casus_belli.label(),
+                war.score,
+                war.exhaustion
+            )),
+        ]));
+    }
+
     let mut armies: Vec<_> = snapshot
         .all_metrics
         .0
@@ -605,9 +859,31 @@ pub fn render_war_theater_panel(frame: &mut Frame, area: Rect, snapshot: &Observ
         .collect();
     armies.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
     for (nation, mil, terr) in armies.into_iter().take(3) {
+        let fielded = snapshot.armies.iter().filter(|a| a.nation == *nation);
+        let campaigning = fielded.clone().filter(|a| a.enemy.is_some()).count();
+        let arsenal = snapshot
+            .arsenals
+            .0
+            .get(nation)
+            .map(|arsenal| {
+                format!(
+                    " / Warheads {} ({})",
+                    arsenal.warheads,
+                    arsenal.doctrine.label()
+                )
+            })
+            .unwrap_or_default();
         lines.push(Line::from(Span::styled(
-            format!("{} Mil {:.1} / Terr {:.1}", nation.name(), mil, terr),
-            Style::default().fg(nation.color()),
+            format!(
+                "{} Mil {:.1} / Terr {:.1} / Armies {} ({} afield){}",
+                snapshot.nations.name(*nation),
+                mil,
+                terr,
+                fielded.count(),
+                campaigning,
+                arsenal
+            ),
+            Style::default().fg(snapshot.nations.color(*nation)),
         )));
     }
 
@@ -625,8 +901,8 @@ pub fn render_war_theater_panel(frame: &mut Frame, area: Rect, snapshot: &Observ
             } = &e.kind
             {
                 Some((
-                    winner.name().to_string(),
-                    loser.name().to_string(),
+                    snapshot.nations.name(*winner).to_string(),
+                    snapshot.nations.name(*loser).to_string(),
                     *nuclear,
                     *casualties,
                 ))